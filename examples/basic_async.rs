@@ -16,10 +16,12 @@ async fn main() {
 
     let mut scheduler = Scheduler::tokio_scheduler_with_current();
 
-    let id = scheduler.add_async_task(
-        MyTask::default(),
-        IntervalSchedule::every(Duration::from_secs(1)),
-    );
+    let id = scheduler
+        .add_async_task(
+            MyTask::default(),
+            IntervalSchedule::every(Duration::from_secs(1)),
+        )
+        .expect("Should be able to register a task");
 
     info!("Starting tasks, press enter to stop them.");
     let mut buf = String::new();
@@ -44,7 +46,11 @@ struct MyTask {
 impl AsyncTask for MyTask {
     async fn run(&self) {
         info!("MyTask is running");
-        if self.counter.fetch_add(1, Ordering::Relaxed) % 5 == 0 {
+        if self
+            .counter
+            .fetch_add(1, Ordering::Relaxed)
+            .is_multiple_of(5)
+        {
             panic!("My task panicked!");
         }
     }
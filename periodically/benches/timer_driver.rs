@@ -0,0 +1,177 @@
+//! Compares driving tasks with a nanny per task against [Scheduler::with_timer_driver].
+//!
+//! Run with `cargo bench --bench timer_driver`. Nannies are only benchmarked up to 100k tasks,
+//!   since a million of them takes gigabytes of memory. Besides timings, the memory taken up by
+//!   each waiting task is printed, as counted by the allocator.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use periodically::{AsyncTask, IntervalSchedule, OneShot, Scheduler};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{channel, Sender},
+        Arc,
+    },
+    thread::sleep,
+    time::{Duration, Instant},
+};
+use tokio::runtime::Runtime;
+
+const SIZES: [usize; 3] = [10_000, 100_000, 1_000_000];
+
+const MAX_NANNIES: usize = 100_000;
+
+/// Keeps count of the bytes allocated by the benchmarks, and not yet freed.
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+// only keeps count, and leaves the allocating to the system allocator
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let allocation = System.alloc(layout);
+        if !allocation.is_null() {
+            ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        }
+        allocation
+    }
+
+    unsafe fn dealloc(&self, allocation: *mut u8, layout: Layout) {
+        System.dealloc(allocation, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, allocation: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let reallocation = System.realloc(allocation, layout, new_size);
+        if !reallocation.is_null() {
+            ALLOCATED.fetch_add(new_size, Ordering::Relaxed);
+            ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+        reallocation
+    }
+}
+
+/// A task which never gets to run within a benchmark.
+struct Idle;
+
+impl AsyncTask for Idle {
+    async fn run(&self) {}
+}
+
+/// Lets the benchmark know once every task has run.
+struct Countdown {
+    remaining: Arc<AtomicUsize>,
+    done: Sender<()>,
+}
+
+impl AsyncTask for Countdown {
+    async fn run(&self) {
+        if self.remaining.fetch_sub(1, Ordering::AcqRel) == 1 {
+            let _ = self.done.send(());
+        }
+    }
+}
+
+fn scheduler(driven: bool) -> Scheduler {
+    let scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    match driven {
+        true => scheduler.with_timer_driver(),
+        false => scheduler,
+    }
+}
+
+fn drivers(size: usize) -> impl Iterator<Item = (&'static str, bool)> {
+    [("nanny", false), ("timer_driver", true)]
+        .into_iter()
+        .filter(move |(_, driven)| *driven || size <= MAX_NANNIES)
+}
+
+/// How long it takes to register tasks which are waiting on a timer.
+fn register_idle(c: &mut Criterion) {
+    let mut group = c.benchmark_group("register_idle");
+    group.sample_size(10);
+    for size in SIZES {
+        for (name, driven) in drivers(size) {
+            group.bench_with_input(BenchmarkId::new(name, size), &size, |b, &size| {
+                b.iter_custom(|iters| {
+                    let mut elapsed = Duration::ZERO;
+                    for _ in 0..iters {
+                        let mut scheduler = scheduler(driven);
+                        let started = Instant::now();
+                        for _ in 0..size {
+                            let schedule = IntervalSchedule::every(Duration::from_secs(60 * 60));
+                            scheduler.add_async_task(Idle, schedule).unwrap();
+                        }
+                        elapsed += started.elapsed();
+                    }
+                    elapsed
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+/// How much memory tasks which are waiting on a timer take up. Printed rather than benchmarked,
+///   since criterion only measures time.
+fn memory_idle(_: &mut Criterion) {
+    for size in SIZES {
+        for (name, driven) in drivers(size) {
+            let mut scheduler = scheduler(driven);
+            let before = ALLOCATED.load(Ordering::Relaxed);
+            for _ in 0..size {
+                let schedule = IntervalSchedule::every(Duration::from_secs(60 * 60));
+                scheduler.add_async_task(Idle, schedule).unwrap();
+            }
+            // lets the nannies start waiting, which is when their timers are set up
+            sleep(Duration::from_millis(500));
+            let allocated = ALLOCATED.load(Ordering::Relaxed).saturating_sub(before);
+            println!(
+                "memory_idle/{name}/{size}: {} bytes in total, {} bytes per task",
+                allocated,
+                allocated / size
+            );
+        }
+    }
+}
+
+/// How long it takes to register tasks which are due straight away, and for all of them to run.
+fn run_once(c: &mut Criterion) {
+    let mut group = c.benchmark_group("run_once");
+    group.sample_size(10);
+    for size in SIZES {
+        for (name, driven) in drivers(size) {
+            group.bench_with_input(BenchmarkId::new(name, size), &size, |b, &size| {
+                b.iter_custom(|iters| {
+                    let mut elapsed = Duration::ZERO;
+                    for _ in 0..iters {
+                        let mut scheduler = scheduler(driven);
+                        let remaining = Arc::new(AtomicUsize::new(size));
+                        let (done, all_done) = channel();
+                        let started = Instant::now();
+                        for _ in 0..size {
+                            let task = Countdown {
+                                remaining: remaining.clone(),
+                                done: done.clone(),
+                            };
+                            scheduler
+                                .add_async_task(task, OneShot::after(Duration::ZERO))
+                                .unwrap();
+                        }
+                        all_done.recv().unwrap();
+                        elapsed += started.elapsed();
+                    }
+                    elapsed
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, register_idle, run_once, memory_idle);
+criterion_main!(benches);
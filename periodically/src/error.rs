@@ -0,0 +1,100 @@
+use crate::{DependencyError, TaskIdentifier};
+use std::fmt;
+
+/// Errors returned by a [`Scheduler`][crate::Scheduler].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The task is not registered with this scheduler, eg; because it has already been cancelled.
+    UnknownTask(TaskIdentifier),
+    /// The task was registered with a different scheduler.
+    ForeignTask(TaskIdentifier),
+    /// The task is no longer registered, and a newer task has since taken over its slot.
+    ///   See [`TaskIdentifier`].
+    StaleTask(TaskIdentifier),
+    /// The task's schedule already finished, so the task is no longer running.
+    AlreadyFinished(TaskIdentifier),
+    /// The runtime which the scheduler runs tasks on has shut down.
+    RuntimeShutDown,
+    /// The scheduler behind a [`SchedulerHandle`][crate::SchedulerHandle] has been dropped.
+    SchedulerDropped,
+    /// A task with the same name is already registered.
+    DuplicateName(String),
+    /// The schedule could not be created, eg; because a cron expression is invalid.
+    InvalidSchedule(Box<dyn std::error::Error + Send + Sync>),
+    /// The task's dependencies are invalid.
+    Dependency(DependencyError),
+    /// The options a task was registered with are invalid. See [`TaskBuilder`][crate::TaskBuilder].
+    InvalidOptions(String),
+    /// The task's outputs were requested as a different type than the task outputs.
+    OutputType(TaskIdentifier),
+    /// The outputs of a [local task][crate::Scheduler::add_local_task] were subscribed to, which they
+    ///   can not be since they need not be [`Send`].
+    LocalOutputs(TaskIdentifier),
+    /// The thread of a task running on a [`SyncExecutor::DedicatedThread`][crate::SyncExecutor::DedicatedThread]
+    ///   could not be spawned.
+    ThreadSpawn(std::io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownTask(id) => write!(f, "task [{id}] is not registered"),
+            Error::ForeignTask(id) => write!(f, "task [{id}] belongs to a different scheduler"),
+            Error::StaleTask(id) => {
+                write!(
+                    f,
+                    "task [{id}] is stale, its slot was reused by a newer task"
+                )
+            }
+            Error::AlreadyFinished(id) => write!(f, "task [{id}] has already finished"),
+            Error::RuntimeShutDown => write!(f, "the scheduler's runtime has shut down"),
+            Error::SchedulerDropped => write!(f, "the scheduler has been dropped"),
+            Error::DuplicateName(name) => {
+                write!(f, "a task named [{name}] is already registered")
+            }
+            Error::InvalidSchedule(err) => write!(f, "invalid schedule: {err}"),
+            Error::Dependency(err) => write!(f, "{err}"),
+            Error::InvalidOptions(reason) => write!(f, "invalid task options: {reason}"),
+            Error::OutputType(id) => write!(f, "task [{id}] outputs a different type"),
+            Error::LocalOutputs(id) => {
+                write!(
+                    f,
+                    "task [{id}] is a local task, its outputs can not be subscribed to"
+                )
+            }
+            Error::ThreadSpawn(err) => write!(f, "failed to spawn the task's thread: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::InvalidSchedule(err) => Some(err.as_ref()),
+            Error::Dependency(err) => Some(err),
+            Error::ThreadSpawn(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<DependencyError> for Error {
+    fn from(err: DependencyError) -> Self {
+        Error::Dependency(err)
+    }
+}
+
+#[cfg(feature = "cron")]
+impl From<crate::CronError> for Error {
+    fn from(err: crate::CronError) -> Self {
+        Error::InvalidSchedule(Box::new(err))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<crate::ScheduleSpecError> for Error {
+    fn from(err: crate::ScheduleSpecError) -> Self {
+        Error::InvalidSchedule(Box::new(err))
+    }
+}
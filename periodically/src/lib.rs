@@ -48,12 +48,34 @@
 //!   }
 //! }
 //! let mut scheduler = Scheduler::tokio_scheduler(runtime);
-//! scheduler.add_sync_task(MyTask {}, IntervalSchedule::every(Duration::from_secs(100)));
+//! scheduler.add_sync_task(MyTask {}, IntervalSchedule::every(Duration::from_secs(100))).unwrap();
 //! ```
 //!
 //! [`Scheduler`] executes tasks in a serial fashion. aka; for every time a task is registered,
 //!   there is only ever at most one execution of that task.
 //!
+//! Tasks which need more than a schedule, eg; a name or a timeout, can be registered through a [`TaskBuilder`] instead.
+//!
+//! Registering and cancelling tasks is fallible. eg; registration fails once the scheduler's runtime has shut down.
+//!   Failures are reported as an [`Error`].
+//!
+//! Families of related tasks, eg; the jobs of a tenant, can be registered into a [`TaskGroup`], which cancels, pauses and
+//!   resumes them as a unit, and can limit how many of them run at once.
+//!
+//! For maintenance windows, eg; while a database is migrated, [`pause_all`][Scheduler::pause_all] pauses every task and
+//!   waits for the runs in progress to finish, until [`resume_all`][Scheduler::resume_all]. Runs missed in the meantime
+//!   are caught up on according to a [`CatchUp`] policy.
+//!
+//! A task whose schedule panics is marked as failed rather than quietly stopping. It can instead be restarted with a
+//!   fresh schedule, or its failure escalated, through a [`Supervision`] policy. Either way, it shows up in the task's
+//!   [`task_status`][Scheduler::task_status].
+//!
+//! Tasks whose schedule finished are forgotten by the scheduler, so one-shot tasks do not pile up. To react to a task
+//!   finishing, register it with an [`on_finish`][TaskBuilder::on_finish] callback.
+//!
+//! To manage tasks from another thread, or from within a task, get a [`SchedulerHandle`] through
+//!   [`handle`][Scheduler::handle] or [`SchedulerHandle::current`].
+//!
 //! ### Tasks
 //!
 //! There are 2 types of task. The synchronous [`Task`], and it's async counterpart [`AsyncTask`].
@@ -62,6 +84,12 @@
 //! Both types of task can be scheduled on the same [`Scheduler`], via [`add_sync_task`][Scheduler::add_sync_task]
 //!   and [`add_async_task`][Scheduler::add_async_task] respectively.
 //!
+//! Sync tasks run on tokio's blocking pool by default. Long-running ones can be given a thread of their own,
+//!   or a pool of their own, through [`TaskBuilder::executor`]. See [`SyncExecutor`].
+//!
+//! Async tasks which cannot be [`Send`], eg; because they hold an [`Rc`][std::rc::Rc], implement [`LocalAsyncTask`]
+//!   instead, and run on a [`LocalSet`][tokio::task::LocalSet] through [`add_local_task`][Scheduler::add_local_task].
+//!
 //! ### Schedules
 //!
 //! The primary function of a [`Schedule`] is to consume context from a task execution, and decide when the next time that task will be executed.
@@ -74,6 +102,32 @@
 //! By using these knobs, and the internal state of the  `impl Schedule`, there is a lot of flexibility in how dynamic schedulers can be built.
 //!   Additionally, since [`next`][Schedule::next] takes the output of the last task execution, an `impl Schedule` provides a way to egress
 //!   execution data via mechanims like mpsc channels if desired.
+//!   To observe a task's outputs without a custom schedule, [`subscribe`][Scheduler::subscribe] to them instead.
+//!
+//! Schedules can also be combined. eg; [`then`][Schedule::then] switches to another schedule once the first one finishes,
+//!   and [`or`][Schedule::or] fires at whichever of two schedules comes first.
+//!
+//! To check a schedule before deploying it, [`preview`][Schedule::preview] projects the times at which it would run a task.
+//!
+//! To wait for a task to end, eg; a [`OneShot`] warming a cache at startup, wrap its schedule in an [`Awaitable`].
+//!
+//! ### Dependencies
+//!
+//! Tasks can also run after other tasks, instead of on a timer. A task registered with
+//!   [`add_sync_task_after`][Scheduler::add_sync_task_after] or [`add_async_task_after`][Scheduler::add_async_task_after]
+//!   runs once each of its [`Dependencies`][Dependency] has been [`triggered`][Trigger] by a run of its upstream task.
+//!   Cycles between dependent tasks are rejected when they are registered.
+//!
+//! The output of an upstream task can be handed to its dependents by registering the upstream task with
+//!   a [`Piped`] schedule.
+//!
+//! ### Persistence
+//!
+//! By default, schedules start from scratch every time the process starts. Named tasks can instead save
+//!   when they last ran and when they are next due to a [`StateStore`], by configuring the scheduler
+//!   with [`with_state_store`][Scheduler::with_state_store] and registering tasks with
+//!   [`add_named_sync_task`][Scheduler::add_named_sync_task]. On startup, runs that were missed while the
+//!   process was down are handled according to a [`CatchUp`] policy. See [`Persistent`].
 //!
 //! ### Features
 //!
@@ -83,7 +137,12 @@
 //! * `tokio`: Enables the tokio-based scheduler. As of 0.2.0, this is the only scheduler, and essentially required.
 //! * `log`: Enables an intergration with the [`log`] crate in the [`Scheduler`]. Helps provide debug information when dealing with problematic tasks.
 //! * `backoff`: Adds a built-in [`Schedule`] named [`BackoffSchedule`] which uses the external [`backoff`] crate.
-//! * `cron`: Adds a built-in [`Schedule`] named [`CronSchedule`] which uses the external [`cron`] crate.
+//! * `cron`: Adds a built-in [`Schedule`] named [`CronSchedule`] which runs on cron expressions, including classic crontab lines. See [`CronExpression`].
+//! * `calendar`: Adds a built-in [`Schedule`] named [`CalendarSchedule`], which runs on calendar dates such as business days, and [`TimeWindows`], which keeps any schedule within daily windows through [`Windowed`].
+//! * `persistence`: Adds a [`StateStore`] named [`FileStateStore`], which saves task state to a JSON file.
+//! * `stream`: Adds [`Scheduler::output_stream`], which exposes the outputs of a task as a [`Stream`][tokio_stream::Stream].
+//! * `rayon`: Implements [`ThreadPool`] for [`rayon::ThreadPool`], so that sync tasks can run on a rayon pool.
+//! * `serde`: Adds [`ScheduleSpec`], which parses schedules from configuration strings such as `"1h30m"` or `"@daily"`, and serializes [`TaskIdentifier`]s as strings.
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
@@ -117,23 +176,50 @@ macro_rules! cfg_feature {
     }
 }
 
+mod error;
 mod schedule;
 mod schedulers;
+mod state;
 
 use std::future::Future;
 
+pub use error::Error;
 pub use schedule::*;
+#[cfg(feature = "stream")]
+pub use schedulers::OutputStream;
 pub use schedulers::Scheduler;
-pub use schedulers::TaskIdentifier;
+pub use schedulers::TaskGroup;
+pub use schedulers::{Dependency, DependencyError, Trigger};
+pub use schedulers::{Escalation, ScheduleFailure, Supervision, TaskStatus};
+#[cfg(feature = "tokio")]
+pub use schedulers::{InFlightRuns, SchedulerHandle, TaskFailure};
+pub use schedulers::{Overlap, TaskBuilder};
+pub use schedulers::{ParseTaskIdentifierError, TaskIdentifier};
+pub use schedulers::{SyncExecutor, ThreadPool};
+pub use state::*;
 
 /// Defines a task that can run in an synchronous runtime.
+///
+/// Registered tasks are shared with the threads running them, so they must be [`Send`] and [`Sync`].
+///   Their outputs only need to be [`Send`].
 pub trait Task<T = ()> {
     /// Executes the task.
     fn run(&self) -> T;
 }
 
 /// Defines a task that can run in an asynchronous runtime.
+///
+/// Registered tasks must be [`Send`] and [`Sync`], since their futures borrow them while moving between threads.
+///   Their outputs only need to be [`Send`]. Tasks which cannot be shared between threads can be a [`LocalAsyncTask`] instead.
 pub trait AsyncTask<T = ()> {
     /// Executes the task.
     fn run(&self) -> impl Future<Output = T> + Send;
 }
+
+/// Defines an asynchronous task which is bound to a single thread. See [`Scheduler::add_local_task`].
+///
+/// Unlike an [`AsyncTask`], neither the task nor the future it returns need to be [`Send`].
+pub trait LocalAsyncTask<T = ()> {
+    /// Executes the task.
+    fn run(&self) -> impl Future<Output = T>;
+}
@@ -1,4 +1,16 @@
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
+
+mod clock;
+
+mod aligned;
+pub use aligned::AlignedInterval;
+
+mod awaitable;
+pub(crate) use awaitable::with_panic_message;
+pub use awaitable::{Awaitable, Completion, TaskOutcome};
+
+mod combinators;
+pub use combinators::{Expiring, Or, Then, Until};
 
 mod interval;
 pub use interval::IntervalSchedule;
@@ -6,10 +18,36 @@ pub use interval::IntervalSchedule;
 mod oneshot;
 pub use oneshot::OneShot;
 
+mod persistent;
+pub use persistent::Persistent;
+
+mod preview;
+pub use preview::Preview;
+
+mod piped;
+pub use piped::{Piped, PipedOutput};
+
+mod retry;
+pub use retry::RetryOnError;
+
 cfg_feature! {
     "cron",
     mod cron;
-    pub use cron::CronSchedule;
+    pub use cron::{CronError, CronExpression, CronField, CronSchedule, Misfire};
+}
+
+cfg_feature! {
+    "calendar",
+    mod calendar;
+    pub use calendar::{CalendarSchedule, CalendarScheduleBuilder, HolidayCalendar, HolidayCalendarError};
+    mod windows;
+    pub use windows::{TimeWindows, Windowed};
+}
+
+cfg_feature! {
+    "serde",
+    mod spec;
+    pub use spec::{ScheduleSpec, ScheduleSpecError};
 }
 
 cfg_feature! {
@@ -40,4 +78,73 @@ pub trait Schedule<T> {
     fn next_on_task_panic(&self) -> Option<Duration> {
         self.initial()
     }
+
+    /// Whether this schedule runs the task at times of the wall clock, eg; cron expressions or
+    /// times of day, rather than at intervals since the previous run. Defaults to `false`.
+    ///
+    /// The scheduler wakes up tasks whose schedule follows the wall clock on time after the wall
+    /// clock jumps ahead, eg; after the host was suspended. Other tasks keep to their intervals.
+    fn follows_wall_clock(&self) -> bool {
+        false
+    }
+
+    /// Uses this schedule until it returns `None`, and then switches over to `next`.
+    /// See [Then].
+    fn then<S: Schedule<T>>(self, next: S) -> Then<Self, S, T>
+    where
+        Self: Sized,
+    {
+        Then::new(self, next)
+    }
+
+    /// Fires at whichever of this schedule and `other` comes first. See [Or].
+    fn or<S: Schedule<T>>(self, other: S) -> Or<Self, S, T>
+    where
+        Self: Sized,
+    {
+        Or::new(self, other)
+    }
+
+    /// Stops this schedule once the task output satisfies `condition`. See [Until].
+    fn until<F: Fn(&T) -> bool>(self, condition: F) -> Until<Self, F>
+    where
+        Self: Sized,
+    {
+        Until::new(self, condition)
+    }
+
+    /// Stops this schedule once `lifetime` has passed since it was started. See [Expiring].
+    fn for_duration(self, lifetime: Duration) -> Expiring<Self, T>
+    where
+        Self: Sized,
+    {
+        Expiring::new(self, lifetime)
+    }
+
+    /// Projects the times at which this schedule would run a task, starting at `from`, without running anything.
+    ///   `outputs` provides the task output fed to [`next`][Schedule::next] after each simulated run. See [Preview].
+    fn preview<F: FnMut() -> T>(&self, from: SystemTime, outputs: F) -> Preview<'_, Self, T, F>
+    where
+        Self: Sized,
+    {
+        Preview::new(self, from, outputs)
+    }
+}
+
+impl<T, S: Schedule<T> + ?Sized> Schedule<T> for Box<S> {
+    fn initial(&self) -> Option<Duration> {
+        (**self).initial()
+    }
+
+    fn next(&self, task_output: T) -> Option<Duration> {
+        (**self).next(task_output)
+    }
+
+    fn next_on_task_panic(&self) -> Option<Duration> {
+        (**self).next_on_task_panic()
+    }
+
+    fn follows_wall_clock(&self) -> bool {
+        (**self).follows_wall_clock()
+    }
 }
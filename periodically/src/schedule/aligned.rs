@@ -0,0 +1,127 @@
+use std::{
+    cell::Cell,
+    hash::{DefaultHasher, Hash, Hasher},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use super::{clock, Schedule};
+
+/// Schedules runs on wall-clock boundaries, eg; every 5 minutes at :00, :05, :10, and so on.
+///
+/// Unlike an [IntervalSchedule][super::IntervalSchedule], runs do not depend on when the process
+///   started, or on how long the task takes. Boundaries are multiples of the period since the Unix
+///   epoch, so they line up with UTC. eg; daily runs happen at midnight UTC.
+///
+/// An offset shifts every boundary, which spreads the load when many instances share the same schedule.
+///
+/// If a run overruns the next boundary, that boundary is skipped rather than run late.
+///
+/// ```
+/// use periodically::{AlignedInterval, Schedule};
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// // every 5 minutes, at 30s past :00, :05, :10, ...
+/// let schedule = AlignedInterval::every(Duration::from_secs(5 * 60)).with_offset(Duration::from_secs(30));
+///
+/// let from = UNIX_EPOCH + Duration::from_secs(60);
+/// let times: Vec<_> = schedule.preview(from, || ()).take(2).collect();
+/// assert_eq!(
+///     times,
+///     vec![
+///         UNIX_EPOCH + Duration::from_secs(5 * 60 + 30),
+///         UNIX_EPOCH + Duration::from_secs(10 * 60 + 30),
+///     ]
+/// );
+/// ```
+pub struct AlignedInterval {
+    period: Duration,
+    offset: Duration,
+    /// The boundary which the latest run was scheduled for.
+    scheduled: Cell<Option<SystemTime>>,
+}
+
+impl AlignedInterval {
+    /// Creates a [Schedule] which runs at every multiple of `period`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero.
+    pub fn every(period: Duration) -> Self {
+        assert!(!period.is_zero(), "period must be greater than zero");
+        Self {
+            period,
+            offset: Duration::ZERO,
+            scheduled: Cell::new(None),
+        }
+    }
+
+    /// Shifts every boundary by `offset`. Offsets longer than the period wrap around.
+    pub fn with_offset(mut self, offset: Duration) -> Self {
+        self.offset = nanos(offset.as_nanos() % self.period.as_nanos());
+        self
+    }
+
+    /// Shifts every boundary by an offset derived from `key`, eg; the hostname, so that instances
+    ///   with different keys run at different times within the period.
+    ///
+    /// The same key always maps to the same offset for a given build of `periodically`, but the
+    ///   mapping may change between Rust releases.
+    pub fn with_offset_from(self, key: impl Hash) -> Self {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let offset = hasher.finish() as u128 % self.period.as_nanos();
+        self.with_offset(nanos(offset))
+    }
+
+    /// How long until the next boundary after the current time, or at it if `inclusive`.
+    fn until_boundary(&self, inclusive: bool) -> Duration {
+        let now = clock::now();
+        let period = self.period.as_nanos();
+        let since_epoch = now
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let past_boundary = (since_epoch + period - self.offset.as_nanos()) % period;
+        let mut until = match past_boundary {
+            0 if inclusive => 0,
+            _ => period - past_boundary,
+        };
+
+        // waking up slightly early must not run the same boundary twice
+        let boundary = now + nanos(until);
+        if let Some(scheduled) = self.scheduled.get() {
+            if boundary <= scheduled {
+                until += period;
+            }
+        }
+        self.scheduled.set(Some(now + nanos(until)));
+        nanos(until)
+    }
+}
+
+fn nanos(nanos: u128) -> Duration {
+    const NANOS_PER_SEC: u128 = 1_000_000_000;
+    Duration::new(
+        (nanos / NANOS_PER_SEC) as u64,
+        (nanos % NANOS_PER_SEC) as u32,
+    )
+}
+
+impl<T> Schedule<T> for AlignedInterval {
+    fn initial(&self) -> Option<Duration> {
+        self.scheduled.set(None);
+        Some(self.until_boundary(true))
+    }
+
+    fn next(&self, _: T) -> Option<Duration> {
+        Some(self.until_boundary(false))
+    }
+
+    fn next_on_task_panic(&self) -> Option<Duration> {
+        Some(self.until_boundary(false))
+    }
+
+    fn follows_wall_clock(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,214 @@
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Condvar, Mutex},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use super::Schedule;
+
+/// Wraps another [Schedule], so that the task's end can be awaited through a [Completion].
+///
+/// The task ends once the wrapped schedule returns `None`, or when the task is cancelled.
+///   This makes it possible to wait for a [OneShot][crate::OneShot] to run, and to get its output.
+///
+/// ```
+/// use periodically::{Awaitable, OneShot, Scheduler, Task, TaskOutcome};
+/// use std::time::Duration;
+///
+/// struct WarmCache;
+///
+/// impl Task<usize> for WarmCache {
+///     fn run(&self) -> usize {
+///         // ..
+///         // load the cache, and return how many entries were loaded
+///         // ..
+///         42
+///     }
+/// }
+///
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+/// let mut scheduler = Scheduler::tokio_scheduler(runtime);
+/// let (schedule, completion) = Awaitable::new(OneShot::after(Duration::ZERO));
+/// scheduler.add_sync_task(WarmCache, schedule).unwrap();
+///
+/// // block startup until the cache is warm
+/// assert_eq!(completion.wait(), TaskOutcome::Finished(Some(42)));
+/// ```
+pub struct Awaitable<S, T> {
+    schedule: S,
+    sender: CompletionSender<T>,
+    /// Whether the latest run of the task panicked.
+    panicked: Cell<bool>,
+    /// The panic message of the latest run, if it panicked with one.
+    panic_message: RefCell<Option<String>>,
+}
+
+impl<S, T> Awaitable<S, T> {
+    /// Wraps `schedule`, returning the wrapped schedule along with a [Completion] of its task.
+    pub fn new(schedule: S) -> (Self, Completion<T>) {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                completed: false,
+                outcome: None,
+                waker: None,
+            }),
+            finished: Condvar::new(),
+        });
+        let awaitable = Self {
+            schedule,
+            sender: CompletionSender(shared.clone()),
+            panicked: Cell::new(false),
+            panic_message: RefCell::new(None),
+        };
+        (awaitable, Completion(shared))
+    }
+
+    fn finish_if_done(
+        &self,
+        next: Option<Duration>,
+        outcome: impl FnOnce() -> TaskOutcome<T>,
+    ) -> Option<Duration> {
+        if next.is_none() {
+            self.sender.0.complete(outcome());
+        }
+        next
+    }
+}
+
+impl<S: Schedule<T>, T: Clone> Schedule<T> for Awaitable<S, T> {
+    fn initial(&self) -> Option<Duration> {
+        let next = self.schedule.initial();
+        self.finish_if_done(next, || match self.panicked.get() {
+            true => TaskOutcome::Panicked(self.panic_message.borrow().clone()),
+            false => TaskOutcome::Finished(None),
+        })
+    }
+
+    fn next(&self, task_output: T) -> Option<Duration> {
+        self.panicked.set(false);
+        let next = self.schedule.next(task_output.clone());
+        self.finish_if_done(next, || TaskOutcome::Finished(Some(task_output)))
+    }
+
+    fn next_on_task_panic(&self) -> Option<Duration> {
+        self.panicked.set(true);
+        let message = PANIC_MESSAGE.with_borrow(Clone::clone);
+        self.panic_message.replace(message.clone());
+        let next = self.schedule.next_on_task_panic();
+        self.finish_if_done(next, || TaskOutcome::Panicked(message))
+    }
+
+    fn follows_wall_clock(&self) -> bool {
+        self.schedule.follows_wall_clock()
+    }
+}
+
+/// How a task wrapped in an [Awaitable] schedule ended.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TaskOutcome<T> {
+    /// The schedule finished. Holds the output of the last run, or `None` if the task never ran.
+    Finished(Option<T>),
+    /// The schedule finished after a run of the task panicked, or otherwise failed, eg; by timing out.
+    ///   Holds the panic message, if the run panicked with a string.
+    Panicked(Option<String>),
+    /// The task was cancelled, or its scheduler was dropped, before the schedule finished.
+    Cancelled,
+}
+
+thread_local! {
+    /// The panic message of the run which a schedule is told about, see [with_panic_message].
+    static PANIC_MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, which tells a schedule that a run panicked, so that [Awaitable]s can report `message`.
+pub(crate) fn with_panic_message<R>(message: Option<String>, f: impl FnOnce() -> R) -> R {
+    /// Puts back the previous message, even if `f` panics.
+    struct Restore(Option<String>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            PANIC_MESSAGE.set(self.0.take());
+        }
+    }
+
+    let _restore = Restore(PANIC_MESSAGE.replace(message));
+    f()
+}
+
+/// Waits for a task wrapped in an [Awaitable] schedule to end.
+///
+/// Can be `.await`ed, or blocked on with [`wait`][Completion::wait].
+pub struct Completion<T>(Arc<Shared<T>>);
+
+impl<T> Completion<T> {
+    /// Blocks the current thread until the task ends.
+    ///
+    /// Must not be called from an async context, since it would block the executor. `.await` the
+    ///   [Completion] instead.
+    pub fn wait(self) -> TaskOutcome<T> {
+        let state = self.0.state.lock().unwrap();
+        let mut state = self
+            .0
+            .finished
+            .wait_while(state, |state| !state.completed)
+            .unwrap();
+        state.outcome.take().unwrap_or(TaskOutcome::Cancelled)
+    }
+
+    /// Whether the task has ended.
+    pub fn is_finished(&self) -> bool {
+        self.0.state.lock().unwrap().completed
+    }
+}
+
+impl<T> Future for Completion<T> {
+    type Output = TaskOutcome<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.0.state.lock().unwrap();
+        if state.completed {
+            return Poll::Ready(state.outcome.take().unwrap_or(TaskOutcome::Cancelled));
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    finished: Condvar,
+}
+
+struct State<T> {
+    completed: bool,
+    outcome: Option<TaskOutcome<T>>,
+    waker: Option<Waker>,
+}
+
+impl<T> Shared<T> {
+    /// Records how the task ended, unless it already has.
+    fn complete(&self, outcome: TaskOutcome<T>) {
+        let mut state = self.state.lock().unwrap();
+        if state.completed {
+            return;
+        }
+        state.completed = true;
+        state.outcome = Some(outcome);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+        self.finished.notify_all();
+    }
+}
+
+/// Completes the [Completion] with [TaskOutcome::Cancelled] if the schedule is dropped before it finishes.
+struct CompletionSender<T>(Arc<Shared<T>>);
+
+impl<T> Drop for CompletionSender<T> {
+    fn drop(&mut self) {
+        self.0.complete(TaskOutcome::Cancelled);
+    }
+}
@@ -0,0 +1,243 @@
+use super::{clock, Schedule};
+use chrono::{
+    DateTime, Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone, Utc,
+    Weekday,
+};
+use std::{collections::BTreeSet, time::Duration};
+
+mod holidays;
+pub use holidays::{HolidayCalendar, HolidayCalendarError};
+
+/// How many days ahead to search for the next matching date before giving up.
+const SEARCH_DAYS: u64 = 8 * 366;
+
+/// How far past a local time which does not exist, eg; skipped by a daylight saving transition,
+///   to look for one which does.
+const GAP_MINUTES: i64 = 3 * 60;
+
+/// The days which count as business days, when looking for the last business day of a month.
+const BUSINESS_DAYS: [Weekday; 5] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+];
+
+/// Runs jobs on calendar dates, such as "the 2nd Tuesday of the month" or "every weekday except public holidays".
+///
+/// Created through a [`CalendarScheduleBuilder`]. A date matches if it satisfies any of the builder's
+///   day rules, and is not excluded. Without any day rules, every date which is not excluded matches.
+///   The task runs at each of the configured times of day on every matching date, as observed in the
+///   schedule's timezone. Times which are skipped by a daylight saving transition run once the clocks
+///   have gone forward.
+///
+/// ```
+/// use chrono::{NaiveTime, Utc, Weekday};
+/// use periodically::{CalendarSchedule, HolidayCalendar, Schedule};
+/// use std::time::Duration;
+///
+/// let holidays = HolidayCalendar::from_list("2024-12-25\n2024-12-26").unwrap();
+/// let schedule = CalendarSchedule::builder(Utc)
+///     .at(NaiveTime::from_hms_opt(18, 0, 0).unwrap())
+///     .on_weekdays([Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri])
+///     .excluding_calendar(&holidays)
+///     .build();
+/// # let schedule = Box::new(schedule) as Box<dyn Schedule<()>>;
+/// assert!(schedule.initial().unwrap() <= Duration::from_secs(4 * 24 * 60 * 60));
+/// ```
+pub struct CalendarSchedule<Tz: TimeZone> {
+    timezone: Tz,
+    times: BTreeSet<NaiveTime>,
+    rules: Vec<DayRule>,
+    excluded: BTreeSet<NaiveDate>,
+}
+
+/// A rule deciding whether a date matches a [`CalendarSchedule`].
+#[derive(Debug, Clone, Copy)]
+enum DayRule {
+    Weekday(Weekday),
+    NthWeekday(u32, Weekday),
+    LastWeekday(Weekday),
+    DayOfMonth(u32),
+    LastDayOfMonth,
+    LastBusinessDayOfMonth,
+}
+
+impl<Tz: TimeZone> CalendarSchedule<Tz> {
+    /// Starts building a [`CalendarSchedule`] which observes dates and times in `timezone`.
+    pub fn builder(timezone: Tz) -> CalendarScheduleBuilder<Tz> {
+        CalendarScheduleBuilder {
+            schedule: CalendarSchedule {
+                timezone,
+                times: BTreeSet::new(),
+                rules: Vec::new(),
+                excluded: BTreeSet::new(),
+            },
+        }
+    }
+
+    /// Returns the first time strictly after `after` at which this schedule fires.
+    ///
+    /// ```
+    /// use chrono::{NaiveTime, TimeZone, Utc, Weekday};
+    /// use periodically::CalendarSchedule;
+    ///
+    /// let second_tuesday = CalendarSchedule::builder(Utc)
+    ///     .at(NaiveTime::from_hms_opt(9, 30, 0).unwrap())
+    ///     .on_nth_weekday(2, Weekday::Tue)
+    ///     .build();
+    /// let after = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+    /// let expected = Utc.with_ymd_and_hms(2024, 3, 12, 9, 30, 0).unwrap();
+    /// assert_eq!(second_tuesday.next_after(&after), Some(expected));
+    /// ```
+    pub fn next_after(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let first_date = after.with_timezone(&self.timezone).date_naive();
+        (0..SEARCH_DAYS)
+            .filter_map(|offset| first_date.checked_add_days(Days::new(offset)))
+            .filter(|date| self.matches(*date))
+            .flat_map(|date| self.times.iter().map(move |time| date.and_time(*time)))
+            // times skipped by a daylight saving transition run once the clocks have gone forward
+            .filter_map(|local| resolve(&self.timezone, local))
+            .find(|datetime| datetime > after)
+    }
+
+    fn matches(&self, date: NaiveDate) -> bool {
+        if self.excluded.contains(&date) {
+            return false;
+        }
+        self.rules.is_empty() || self.rules.iter().any(|rule| self.rule_matches(rule, date))
+    }
+
+    fn rule_matches(&self, rule: &DayRule, date: NaiveDate) -> bool {
+        let last_day = last_day_of_month(date);
+        match *rule {
+            DayRule::Weekday(weekday) => date.weekday() == weekday,
+            DayRule::NthWeekday(n, weekday) => {
+                date.weekday() == weekday && (date.day() - 1) / 7 + 1 == n
+            }
+            DayRule::LastWeekday(weekday) => {
+                date.weekday() == weekday && date.day() + 7 > last_day.day()
+            }
+            DayRule::DayOfMonth(day) => date.day() == day,
+            DayRule::LastDayOfMonth => date == last_day,
+            DayRule::LastBusinessDayOfMonth => {
+                let last_business_day = std::iter::successors(Some(last_day), NaiveDate::pred_opt)
+                    .take_while(|day| day.month() == date.month())
+                    .find(|day| {
+                        BUSINESS_DAYS.contains(&day.weekday()) && !self.excluded.contains(day)
+                    });
+                last_business_day == Some(date)
+            }
+        }
+    }
+
+    fn calculate_next(&self) -> Option<Duration> {
+        let now = DateTime::<Utc>::from(clock::now()).with_timezone(&self.timezone);
+        let next = self.next_after(&now)?;
+        let delta = next.signed_duration_since(now);
+        Some(delta.to_std().unwrap_or(Duration::from_secs(0)))
+    }
+}
+
+impl<T, Tz: TimeZone> Schedule<T> for CalendarSchedule<Tz> {
+    fn initial(&self) -> Option<Duration> {
+        self.calculate_next()
+    }
+
+    fn next(&self, _: T) -> Option<Duration> {
+        self.calculate_next()
+    }
+
+    fn follows_wall_clock(&self) -> bool {
+        true
+    }
+}
+
+/// Builds a [`CalendarSchedule`]. See [`CalendarSchedule::builder`].
+pub struct CalendarScheduleBuilder<Tz: TimeZone> {
+    schedule: CalendarSchedule<Tz>,
+}
+
+impl<Tz: TimeZone> CalendarScheduleBuilder<Tz> {
+    /// Adds a time of day at which the task runs on matching dates. Defaults to midnight if no times are added.
+    pub fn at(mut self, time: NaiveTime) -> Self {
+        self.schedule.times.insert(time);
+        self
+    }
+
+    /// Matches every date falling on one of the given `weekdays`.
+    pub fn on_weekdays(mut self, weekdays: impl IntoIterator<Item = Weekday>) -> Self {
+        let rules = weekdays.into_iter().map(DayRule::Weekday);
+        self.schedule.rules.extend(rules);
+        self
+    }
+
+    /// Matches the `n`th occurence of `weekday` in each month. eg; `on_nth_weekday(2, Weekday::Tue)` for the 2nd Tuesday.
+    pub fn on_nth_weekday(mut self, n: u32, weekday: Weekday) -> Self {
+        self.schedule.rules.push(DayRule::NthWeekday(n, weekday));
+        self
+    }
+
+    /// Matches the last occurence of `weekday` in each month.
+    pub fn on_last_weekday(mut self, weekday: Weekday) -> Self {
+        self.schedule.rules.push(DayRule::LastWeekday(weekday));
+        self
+    }
+
+    /// Matches the given days of the month, counting from 1. Months without such a day are skipped.
+    pub fn on_days_of_month(mut self, days: impl IntoIterator<Item = u32>) -> Self {
+        let rules = days.into_iter().map(DayRule::DayOfMonth);
+        self.schedule.rules.extend(rules);
+        self
+    }
+
+    /// Matches the last day of each month.
+    pub fn on_last_day_of_month(mut self) -> Self {
+        self.schedule.rules.push(DayRule::LastDayOfMonth);
+        self
+    }
+
+    /// Matches the last business day of each month. Business days are Monday to Friday, except excluded dates.
+    pub fn on_last_business_day_of_month(mut self) -> Self {
+        self.schedule.rules.push(DayRule::LastBusinessDayOfMonth);
+        self
+    }
+
+    /// Never matches any of the given `dates`.
+    pub fn excluding(mut self, dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.schedule.excluded.extend(dates);
+        self
+    }
+
+    /// Never matches any of the dates in a [`HolidayCalendar`].
+    pub fn excluding_calendar(self, calendar: &HolidayCalendar) -> Self {
+        self.excluding(calendar.dates())
+    }
+
+    /// Finishes building the [`CalendarSchedule`].
+    pub fn build(mut self) -> CalendarSchedule<Tz> {
+        if self.schedule.times.is_empty() {
+            self.schedule.times.insert(NaiveTime::MIN);
+        }
+        self.schedule
+    }
+}
+
+fn last_day_of_month(date: NaiveDate) -> NaiveDate {
+    let (year, month) = match date.month() {
+        12 => (date.year() + 1, 1),
+        month => (date.year(), month + 1),
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|first| first.pred_opt())
+        .unwrap_or(NaiveDate::MAX)
+}
+
+/// The first existing time at or after the local time `local` in `timezone`.
+pub(super) fn resolve<Tz: TimeZone>(timezone: &Tz, local: NaiveDateTime) -> Option<DateTime<Tz>> {
+    (0..=GAP_MINUTES).find_map(|minutes| {
+        let local = local.checked_add_signed(TimeDelta::minutes(minutes))?;
+        timezone.from_local_datetime(&local).earliest()
+    })
+}
@@ -0,0 +1,201 @@
+use chrono::NaiveDate;
+use std::{collections::BTreeSet, fs, io, path::Path};
+
+/// A set of dates on which a [`CalendarSchedule`][super::CalendarSchedule] should not run, such as public holidays.
+///
+/// Can be loaded from a simple list of dates, or from an iCalendar (`.ics`) file.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use periodically::HolidayCalendar;
+///
+/// let list = "\
+/// 2024-01-01 # new year's day
+///
+/// 2024-12-25
+/// ";
+/// let holidays = HolidayCalendar::from_list(list).unwrap();
+/// assert!(holidays.contains(NaiveDate::from_ymd_opt(2024, 12, 25).unwrap()));
+/// assert!(!holidays.contains(NaiveDate::from_ymd_opt(2024, 12, 24).unwrap()));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HolidayCalendar {
+    dates: BTreeSet<NaiveDate>,
+}
+
+impl HolidayCalendar {
+    /// Creates a [`HolidayCalendar`] from the given `dates`.
+    pub fn from_dates(dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        Self {
+            dates: dates.into_iter().collect(),
+        }
+    }
+
+    /// Parses a list of dates, one `YYYY-MM-DD` date per line. Blank lines are ignored, and `#` starts a comment.
+    pub fn from_list(list: &str) -> Result<Self, HolidayCalendarError> {
+        let mut dates = BTreeSet::new();
+        for (index, line) in list.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let date = NaiveDate::parse_from_str(line, "%Y-%m-%d")
+                .map_err(|err| HolidayCalendarError::parse(index, format!("[{line}]: {err}")))?;
+            dates.insert(date);
+        }
+        Ok(Self { dates })
+    }
+
+    /// Reads a list of dates from a file. See [`from_list`][HolidayCalendar::from_list].
+    pub fn from_list_file(path: impl AsRef<Path>) -> Result<Self, HolidayCalendarError> {
+        Self::from_list(&fs::read_to_string(path)?)
+    }
+
+    /// Parses the events of an iCalendar (RFC 5545) document. Every date covered by an event is
+    ///   included. All-day events may span multiple days, as given by their `DTEND`.
+    ///   Recurrence rules are not expanded, so only the first occurence of a recurring event is included.
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use periodically::HolidayCalendar;
+    ///
+    /// let ical = "\
+    /// BEGIN:VCALENDAR
+    /// BEGIN:VEVENT
+    /// SUMMARY:Christmas
+    /// DTSTART;VALUE=DATE:20241225
+    /// DTEND;VALUE=DATE:20241227
+    /// END:VEVENT
+    /// END:VCALENDAR
+    /// ";
+    /// let holidays = HolidayCalendar::from_ical(ical).unwrap();
+    /// let dates: Vec<NaiveDate> = holidays.dates().collect();
+    /// assert_eq!(dates, vec![
+    ///     NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2024, 12, 26).unwrap(),
+    /// ]);
+    /// ```
+    pub fn from_ical(ical: &str) -> Result<Self, HolidayCalendarError> {
+        let mut dates = BTreeSet::new();
+        let mut event: Option<(Option<NaiveDate>, Option<NaiveDate>)> = None;
+        for (index, line) in unfold(ical) {
+            let (name, value) = match line.split_once(':') {
+                Some((name, value)) => (name.split(';').next().unwrap_or_default(), value),
+                None => continue,
+            };
+            match (name.to_ascii_uppercase().as_str(), &mut event) {
+                ("BEGIN", _) if value.eq_ignore_ascii_case("VEVENT") => event = Some((None, None)),
+                ("DTSTART", Some((start, _))) => *start = Some(parse_ical_date(index, value)?),
+                ("DTEND", Some((_, end))) if is_ical_date(value) => {
+                    *end = Some(parse_ical_date(index, value)?)
+                }
+                ("END", Some((start, end))) if value.eq_ignore_ascii_case("VEVENT") => {
+                    let start =
+                        start.ok_or(HolidayCalendarError::MissingStart { line: index + 1 })?;
+                    let end = end.unwrap_or(start).max(start);
+                    dates.extend(start.iter_days().take_while(|date| *date < end));
+                    dates.insert(start);
+                    event = None;
+                }
+                _ => {}
+            }
+        }
+        Ok(Self { dates })
+    }
+
+    /// Reads an iCalendar file. See [`from_ical`][HolidayCalendar::from_ical].
+    pub fn from_ical_file(path: impl AsRef<Path>) -> Result<Self, HolidayCalendarError> {
+        Self::from_ical(&fs::read_to_string(path)?)
+    }
+
+    /// Whether `date` is in this calendar.
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.dates.contains(&date)
+    }
+
+    /// The dates in this calendar, in order.
+    pub fn dates(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.dates.iter().copied()
+    }
+}
+
+/// Joins folded iCalendar content lines, which continue onto lines starting with whitespace.
+fn unfold(ical: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (index, line) in ical.lines().enumerate() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some((_, previous))) => previous.push_str(continuation),
+            _ => lines.push((index, line.trim_end().to_owned())),
+        }
+    }
+    lines
+}
+
+/// Whether an iCalendar value is a plain date, rather than a date-time.
+fn is_ical_date(value: &str) -> bool {
+    !value.contains('T')
+}
+
+/// Parses the date part of an iCalendar `DATE` or `DATE-TIME` value.
+fn parse_ical_date(index: usize, value: &str) -> Result<NaiveDate, HolidayCalendarError> {
+    let date = value.get(..8).unwrap_or(value);
+    NaiveDate::parse_from_str(date, "%Y%m%d")
+        .map_err(|err| HolidayCalendarError::parse(index, format!("[{value}]: {err}")))
+}
+
+/// Reasons why a [`HolidayCalendar`] could not be loaded.
+#[derive(Debug)]
+pub enum HolidayCalendarError {
+    /// The file could not be read.
+    Io(io::Error),
+    /// The contents could not be parsed.
+    Parse {
+        /// The line which could not be parsed, counting from 1.
+        line: usize,
+        /// What was wrong with the line.
+        message: String,
+    },
+    /// An iCalendar event has no `DTSTART`, so its dates are unknown.
+    MissingStart {
+        /// The line which ends the event, counting from 1.
+        line: usize,
+    },
+}
+
+impl HolidayCalendarError {
+    fn parse(index: usize, message: String) -> Self {
+        HolidayCalendarError::Parse {
+            line: index + 1,
+            message,
+        }
+    }
+}
+
+impl std::fmt::Display for HolidayCalendarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HolidayCalendarError::Io(err) => write!(f, "cannot read calendar: {err}"),
+            HolidayCalendarError::Parse { line, message } => {
+                write!(f, "invalid date on line {line}: {message}")
+            }
+            HolidayCalendarError::MissingStart { line } => {
+                write!(f, "event ending on line {line} has no DTSTART")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HolidayCalendarError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HolidayCalendarError::Io(err) => Some(err),
+            HolidayCalendarError::Parse { .. } | HolidayCalendarError::MissingStart { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for HolidayCalendarError {
+    fn from(err: io::Error) -> Self {
+        HolidayCalendarError::Io(err)
+    }
+}
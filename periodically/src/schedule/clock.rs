@@ -0,0 +1,56 @@
+//! The time as seen by schedules. Normally the real time, but simulated while a [`Preview`][super::Preview]
+//!   is driving a schedule.
+
+use std::{
+    cell::Cell,
+    time::{Duration, Instant, SystemTime},
+};
+
+thread_local! {
+    static SIMULATION: Cell<Option<Simulation>> = const { Cell::new(None) };
+}
+
+/// A simulated clock, which has advanced `elapsed` past the wall-clock time `from`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Simulation {
+    pub(crate) from: SystemTime,
+    pub(crate) started: Instant,
+    pub(crate) elapsed: Duration,
+}
+
+/// Runs `f` with the clock simulated on this thread.
+pub(crate) fn simulate<R>(simulation: Simulation, f: impl FnOnce() -> R) -> R {
+    /// Restores the previous clock, even if `f` panics.
+    struct Restore(Option<Simulation>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SIMULATION.set(self.0);
+        }
+    }
+    let _restore = Restore(SIMULATION.replace(Some(simulation)));
+    f()
+}
+
+/// Whether the clock is simulated, in which case schedules should avoid side effects.
+pub(crate) fn is_simulated() -> bool {
+    SIMULATION.get().is_some()
+}
+
+/// The current wall-clock time.
+pub(crate) fn now() -> SystemTime {
+    match SIMULATION.get() {
+        Some(simulation) => simulation.from + simulation.elapsed,
+        None => SystemTime::now(),
+    }
+}
+
+/// The current monotonic time.
+pub(crate) fn instant() -> Instant {
+    match SIMULATION.get() {
+        Some(simulation) => simulation
+            .started
+            .checked_add(simulation.elapsed)
+            .unwrap_or(simulation.started),
+        None => Instant::now(),
+    }
+}
@@ -0,0 +1,284 @@
+use std::{
+    cell::Cell,
+    marker::PhantomData,
+    time::{Duration, Instant},
+};
+
+use super::{clock, Schedule};
+
+/// Runs one schedule until it returns `None`, and then hands over to another.
+///
+/// Created by [`Schedule::then`]. When handing over, the second schedule is consulted
+///   through [`initial`][Schedule::initial].
+///
+/// ```
+/// use periodically::{Schedule, IntervalSchedule, OneShot};
+/// use std::time::Duration;
+///
+/// let schedule = OneShot::after(Duration::ZERO).then(IntervalSchedule::every(Duration::from_secs(5)));
+/// # let schedule = Box::new(schedule) as Box<dyn Schedule<()>>;
+/// assert_eq!(schedule.initial(), Some(Duration::ZERO));
+/// assert_eq!(schedule.next(()), Some(Duration::from_secs(5)));
+/// assert_eq!(schedule.next(()), Some(Duration::from_secs(5)));
+/// ```
+pub struct Then<A, B, T> {
+    first: A,
+    second: B,
+    switched: Cell<bool>,
+    output: PhantomData<fn(T)>,
+}
+
+impl<A, B: Schedule<T>, T> Then<A, B, T> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            switched: Cell::new(false),
+            output: PhantomData,
+        }
+    }
+
+    fn or_switch(&self, next: Option<Duration>) -> Option<Duration> {
+        next.or_else(|| {
+            self.switched.set(true);
+            self.second.initial()
+        })
+    }
+}
+
+impl<T, A: Schedule<T>, B: Schedule<T>> Schedule<T> for Then<A, B, T> {
+    fn initial(&self) -> Option<Duration> {
+        self.or_switch(self.first.initial())
+    }
+
+    fn next(&self, task_output: T) -> Option<Duration> {
+        if self.switched.get() {
+            self.second.next(task_output)
+        } else {
+            self.or_switch(self.first.next(task_output))
+        }
+    }
+
+    fn next_on_task_panic(&self) -> Option<Duration> {
+        if self.switched.get() {
+            self.second.next_on_task_panic()
+        } else {
+            self.or_switch(self.first.next_on_task_panic())
+        }
+    }
+
+    fn follows_wall_clock(&self) -> bool {
+        match self.switched.get() {
+            true => self.second.follows_wall_clock(),
+            false => self.first.follows_wall_clock(),
+        }
+    }
+}
+
+/// Fires at whichever of two schedules comes first.
+///
+/// Created by [`Schedule::or`]. Each schedule keeps its own timeline: when one of them
+///   fires, only that one is asked for its next execution, while the other keeps
+///   counting down towards its own. Finishes once both schedules have returned `None`.
+///
+/// Requires the task output to be [Clone], since both schedules may be due at once.
+///
+/// ```
+/// use periodically::{Schedule, IntervalSchedule};
+/// use std::time::Duration;
+///
+/// let fast = IntervalSchedule::every(Duration::from_secs(10));
+/// let slow = IntervalSchedule::every(Duration::from_secs(60));
+/// let schedule = fast.or(slow);
+/// # let schedule = Box::new(schedule) as Box<dyn Schedule<()>>;
+/// assert!(schedule.initial() <= Some(Duration::from_secs(10)));
+/// ```
+pub struct Or<A, B, T> {
+    first: A,
+    second: B,
+    first_due: Cell<Option<Instant>>,
+    second_due: Cell<Option<Instant>>,
+    output: PhantomData<fn(T)>,
+}
+
+impl<A, B, T> Or<A, B, T> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            first_due: Cell::new(None),
+            second_due: Cell::new(None),
+            output: PhantomData,
+        }
+    }
+
+    /// Refreshes the due time of every schedule which has fired, and returns the time until the earliest.
+    fn advance(
+        &self,
+        mut first: impl FnMut() -> Option<Duration>,
+        mut second: impl FnMut() -> Option<Duration>,
+    ) -> Option<Duration> {
+        let now = clock::instant();
+        if self.first_due.get().is_some_and(|due| due <= now) {
+            self.first_due.set(first().map(|delay| now + delay));
+        }
+        if self.second_due.get().is_some_and(|due| due <= now) {
+            self.second_due.set(second().map(|delay| now + delay));
+        }
+        self.until_earliest(now)
+    }
+
+    fn until_earliest(&self, now: Instant) -> Option<Duration> {
+        let due = match (self.first_due.get(), self.second_due.get()) {
+            (Some(first), Some(second)) => first.min(second),
+            (first, second) => first.or(second)?,
+        };
+        Some(due.saturating_duration_since(now))
+    }
+}
+
+impl<T: Clone, A: Schedule<T>, B: Schedule<T>> Schedule<T> for Or<A, B, T> {
+    fn initial(&self) -> Option<Duration> {
+        let now = clock::instant();
+        self.first_due
+            .set(self.first.initial().map(|delay| now + delay));
+        self.second_due
+            .set(self.second.initial().map(|delay| now + delay));
+        self.until_earliest(now)
+    }
+
+    fn next(&self, task_output: T) -> Option<Duration> {
+        self.advance(
+            || self.first.next(task_output.clone()),
+            || self.second.next(task_output.clone()),
+        )
+    }
+
+    fn next_on_task_panic(&self) -> Option<Duration> {
+        self.advance(
+            || self.first.next_on_task_panic(),
+            || self.second.next_on_task_panic(),
+        )
+    }
+
+    fn follows_wall_clock(&self) -> bool {
+        self.first.follows_wall_clock() || self.second.follows_wall_clock()
+    }
+}
+
+/// Stops a schedule once the task output meets a condition.
+///
+/// Created by [`Schedule::until`]. Usually followed by [`then`][Schedule::then] to switch
+///   to another schedule.
+///
+/// ```
+/// use periodically::{Schedule, IntervalSchedule};
+/// use std::time::Duration;
+///
+/// let schedule = IntervalSchedule::every(Duration::from_secs(1)).until(|warm: &bool| *warm);
+/// # let schedule = Box::new(schedule) as Box<dyn Schedule<bool>>;
+/// assert_eq!(schedule.next(false), Some(Duration::from_secs(1)));
+/// assert_eq!(schedule.next(true), None);
+/// ```
+pub struct Until<S, F> {
+    schedule: S,
+    condition: F,
+}
+
+impl<S, F> Until<S, F> {
+    pub(crate) fn new(schedule: S, condition: F) -> Self {
+        Self {
+            schedule,
+            condition,
+        }
+    }
+}
+
+impl<T, S: Schedule<T>, F: Fn(&T) -> bool> Schedule<T> for Until<S, F> {
+    fn initial(&self) -> Option<Duration> {
+        self.schedule.initial()
+    }
+
+    fn next(&self, task_output: T) -> Option<Duration> {
+        if (self.condition)(&task_output) {
+            None
+        } else {
+            self.schedule.next(task_output)
+        }
+    }
+
+    fn next_on_task_panic(&self) -> Option<Duration> {
+        self.schedule.next_on_task_panic()
+    }
+
+    fn follows_wall_clock(&self) -> bool {
+        self.schedule.follows_wall_clock()
+    }
+}
+
+/// Stops a schedule once a period of time has passed since it first started.
+///
+/// Created by [`Schedule::for_duration`]. Usually followed by [`then`][Schedule::then]
+///   to switch to another schedule.
+///
+/// ```
+/// use periodically::{Schedule, IntervalSchedule};
+/// use std::time::Duration;
+///
+/// let schedule = IntervalSchedule::every(Duration::from_secs(1))
+///     .for_duration(Duration::from_secs(60))
+///     .then(IntervalSchedule::every(Duration::from_secs(30)));
+/// # let schedule = Box::new(schedule) as Box<dyn Schedule<()>>;
+/// assert_eq!(schedule.initial(), Some(Duration::from_secs(1)));
+/// assert_eq!(schedule.next(()), Some(Duration::from_secs(1)));
+///
+/// let expired = IntervalSchedule::every(Duration::from_secs(1)).for_duration(Duration::ZERO);
+/// # let expired = Box::new(expired) as Box<dyn Schedule<()>>;
+/// assert_eq!(expired.initial(), None);
+/// ```
+pub struct Expiring<S, T> {
+    schedule: S,
+    lifetime: Duration,
+    started: Cell<Option<Instant>>,
+    output: PhantomData<fn(T)>,
+}
+
+impl<S, T> Expiring<S, T> {
+    pub(crate) fn new(schedule: S, lifetime: Duration) -> Self {
+        Self {
+            schedule,
+            lifetime,
+            started: Cell::new(None),
+            output: PhantomData,
+        }
+    }
+
+    fn unless_expired(&self, next: impl FnOnce() -> Option<Duration>) -> Option<Duration> {
+        let now = clock::instant();
+        let started = self.started.get().unwrap_or(now);
+        self.started.set(Some(started));
+        if now.saturating_duration_since(started) >= self.lifetime {
+            None
+        } else {
+            next()
+        }
+    }
+}
+
+impl<T, S: Schedule<T>> Schedule<T> for Expiring<S, T> {
+    fn initial(&self) -> Option<Duration> {
+        self.unless_expired(|| self.schedule.initial())
+    }
+
+    fn next(&self, task_output: T) -> Option<Duration> {
+        self.unless_expired(|| self.schedule.next(task_output))
+    }
+
+    fn next_on_task_panic(&self) -> Option<Duration> {
+        self.unless_expired(|| self.schedule.next_on_task_panic())
+    }
+
+    fn follows_wall_clock(&self) -> bool {
+        self.schedule.follows_wall_clock()
+    }
+}
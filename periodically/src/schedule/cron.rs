@@ -1,14 +1,52 @@
-use super::Schedule;
-use std::{str::FromStr, time::Duration};
+use super::{clock, Schedule};
+use chrono::{DateTime, Utc};
+use std::{cell::Cell, time::Duration};
 
-/// Runs jobs periodically based on a [`CronSchedule`] from the [`cron`] crate.
+mod expression;
+pub use expression::{CronError, CronExpression, CronField};
+
+/// Runs jobs periodically based on a [`CronExpression`], or a [`Schedule`][cron::Schedule] from the [`cron`] crate.
+///
+/// If a slot passes without the task running, eg; because the previous run took too long,
+///   or the host was suspended, the slot has misfired. What happens next is decided by the
+///   schedule's [`Misfire`] policy.
 pub struct CronSchedule {
-    cron: cron::Schedule,
+    timetable: Timetable,
+    misfire: Misfire,
+    /// The slot the task was last scheduled for.
+    scheduled: Cell<Option<DateTime<Utc>>>,
+}
+
+enum Timetable {
+    Expression(CronExpression),
+    Cron(Box<cron::Schedule>),
+}
+
+impl Timetable {
+    fn next_after(&self, after: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Timetable::Expression(expression) => expression.next_after(after),
+            Timetable::Cron(cron) => cron.after(after).next(),
+        }
+    }
+}
+
+/// Decides what a [`CronSchedule`] does with slots that passed without the task running.
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy, Default)]
+pub enum Misfire {
+    /// Misfired slots are dropped, and the task waits for the next upcoming slot.
+    #[default]
+    Skip,
+    /// If any slots misfired, the task runs once immediately.
+    FireOnce,
+    /// The task runs immediately for every misfired slot, one after the other.
+    FireAll,
 }
 
 /// Schedule events according to a cron schedule.
 impl CronSchedule {
-    /// Creates a [`CronSchedule`] that always returns `period`.
+    /// Creates a [`CronSchedule`] from a cron expression. Accepts classic 5 field crontab lines,
+    ///   expressions with a leading seconds field, and macros such as `@hourly`. See [`CronExpression`].
     ///
     /// ```
     /// use periodically::{Schedule, CronSchedule};
@@ -19,11 +57,21 @@ impl CronSchedule {
     /// # let periodic = Box::new(periodic) as Box<dyn Schedule<()>>;
     /// assert!(periodic.initial().unwrap() < Duration::from_secs(60));
     /// assert!(periodic.next(()).unwrap() < Duration::from_secs(60));
+    ///
+    /// let every_five_minutes = CronSchedule::from_cron_str("*/5 * * * *").unwrap();
+    /// # let every_five_minutes = Box::new(every_five_minutes) as Box<dyn Schedule<()>>;
+    /// assert!(every_five_minutes.initial().unwrap() <= Duration::from_secs(5 * 60));
     /// ```
-    pub fn from_cron_str(cron_str: impl AsRef<str>) -> Result<Self, cron::error::Error> {
-        let cron = cron::Schedule::from_str(cron_str.as_ref())?;
-        Ok(Self { cron })
+    pub fn from_cron_str(cron_str: impl AsRef<str>) -> Result<Self, CronError> {
+        let expression = cron_str.as_ref().parse()?;
+        Ok(Self::from_cron_expression(expression))
     }
+
+    /// Creates a [`CronSchedule`] from a parsed [`CronExpression`].
+    pub fn from_cron_expression(expression: CronExpression) -> Self {
+        Self::from_timetable(Timetable::Expression(expression))
+    }
+
     /// Creates a [`CronSchedule`] that always returns `period`.
     ///
     /// ```
@@ -38,25 +86,70 @@ impl CronSchedule {
     /// assert!(periodic.initial() < Some(Duration::from_secs(60)));
     /// assert!(periodic.next(()) < Some(Duration::from_secs(60)));
     /// ```
-
     pub fn from_cron_schedule(cron: cron::Schedule) -> Self {
-        Self { cron }
+        Self::from_timetable(Timetable::Cron(Box::new(cron)))
+    }
+
+    fn from_timetable(timetable: Timetable) -> Self {
+        Self {
+            timetable,
+            misfire: Misfire::default(),
+            scheduled: Cell::new(None),
+        }
+    }
+
+    /// Sets how slots that passed without the task running are handled. Defaults to [`Misfire::Skip`].
+    ///
+    /// ```
+    /// use periodically::{CronSchedule, Misfire};
+    ///
+    /// let schedule = CronSchedule::from_cron_str("0 0 * * * *")
+    ///     .unwrap()
+    ///     .with_misfire_policy(Misfire::FireOnce);
+    /// ```
+    pub fn with_misfire_policy(self, misfire: Misfire) -> Self {
+        Self { misfire, ..self }
     }
 
     fn calculate_next(&self) -> Option<Duration> {
-        let dt = self.cron.upcoming(chrono::Utc).next()?;
-        let now = chrono::Utc::now();
-        let delta = dt.signed_duration_since(now);
+        let now = DateTime::<Utc>::from(clock::now());
+        let slot = match (self.scheduled.get(), self.misfire) {
+            (Some(scheduled), Misfire::FireAll) => self.timetable.next_after(&scheduled)?,
+            (Some(scheduled), Misfire::FireOnce) => {
+                let slots = std::iter::successors(self.timetable.next_after(&scheduled), |slot| {
+                    self.timetable.next_after(slot)
+                });
+                match slots.take_while(|slot| *slot <= now).last() {
+                    Some(slot) => slot,
+                    None => self.timetable.next_after(&now)?,
+                }
+            }
+            _ => self.timetable.next_after(&now)?,
+        };
+        #[cfg(feature = "log")]
+        if slot < now {
+            log::warn!("Cron slot [{slot}] misfired, running it late.");
+        }
+        self.scheduled.set(Some(slot));
+        let delta = slot.signed_duration_since(now);
         Some(delta.to_std().unwrap_or(Duration::from_secs(0)))
     }
 }
 
 impl<T> Schedule<T> for CronSchedule {
     fn initial(&self) -> Option<Duration> {
-        self.calculate_next()
+        self.scheduled.set(None);
+        match &self.timetable {
+            Timetable::Expression(expression) if expression.is_reboot() => Some(Duration::ZERO),
+            _ => self.calculate_next(),
+        }
     }
 
     fn next(&self, _: T) -> Option<Duration> {
         self.calculate_next()
     }
+
+    fn follows_wall_clock(&self) -> bool {
+        true
+    }
 }
@@ -0,0 +1,563 @@
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Timelike, Utc, Weekday};
+use std::{collections::BTreeSet, fmt, str::FromStr};
+
+/// The macros understood by [`CronExpression`], and the classic crontab lines they stand for.
+const MACROS: [(&str, &str); 7] = [
+    ("@yearly", "0 0 1 1 *"),
+    ("@annually", "0 0 1 1 *"),
+    ("@monthly", "0 0 1 * *"),
+    ("@weekly", "0 0 * * 0"),
+    ("@daily", "0 0 * * *"),
+    ("@midnight", "0 0 * * *"),
+    ("@hourly", "0 * * * *"),
+];
+
+/// How many years ahead to search for the next matching time, when the expression has no year field.
+const SEARCH_YEARS: i32 = 400;
+
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+const SUNDAY_FIRST: [Weekday; 7] = [
+    Weekday::Sun,
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+];
+
+/// A parsed cron expression. Times are observed in UTC.
+///
+/// Three layouts are understood, told apart by their number of fields:
+/// * Classic crontab lines, with 5 fields: `minute hour day-of-month month day-of-week`.
+///   Days of the week are numbered 0-7, where both 0 and 7 are Sunday. As in crontab, if both
+///   day fields are restricted, a day matches if either of them does.
+/// * 6 fields, with a leading `second` field, as used by the [`cron`] crate.
+///   Days of the week are numbered 1-7, starting from Sunday, and a day must match both day fields.
+/// * 7 fields, which adds a trailing `year` field to the 6 field layout.
+///
+/// Fields hold comma separated lists of values (`5`), ranges (`1-5`), wildcards (`*`, or `?` in
+///   the day fields) and steps (`*/15`, `10-50/20`). Months and days of the week may be given
+///   by name, eg; `JAN` or `MON`. The day fields also support these extensions:
+/// * `L` in the day of month field is the last day of the month, and `L-3` is 3 days before it.
+/// * `15W` is the weekday (Monday to Friday) nearest to the 15th, within the same month.
+///   `LW` is the last weekday of the month.
+/// * `FRIL` or `5L` in the day of week field is the last Friday of the month.
+/// * `FRI#2` is the 2nd Friday of the month.
+///
+/// The macros `@yearly`, `@annually`, `@monthly`, `@weekly`, `@daily`, `@midnight` and `@hourly`
+///   stand for their usual crontab lines. `@reboot` runs once, when the schedule starts.
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use periodically::CronExpression;
+///
+/// let last_friday: CronExpression = "30 17 * * FRIL".parse().unwrap();
+/// let after = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+/// let expected = Utc.with_ymd_and_hms(2024, 5, 31, 17, 30, 0).unwrap();
+/// assert_eq!(last_friday.next_after(&after), Some(expected));
+///
+/// let error = "0 9 * * MON#6".parse::<CronExpression>().unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "invalid day of week field [MON#6]: the occurence must be between 1 and 5"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpression {
+    expression: String,
+    timetable: Timetable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Timetable {
+    Reboot,
+    Fields(Box<Fields>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Fields {
+    seconds: u64,
+    minutes: u64,
+    hours: u64,
+    days_of_month: DayField,
+    months: u64,
+    days_of_week: DayField,
+    years: Option<BTreeSet<i32>>,
+    /// Whether a day matches if either day field does, rather than both.
+    either_day: bool,
+}
+
+/// A day of month or day of week field. Days of the week are numbered from 0, for Sunday.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct DayField {
+    wildcard: bool,
+    values: u64,
+    special: Vec<SpecialDay>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpecialDay {
+    /// The given number of days before the last day of the month.
+    Last(u32),
+    /// The weekday nearest to the given day of the month.
+    NearestWeekday(u32),
+    LastWeekdayOfMonth,
+    LastOf(Weekday),
+    Nth(u32, Weekday),
+}
+
+/// How days of the week are numbered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Numbering {
+    /// 0-7, where 0 and 7 are Sunday.
+    Crontab,
+    /// 1-7, where 1 is Sunday.
+    SundayIsOne,
+}
+
+impl CronExpression {
+    /// Returns the first time strictly after `after` matching this expression.
+    ///   Always `None` for `@reboot`, which is not tied to any time.
+    pub fn next_after(&self, after: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match &self.timetable {
+            Timetable::Reboot => None,
+            Timetable::Fields(fields) => fields.next_after(after),
+        }
+    }
+
+    /// Whether this is the `@reboot` macro, which runs once when the schedule starts.
+    pub fn is_reboot(&self) -> bool {
+        self.timetable == Timetable::Reboot
+    }
+}
+
+impl FromStr for CronExpression {
+    type Err = CronError;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let trimmed = expression.trim();
+        let timetable = if trimmed.starts_with('@') {
+            let name = trimmed.to_ascii_lowercase();
+            match MACROS.iter().find(|(macro_name, _)| *macro_name == name) {
+                Some((_, crontab)) => Timetable::Fields(Box::new(Fields::parse(crontab)?)),
+                None if name == "@reboot" => Timetable::Reboot,
+                None => return Err(CronError::UnknownMacro(trimmed.to_owned())),
+            }
+        } else {
+            Timetable::Fields(Box::new(Fields::parse(trimmed)?))
+        };
+        Ok(Self {
+            expression: trimmed.to_owned(),
+            timetable,
+        })
+    }
+}
+
+impl fmt::Display for CronExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+impl Fields {
+    fn parse(expression: &str) -> Result<Self, CronError> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let (seconds, rest, numbering) = match fields.len() {
+            5 => ("0", &fields[..], Numbering::Crontab),
+            6 | 7 => (fields[0], &fields[1..], Numbering::SundayIsOne),
+            count => return Err(CronError::FieldCount(count)),
+        };
+        let seconds = bits(parse_list(CronField::Seconds, seconds, 0, 59, &[])?);
+        let minutes = bits(parse_list(CronField::Minutes, rest[0], 0, 59, &[])?);
+        let hours = bits(parse_list(CronField::Hours, rest[1], 0, 23, &[])?);
+        let days_of_month = parse_days_of_month(rest[2])?;
+        let months = bits(parse_list(CronField::Month, rest[3], 1, 12, &MONTHS)?);
+        let days_of_week = parse_days_of_week(rest[4], numbering)?;
+        let years = match rest.get(5) {
+            Some(years) => Some(
+                parse_list(CronField::Year, years, 1970, 2099, &[])?
+                    .into_iter()
+                    .map(|year| year as i32)
+                    .collect(),
+            ),
+            None => None,
+        };
+        Ok(Self {
+            seconds,
+            minutes,
+            hours,
+            either_day: numbering == Numbering::Crontab
+                && !days_of_month.wildcard
+                && !days_of_week.wildcard,
+            days_of_month,
+            months,
+            days_of_week,
+            years,
+        })
+    }
+
+    fn next_after(&self, after: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.naive_utc().with_nanosecond(0)? + chrono::Duration::seconds(1);
+        let last_year = match &self.years {
+            Some(years) => *years.last()?,
+            None => start.year().saturating_add(SEARCH_YEARS),
+        };
+        let mut date = start.date();
+        while date.year() <= last_year {
+            if self
+                .years
+                .as_ref()
+                .is_some_and(|years| !years.contains(&date.year()))
+            {
+                date = NaiveDate::from_ymd_opt(date.year() + 1, 1, 1)?;
+                continue;
+            }
+            if !has_bit(self.months, date.month()) {
+                date = first_of_next_month(date)?;
+                continue;
+            }
+            if self.matches_day(date) {
+                let not_before = (date == start.date()).then(|| start.time());
+                if let Some(time) = self.first_time(not_before) {
+                    return Some(date.and_time(time).and_utc());
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day_of_month = self.days_of_month.matches(date, date.day());
+        let day_of_week = self
+            .days_of_week
+            .matches(date, date.weekday().num_days_from_sunday());
+        if self.either_day {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+
+    /// The earliest time of day matching this expression, no earlier than `not_before`.
+    fn first_time(&self, not_before: Option<NaiveTime>) -> Option<NaiveTime> {
+        let (hour, minute, second) = not_before
+            .map(|time| (time.hour(), time.minute(), time.second()))
+            .unwrap_or_default();
+        for h in set_bits(self.hours).filter(|h| *h >= hour) {
+            let minutes = set_bits(self.minutes).filter(|m| h > hour || *m >= minute);
+            for m in minutes {
+                let mut seconds =
+                    set_bits(self.seconds).filter(|s| h > hour || m > minute || *s >= second);
+                if let Some(s) = seconds.next() {
+                    return NaiveTime::from_hms_opt(h, m, s);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl DayField {
+    fn matches(&self, date: NaiveDate, value: u32) -> bool {
+        self.wildcard
+            || has_bit(self.values, value)
+            || self.special.iter().any(|special| special.matches(date))
+    }
+}
+
+impl SpecialDay {
+    fn matches(&self, date: NaiveDate) -> bool {
+        let last_day = last_day_of_month(date).day();
+        match *self {
+            SpecialDay::Last(offset) => date.day() + offset == last_day,
+            SpecialDay::NearestWeekday(day) => {
+                let target = date.with_day(day).map(|target| match target.weekday() {
+                    Weekday::Sat if day == 1 => day + 2,
+                    Weekday::Sat => day - 1,
+                    Weekday::Sun if day == last_day => day - 2,
+                    Weekday::Sun => day + 1,
+                    _ => day,
+                });
+                target == Some(date.day())
+            }
+            SpecialDay::LastWeekdayOfMonth => {
+                let last_weekday = match last_day_of_month(date).weekday() {
+                    Weekday::Sat => last_day - 1,
+                    Weekday::Sun => last_day - 2,
+                    _ => last_day,
+                };
+                date.day() == last_weekday
+            }
+            SpecialDay::LastOf(weekday) => date.weekday() == weekday && date.day() + 7 > last_day,
+            SpecialDay::Nth(n, weekday) => {
+                date.weekday() == weekday && (date.day() - 1) / 7 + 1 == n
+            }
+        }
+    }
+}
+
+fn parse_days_of_month(field: &str) -> Result<DayField, CronError> {
+    let invalid =
+        |value: &str, reason: &str| CronError::invalid(CronField::DayOfMonth, value, reason);
+    let mut days = DayField::default();
+    for item in field.split(',') {
+        let upper = item.to_ascii_uppercase();
+        if item == "?" || item == "*" {
+            days.wildcard = true;
+        } else if upper == "LW" {
+            days.special.push(SpecialDay::LastWeekdayOfMonth);
+        } else if upper == "L" {
+            days.special.push(SpecialDay::Last(0));
+        } else if let Some(offset) = upper.strip_prefix("L-") {
+            let offset = offset
+                .parse()
+                .ok()
+                .filter(|offset| (1..=30).contains(offset))
+                .ok_or_else(|| {
+                    invalid(
+                        item,
+                        "the offset from the last day must be between 1 and 30",
+                    )
+                })?;
+            days.special.push(SpecialDay::Last(offset));
+        } else if let Some(day) = upper.strip_suffix('W') {
+            let day = day
+                .parse()
+                .ok()
+                .filter(|day| (1..=31).contains(day))
+                .ok_or_else(|| invalid(item, "the day must be between 1 and 31"))?;
+            days.special.push(SpecialDay::NearestWeekday(day));
+        } else {
+            days.values |= bits(parse_list(CronField::DayOfMonth, item, 1, 31, &[])?);
+        }
+    }
+    Ok(days)
+}
+
+fn parse_days_of_week(field: &str, numbering: Numbering) -> Result<DayField, CronError> {
+    let first = match numbering {
+        Numbering::Crontab => 0,
+        Numbering::SundayIsOne => 1,
+    };
+    let to_sunday_first = |value: u32| (value - first) % 7;
+    let weekday = |item: &str, text: &str| {
+        let value = parse_value(CronField::DayOfWeek, item, text, first, 7, &WEEKDAYS)?;
+        Ok(SUNDAY_FIRST[to_sunday_first(value) as usize])
+    };
+    let mut days = DayField::default();
+    for item in field.split(',') {
+        let upper = item.to_ascii_uppercase();
+        if item == "?" || item == "*" {
+            days.wildcard = true;
+        } else if let Some((text, n)) = upper.split_once('#') {
+            let n = n
+                .parse()
+                .ok()
+                .filter(|n| (1..=5).contains(n))
+                .ok_or_else(|| {
+                    CronError::invalid(
+                        CronField::DayOfWeek,
+                        item,
+                        "the occurence must be between 1 and 5",
+                    )
+                })?;
+            days.special.push(SpecialDay::Nth(n, weekday(item, text)?));
+        } else if upper == "L" {
+            return Err(CronError::invalid(
+                CronField::DayOfWeek,
+                item,
+                "L needs a day of the week, eg; FRIL for the last Friday of the month",
+            ));
+        } else if let Some(text) = upper.strip_suffix('L') {
+            days.special.push(SpecialDay::LastOf(weekday(item, text)?));
+        } else {
+            let values = parse_list(CronField::DayOfWeek, item, first, 7, &WEEKDAYS)?;
+            days.values |= bits(values.into_iter().map(to_sunday_first).collect());
+        }
+    }
+    Ok(days)
+}
+
+/// Parses a comma separated list of values, ranges and steps into the values it covers.
+///   `names` are alternatives to the numbers, starting from `min`.
+fn parse_list(
+    field: CronField,
+    list: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+) -> Result<Vec<u32>, CronError> {
+    let mut values = Vec::new();
+    for item in list.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| {
+                        CronError::invalid(field, item, "the step must be a positive number")
+                    })?;
+                (range, Some(step))
+            }
+            None => (item, None),
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (
+                parse_value(field, item, start, min, max, names)?,
+                parse_value(field, item, end, min, max, names)?,
+            ),
+            None => {
+                let value = parse_value(field, item, range, min, max, names)?;
+                (value, if step.is_some() { max } else { value })
+            }
+        };
+        if start > end {
+            return Err(CronError::invalid(
+                field,
+                item,
+                "the range starts after it ends",
+            ));
+        }
+        values.extend((start..=end).step_by(step.unwrap_or(1) as usize));
+    }
+    Ok(values)
+}
+
+fn parse_value(
+    field: CronField,
+    item: &str,
+    text: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+) -> Result<u32, CronError> {
+    let named = names
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(text))
+        .map(|index| index as u32 + min);
+    let value = match named {
+        Some(value) => value,
+        None => text.parse().map_err(|_| {
+            CronError::invalid(field, item, &format!("[{text}] is not a number or name"))
+        })?,
+    };
+    if !(min..=max).contains(&value) {
+        let reason = format!("[{value}] is out of range, expected {min}-{max}");
+        return Err(CronError::invalid(field, item, &reason));
+    }
+    Ok(value)
+}
+
+fn bits(values: Vec<u32>) -> u64 {
+    values.into_iter().fold(0, |bits, value| bits | 1 << value)
+}
+
+fn has_bit(bits: u64, value: u32) -> bool {
+    bits & (1 << value) != 0
+}
+
+fn set_bits(bits: u64) -> impl Iterator<Item = u32> {
+    (0..64).filter(move |bit| has_bit(bits, *bit))
+}
+
+fn first_of_next_month(date: NaiveDate) -> Option<NaiveDate> {
+    match date.month() {
+        12 => NaiveDate::from_ymd_opt(date.year() + 1, 1, 1),
+        month => NaiveDate::from_ymd_opt(date.year(), month + 1, 1),
+    }
+}
+
+fn last_day_of_month(date: NaiveDate) -> NaiveDate {
+    first_of_next_month(date)
+        .and_then(|first| first.pred_opt())
+        .unwrap_or(NaiveDate::MAX)
+}
+
+/// A field of a [`CronExpression`].
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+pub enum CronField {
+    Seconds,
+    Minutes,
+    Hours,
+    DayOfMonth,
+    Month,
+    DayOfWeek,
+    Year,
+}
+
+impl fmt::Display for CronField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CronField::Seconds => "seconds",
+            CronField::Minutes => "minutes",
+            CronField::Hours => "hours",
+            CronField::DayOfMonth => "day of month",
+            CronField::Month => "month",
+            CronField::DayOfWeek => "day of week",
+            CronField::Year => "year",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Reasons why a string is not a valid [`CronExpression`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CronError {
+    /// The expression does not have 5, 6 or 7 fields. Holds the number of fields found.
+    FieldCount(usize),
+    /// The expression starts with `@`, but is not a known macro.
+    UnknownMacro(String),
+    /// One of the fields is invalid.
+    InvalidField {
+        /// The invalid field.
+        field: CronField,
+        /// The part of the field which is invalid.
+        value: String,
+        /// What is wrong with `value`.
+        reason: String,
+    },
+}
+
+impl CronError {
+    fn invalid(field: CronField, value: &str, reason: &str) -> Self {
+        CronError::InvalidField {
+            field,
+            value: value.to_owned(),
+            reason: reason.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for CronError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CronError::FieldCount(count) => {
+                write!(f, "expected 5, 6 or 7 fields, found {count}")
+            }
+            CronError::UnknownMacro(name) => {
+                let macros: Vec<&str> = MACROS.iter().map(|(name, _)| *name).collect();
+                write!(
+                    f,
+                    "unknown macro [{name}], expected one of {}, @reboot",
+                    macros.join(", ")
+                )
+            }
+            CronError::InvalidField {
+                field,
+                value,
+                reason,
+            } => write!(f, "invalid {field} field [{value}]: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for CronError {}
@@ -33,6 +33,8 @@ impl IntervalSchedule {
 
     /// Creates a [Schedule] that will first return after a delay, and then always returns `interval`.
     ///
+    /// To align runs to wall-clock boundaries instead of a fixed delay, see [AlignedInterval][super::AlignedInterval].
+    ///
     /// ```
     /// use periodically::{Schedule, IntervalSchedule};
     /// use std::time::Duration;
@@ -1,6 +1,6 @@
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
-use super::Schedule;
+use super::{clock, Schedule};
 
 /// Schedules a single ('one shot') execution after
 /// a specified delay of time.
@@ -15,18 +15,46 @@ use super::Schedule;
 /// assert_eq!(one_shot.next(()), None);
 /// ```
 pub struct OneShot {
-    delay: Duration,
+    start: Start,
+}
+
+/// When a [OneShot] runs.
+enum Start {
+    After(Duration),
+    At(SystemTime),
 }
 
 impl OneShot {
     pub fn after(delay: Duration) -> Self {
-        Self { delay }
+        Self {
+            start: Start::After(delay),
+        }
+    }
+
+    /// Schedules a single execution at the wall-clock time `time`. Runs immediately if `time` has already passed.
+    ///
+    /// ```
+    /// use periodically::{Schedule, OneShot};
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// let one_shot = OneShot::at(SystemTime::now() + Duration::from_secs(60));
+    /// # let one_shot = Box::new(one_shot) as Box<dyn Schedule<()>>;
+    /// assert!(one_shot.initial().unwrap() <= Duration::from_secs(60));
+    /// assert_eq!(one_shot.next(()), None);
+    /// ```
+    pub fn at(time: SystemTime) -> Self {
+        Self {
+            start: Start::At(time),
+        }
     }
 }
 
 impl<T> Schedule<T> for OneShot {
     fn initial(&self) -> Option<std::time::Duration> {
-        Some(self.delay)
+        match self.start {
+            Start::After(delay) => Some(delay),
+            Start::At(time) => Some(time.duration_since(clock::now()).unwrap_or_default()),
+        }
     }
 
     fn next(&self, _: T) -> Option<std::time::Duration> {
@@ -36,4 +64,8 @@ impl<T> Schedule<T> for OneShot {
     fn next_on_task_panic(&self) -> Option<Duration> {
         None
     }
+
+    fn follows_wall_clock(&self) -> bool {
+        matches!(self.start, Start::At(_))
+    }
 }
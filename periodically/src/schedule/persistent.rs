@@ -0,0 +1,151 @@
+use std::{
+    cell::Cell,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use super::{clock, Schedule};
+use crate::{CatchUp, StateStore, TaskState};
+
+/// How far in the future to record tasks whose next execution is too far away to represent.
+const FAR_FUTURE: Duration = Duration::from_secs(100 * 365 * 24 * 60 * 60);
+
+/// Wraps another [Schedule], saving when the task last ran and when it is next due to a
+///   [StateStore], so that the schedule carries on where it left off after a process restart.
+///
+/// When the schedule starts, it starts the inner schedule, and looks up the state saved under its name:
+/// * If nothing was saved, the inner schedule's initial delay is used.
+/// * If the task finished before the restart (eg; a completed [OneShot][crate::OneShot]), it does not run again.
+/// * If the task is not due yet, it waits out the remainder of its delay.
+/// * If the task became due while the process was down, the [CatchUp] policy decides what happens.
+///   [CatchUp::RunAll] replays the delays of the inner schedule from the missed due time, running the
+///   task back to back until it is no longer behind. This assumes the inner schedule returns delays
+///   relative to the previous execution, like an [IntervalSchedule][crate::IntervalSchedule].
+///
+/// ```
+/// use periodically::{CatchUp, IntervalSchedule, MemoryStateStore, Persistent, Schedule};
+/// use std::{sync::Arc, time::Duration};
+///
+/// let store = Arc::new(MemoryStateStore::default());
+/// let daily = || IntervalSchedule::every(Duration::from_secs(24 * 60 * 60));
+///
+/// let schedule = Persistent::new("report", daily(), store.clone(), CatchUp::RunOnce);
+/// # let schedule = Box::new(schedule) as Box<dyn Schedule<()>>;
+/// assert_eq!(schedule.initial(), Some(Duration::from_secs(24 * 60 * 60)));
+/// schedule.next(());
+///
+/// // after a restart, the task is not due for another day, rather than running immediately
+/// let restored = Persistent::new("report", daily(), store, CatchUp::RunOnce);
+/// # let restored = Box::new(restored) as Box<dyn Schedule<()>>;
+/// assert!(restored.initial().unwrap() > Duration::from_secs(23 * 60 * 60));
+/// ```
+pub struct Persistent<S> {
+    name: String,
+    schedule: S,
+    store: Arc<dyn StateStore>,
+    catch_up: CatchUp,
+    /// While catching up on every missed run, the time at which the latest missed run was due.
+    behind: Cell<Option<SystemTime>>,
+}
+
+impl<S> Persistent<S> {
+    /// Wraps `schedule`, saving its state to `store` under `name`. Names must be unique per store.
+    pub fn new(
+        name: impl Into<String>,
+        schedule: S,
+        store: Arc<dyn StateStore>,
+        catch_up: CatchUp,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            schedule,
+            store,
+            catch_up,
+            behind: Cell::new(None),
+        }
+    }
+
+    fn load(&self) -> Option<TaskState> {
+        self.store
+            .load(&self.name)
+            .inspect_err(|_err| {
+                #[cfg(feature = "log")]
+                log::warn!("Cannot load state of task [{}]: {_err}", self.name);
+            })
+            .unwrap_or_default()
+    }
+
+    fn save(&self, last_run: Option<SystemTime>, now: SystemTime, next: Option<Duration>) {
+        let next_due = next.map(|delay| now.checked_add(delay).unwrap_or(now + FAR_FUTURE));
+        let state = TaskState { last_run, next_due };
+        if clock::is_simulated() {
+            return;
+        }
+        if let Err(_err) = self.store.save(&self.name, state) {
+            #[cfg(feature = "log")]
+            log::warn!("Cannot save state of task [{}]: {_err}", self.name);
+        }
+    }
+
+    /// Records a finished run, and applies any ongoing catch up to the inner schedule's next delay.
+    fn record(&self, next: Option<Duration>) -> Option<Duration> {
+        let now = clock::now();
+        let next = match (self.behind.take(), next) {
+            (Some(behind), Some(delay)) => {
+                let due = behind.checked_add(delay).unwrap_or(now + FAR_FUTURE);
+                match due.duration_since(now) {
+                    Ok(remaining) => Some(remaining),
+                    Err(_) => {
+                        self.behind.set(Some(due));
+                        Some(Duration::ZERO)
+                    }
+                }
+            }
+            (_, next) => next,
+        };
+        self.save(Some(now), now, next);
+        next
+    }
+}
+
+impl<T, S: Schedule<T>> Schedule<T> for Persistent<S> {
+    fn initial(&self) -> Option<Duration> {
+        let now = clock::now();
+        let state = self.load();
+        // the inner schedule always starts, so that eg; combinators set themselves up, even when
+        //   its delay gives way to the restored one
+        let initial = self.schedule.initial();
+        let next = match state {
+            None => initial,
+            Some(TaskState { next_due: None, .. }) => None,
+            Some(TaskState {
+                next_due: Some(due),
+                ..
+            }) => match due.duration_since(now) {
+                Ok(remaining) => Some(remaining),
+                Err(_) => match self.catch_up {
+                    CatchUp::Skip => initial,
+                    CatchUp::RunOnce => Some(Duration::ZERO),
+                    CatchUp::RunAll => {
+                        self.behind.set(Some(due));
+                        Some(Duration::ZERO)
+                    }
+                },
+            },
+        };
+        self.save(state.and_then(|state| state.last_run), now, next);
+        next
+    }
+
+    fn next(&self, task_output: T) -> Option<Duration> {
+        self.record(self.schedule.next(task_output))
+    }
+
+    fn next_on_task_panic(&self) -> Option<Duration> {
+        self.record(self.schedule.next_on_task_panic())
+    }
+
+    fn follows_wall_clock(&self) -> bool {
+        self.schedule.follows_wall_clock()
+    }
+}
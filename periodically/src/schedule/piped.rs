@@ -0,0 +1,88 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use super::Schedule;
+
+/// Wraps another [Schedule], keeping a copy of the latest task output for other tasks to read.
+///
+/// This is how the output of one task is passed to the tasks that depend on it. The
+///   upstream task is registered with a [Piped] schedule, and the dependent task holds on
+///   to the matching [PipedOutput]. Since the output is stored before any dependent tasks
+///   are triggered, a dependent task always sees the output of the run that triggered it.
+///
+/// ```
+/// use periodically::{Schedule, IntervalSchedule, Piped};
+/// use std::time::Duration;
+///
+/// let (piped, output) = Piped::<_, usize>::new(IntervalSchedule::every(Duration::from_secs(1)));
+/// # let piped = Box::new(piped) as Box<dyn Schedule<usize>>;
+/// assert_eq!(output.latest(), None);
+/// assert_eq!(piped.next(5), Some(Duration::from_secs(1)));
+/// assert_eq!(output.latest(), Some(5));
+/// ```
+pub struct Piped<S, T> {
+    schedule: S,
+    output: PipedOutput<T>,
+}
+
+impl<S, T> Piped<S, T> {
+    /// Wraps `schedule`, returning the wrapped schedule along with a reader of its task's output.
+    pub fn new(schedule: S) -> (Self, PipedOutput<T>) {
+        let output = PipedOutput {
+            latest: Arc::new(Mutex::new(None)),
+        };
+        let piped = Self {
+            schedule,
+            output: output.clone(),
+        };
+        (piped, output)
+    }
+}
+
+impl<S: Schedule<T>, T: Clone> Schedule<T> for Piped<S, T> {
+    fn initial(&self) -> Option<Duration> {
+        self.schedule.initial()
+    }
+
+    fn next(&self, task_output: T) -> Option<Duration> {
+        *self.output.latest.lock().unwrap() = Some(task_output.clone());
+        self.schedule.next(task_output)
+    }
+
+    fn next_on_task_panic(&self) -> Option<Duration> {
+        self.schedule.next_on_task_panic()
+    }
+
+    fn follows_wall_clock(&self) -> bool {
+        self.schedule.follows_wall_clock()
+    }
+}
+
+/// Reads the outputs stored by a [Piped] schedule. Can be cloned and shared between tasks.
+pub struct PipedOutput<T> {
+    latest: Arc<Mutex<Option<T>>>,
+}
+
+impl<T> PipedOutput<T> {
+    /// Takes the latest output, leaving nothing behind until the next run of the task.
+    pub fn take(&self) -> Option<T> {
+        self.latest.lock().unwrap().take()
+    }
+}
+
+impl<T: Clone> PipedOutput<T> {
+    /// Returns a copy of the latest output, if the task has produced one.
+    pub fn latest(&self) -> Option<T> {
+        self.latest.lock().unwrap().clone()
+    }
+}
+
+impl<T> Clone for PipedOutput<T> {
+    fn clone(&self) -> Self {
+        Self {
+            latest: self.latest.clone(),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::{
+    marker::PhantomData,
+    time::{Duration, Instant, SystemTime},
+};
+
+use super::{
+    clock::{self, Simulation},
+    Schedule,
+};
+
+/// Projects the times at which a schedule would run a task, without running anything.
+///
+/// Created by [`Schedule::preview`]. Yields each time the task would start, until the schedule
+///   returns `None`. The simulation assumes that the task finishes as soon as it starts, and
+///   feeds it the outputs given to [`preview`][Schedule::preview].
+///
+/// While previewing, schedules observe a simulated clock, so that time-based schedules such as
+///   [`OneShot::at`][crate::OneShot::at] or a `CronSchedule` project correctly. A [`Persistent`][crate::Persistent]
+///   schedule reads its saved state, but does not save anything.
+///
+/// The preview drives the schedule through its states, as if the task ran. Preview a fresh
+///   schedule, rather than one that is about to be registered.
+///
+/// ```
+/// use periodically::{IntervalSchedule, OneShot, Schedule};
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let schedule = OneShot::after(Duration::from_secs(1))
+///     .then(IntervalSchedule::every(Duration::from_secs(5)));
+/// let times: Vec<_> = schedule.preview(UNIX_EPOCH, || ()).take(3).collect();
+/// assert_eq!(times, vec![
+///     UNIX_EPOCH + Duration::from_secs(1),
+///     UNIX_EPOCH + Duration::from_secs(6),
+///     UNIX_EPOCH + Duration::from_secs(11),
+/// ]);
+/// ```
+pub struct Preview<'a, S, T, F> {
+    schedule: &'a S,
+    outputs: F,
+    from: SystemTime,
+    started: Instant,
+    /// How far the simulated clock has advanced, or `None` before the first run.
+    elapsed: Option<Duration>,
+    finished: bool,
+    output: PhantomData<fn() -> T>,
+}
+
+impl<'a, S, T, F> Preview<'a, S, T, F> {
+    pub(crate) fn new(schedule: &'a S, from: SystemTime, outputs: F) -> Self {
+        Self {
+            schedule,
+            outputs,
+            from,
+            started: Instant::now(),
+            elapsed: None,
+            finished: false,
+            output: PhantomData,
+        }
+    }
+}
+
+impl<S: Schedule<T>, T, F: FnMut() -> T> Iterator for Preview<'_, S, T, F> {
+    type Item = SystemTime;
+
+    fn next(&mut self) -> Option<SystemTime> {
+        if self.finished {
+            return None;
+        }
+        let elapsed = self.elapsed.unwrap_or_default();
+        let simulation = Simulation {
+            from: self.from,
+            started: self.started,
+            elapsed,
+        };
+        let delay = match self.elapsed {
+            None => clock::simulate(simulation, || self.schedule.initial()),
+            Some(_) => {
+                let output = (self.outputs)();
+                clock::simulate(simulation, || self.schedule.next(output))
+            }
+        };
+        let elapsed = delay.and_then(|delay| elapsed.checked_add(delay));
+        let time = elapsed.and_then(|elapsed| self.from.checked_add(elapsed));
+        self.elapsed = elapsed;
+        self.finished = time.is_none();
+        time
+    }
+}
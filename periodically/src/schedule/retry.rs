@@ -0,0 +1,106 @@
+use std::{cell::Cell, time::Duration};
+
+use super::Schedule;
+
+/// Wraps another [Schedule], retrying failed executions before falling back to it.
+///
+/// Implemented for tasks returning a [Result]. If the result is `Ok`, the inner schedule
+///   decides when the task runs next. If the result is `Err`, or the task panics, the task is
+///   retried up to `max_retries` times. Once the retries are exhausted, the inner schedule decides
+///   when the task runs next, and the retry count starts over.
+///
+/// ```
+/// use periodically::{Schedule, IntervalSchedule, RetryOnError};
+/// use std::time::Duration;
+///
+/// let hourly = IntervalSchedule::every(Duration::from_secs(3600));
+/// let schedule = RetryOnError::new(hourly, 2, Duration::from_secs(1)).with_multiplier(2.0);
+/// # let schedule = Box::new(schedule) as Box<dyn Schedule<Result<(), ()>>>;
+/// assert_eq!(schedule.next(Ok(())), Some(Duration::from_secs(3600)));
+/// assert_eq!(schedule.next(Err(())), Some(Duration::from_secs(1)));
+/// assert_eq!(schedule.next_on_task_panic(), Some(Duration::from_secs(2)));
+/// assert_eq!(schedule.next(Err(())), Some(Duration::from_secs(3600)));
+/// assert_eq!(schedule.next(Err(())), Some(Duration::from_secs(1)));
+/// ```
+pub struct RetryOnError<S> {
+    schedule: S,
+    max_retries: usize,
+    delay: Duration,
+    multiplier: f64,
+    max_delay: Option<Duration>,
+    retries: Cell<usize>,
+}
+
+impl<S> RetryOnError<S> {
+    /// Creates a [RetryOnError] which retries up to `max_retries` times, waiting `delay` before each retry.
+    pub fn new(schedule: S, max_retries: usize, delay: Duration) -> Self {
+        Self {
+            schedule,
+            max_retries,
+            delay,
+            multiplier: 1.0,
+            max_delay: None,
+            retries: Cell::new(0),
+        }
+    }
+
+    /// Multiplies the delay by `multiplier` after every consecutive retry, for an exponential backoff.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `multiplier` is less than one, or is not finite.
+    pub fn with_multiplier(self, multiplier: f64) -> Self {
+        assert!(
+            multiplier >= 1.0 && multiplier.is_finite(),
+            "multiplier must be finite and at least one"
+        );
+        Self { multiplier, ..self }
+    }
+
+    /// Caps the delay between retries to `max_delay`.
+    pub fn with_max_delay(self, max_delay: Duration) -> Self {
+        Self {
+            max_delay: Some(max_delay),
+            ..self
+        }
+    }
+
+    /// Returns the delay until the next retry, or `None` if the retries are exhausted.
+    fn retry(&self) -> Option<Duration> {
+        let retries = self.retries.get();
+        if retries >= self.max_retries {
+            self.retries.set(0);
+            return None;
+        }
+        self.retries.set(retries + 1);
+        let factor = self.multiplier.powi(retries.try_into().unwrap_or(i32::MAX));
+        let delay =
+            Duration::try_from_secs_f64(self.delay.as_secs_f64() * factor).unwrap_or(Duration::MAX);
+        Some(
+            self.max_delay
+                .map_or(delay, |max_delay| delay.min(max_delay)),
+        )
+    }
+}
+
+impl<T, E, S: Schedule<Result<T, E>>> Schedule<Result<T, E>> for RetryOnError<S> {
+    fn initial(&self) -> Option<Duration> {
+        self.schedule.initial()
+    }
+
+    fn next(&self, task_output: Result<T, E>) -> Option<Duration> {
+        if task_output.is_ok() {
+            self.retries.set(0);
+            return self.schedule.next(task_output);
+        }
+        self.retry().or_else(|| self.schedule.next(task_output))
+    }
+
+    fn next_on_task_panic(&self) -> Option<Duration> {
+        self.retry().or_else(|| self.schedule.next_on_task_panic())
+    }
+
+    fn follows_wall_clock(&self) -> bool {
+        self.schedule.follows_wall_clock()
+    }
+}
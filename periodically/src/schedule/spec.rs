@@ -0,0 +1,204 @@
+use super::{IntervalSchedule, OneShot, Schedule};
+use chrono::DateTime;
+use serde::{de, Deserialize, Deserializer};
+use std::{fmt, str::FromStr, time::Duration, time::SystemTime};
+
+/// A schedule written as a string, eg; in a configuration file.
+///
+/// The following forms are understood:
+/// * Durations, such as `"90s"`, `"1h30m"` or `"every 10 seconds"`, run the task on an [`IntervalSchedule`].
+///   Each component is a whole number followed by a unit: `ms`, `s`, `m`, `h`, `d` or `w`, or their
+///   spelled out names such as `minutes`. With an `every` prefix, the number may be left out, as in `"every hour"`.
+/// * Cron expressions, such as `"*/5 * * * *"`, and cron macros, such as `"@hourly"`, run the task on a
+///   [`CronSchedule`][crate::CronSchedule]. See [`CronExpression`][crate::CronExpression] for the syntax.
+///   Requires the `cron` feature.
+/// * Timestamps, such as `"at 2030-01-01T09:00:00Z"`, run the task once at that time with a [`OneShot`].
+///   Timestamps are in RFC 3339 format.
+///
+/// A [`ScheduleSpec`] can be parsed with [`str::parse`], or deserialized with `serde` from a string.
+///
+/// ```
+/// use periodically::{Schedule, ScheduleSpec};
+/// use std::time::Duration;
+///
+/// let spec: ScheduleSpec = "1h30m".parse().unwrap();
+/// assert_eq!(spec, ScheduleSpec::Every(Duration::from_secs(90 * 60)));
+///
+/// let schedule = spec.into_schedule::<()>();
+/// assert_eq!(schedule.next(()), Some(Duration::from_secs(90 * 60)));
+///
+/// let error = "5 fortnights".parse::<ScheduleSpec>().unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "invalid schedule [5 fortnights]: unknown unit [fortnights], expected one of ms, s, m, h, d, w"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleSpec {
+    /// Runs the task every given interval.
+    Every(Duration),
+    /// Runs the task according to a cron schedule.
+    #[cfg(feature = "cron")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cron")))]
+    Cron(crate::CronExpression),
+    /// Runs the task once, at the given time.
+    At(SystemTime),
+}
+
+impl ScheduleSpec {
+    /// Creates the [`Schedule`] described by this spec.
+    pub fn into_schedule<T: 'static>(self) -> Box<dyn Schedule<T> + Send> {
+        match self {
+            ScheduleSpec::Every(interval) => Box::new(IntervalSchedule::every(interval)),
+            #[cfg(feature = "cron")]
+            ScheduleSpec::Cron(cron) => Box::new(crate::CronSchedule::from_cron_expression(cron)),
+            ScheduleSpec::At(time) => Box::new(OneShot::at(time)),
+        }
+    }
+}
+
+impl FromStr for ScheduleSpec {
+    type Err = ScheduleSpecError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let trimmed = spec.trim();
+        let error = |reason: String| ScheduleSpecError {
+            spec: spec.to_owned(),
+            reason,
+        };
+        let lowercase = trimmed.to_ascii_lowercase();
+        if trimmed.is_empty() {
+            Err(error("the schedule is empty".to_owned()))
+        } else if lowercase.starts_with("at ") {
+            let timestamp = trimmed["at ".len()..].trim();
+            let time = DateTime::parse_from_rfc3339(timestamp)
+                .map_err(|err| error(format!("invalid timestamp [{timestamp}]: {err}")))?;
+            Ok(ScheduleSpec::At(time.into()))
+        } else if let Some(interval) = lowercase.strip_prefix("every ") {
+            parse_duration(interval, true)
+                .map(ScheduleSpec::Every)
+                .map_err(error)
+        } else if is_cron(trimmed) {
+            parse_cron(trimmed).map_err(error)
+        } else {
+            // durations come first, since eg; "1 h 2 m 3 s" has as many fields as a cron expression
+            match parse_duration(&lowercase, false) {
+                Ok(interval) => Ok(ScheduleSpec::Every(interval)),
+                Err(_) if has_cron_fields(trimmed) => parse_cron(trimmed).map_err(error),
+                Err(reason) => Err(error(reason)),
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ScheduleSpec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let spec = String::deserialize(deserializer)?;
+        spec.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Whether a spec can only be a cron expression or macro, rather than a duration.
+fn is_cron(spec: &str) -> bool {
+    spec.starts_with('@') || spec.contains(['*', '?'])
+}
+
+/// Whether a spec which is not a duration has as many fields as a cron expression.
+fn has_cron_fields(spec: &str) -> bool {
+    spec.split_whitespace().count() >= 5
+}
+
+#[cfg(feature = "cron")]
+fn parse_cron(spec: &str) -> Result<ScheduleSpec, String> {
+    spec.parse()
+        .map(ScheduleSpec::Cron)
+        .map_err(|err| format!("invalid cron expression: {err}"))
+}
+
+#[cfg(not(feature = "cron"))]
+fn parse_cron(_: &str) -> Result<ScheduleSpec, String> {
+    Err("cron expressions require the `cron` feature".to_owned())
+}
+
+/// Parses a sum of durations such as `1h 30m`. If `implicit_one` is set, a lone unit counts as one of that unit.
+fn parse_duration(spec: &str, implicit_one: bool) -> Result<Duration, String> {
+    let mut remaining = spec.trim();
+    let mut total = Duration::ZERO;
+    while !remaining.is_empty() {
+        let digits = remaining
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(remaining.len());
+        let (number, rest) = remaining.split_at(digits);
+        let rest = rest.trim_start();
+        let letters = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let (unit, rest) = rest.split_at(letters);
+        let count = match number {
+            "" if implicit_one && total.is_zero() && rest.trim().is_empty() => 1,
+            "" => return Err(format!("expected a number at [{remaining}]")),
+            number => number
+                .parse::<u32>()
+                .map_err(|_| format!("number [{number}] is too large"))?,
+        };
+        if unit.is_empty() {
+            return Err(format!(
+                "missing unit after [{number}], expected one of ms, s, m, h, d, w"
+            ));
+        }
+        let component = unit_duration(unit)
+            .ok_or_else(|| format!("unknown unit [{unit}], expected one of ms, s, m, h, d, w"))?
+            .checked_mul(count)
+            .ok_or_else(|| format!("[{number}{unit}] is too long"))?;
+        total = total
+            .checked_add(component)
+            .ok_or_else(|| "the interval is too long".to_owned())?;
+        remaining = rest.trim_start().trim_start_matches(',').trim_start();
+    }
+    if total.is_zero() {
+        return Err("the interval must be longer than zero".to_owned());
+    }
+    Ok(total)
+}
+
+fn unit_duration(unit: &str) -> Option<Duration> {
+    let seconds = match unit {
+        "ms" | "milli" | "millis" | "millisecond" | "milliseconds" => {
+            return Some(Duration::from_millis(1))
+        }
+        "s" | "sec" | "secs" | "second" | "seconds" => 1,
+        "m" | "min" | "mins" | "minute" | "minutes" => 60,
+        "h" | "hr" | "hrs" | "hour" | "hours" => 60 * 60,
+        "d" | "day" | "days" => 24 * 60 * 60,
+        "w" | "week" | "weeks" => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    Some(Duration::from_secs(seconds))
+}
+
+/// Describes why a string is not a valid [`ScheduleSpec`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleSpecError {
+    spec: String,
+    reason: String,
+}
+
+impl ScheduleSpecError {
+    /// The string which could not be parsed.
+    pub fn spec(&self) -> &str {
+        &self.spec
+    }
+
+    /// What was wrong with the string.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl fmt::Display for ScheduleSpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid schedule [{}]: {}", self.spec, self.reason)
+    }
+}
+
+impl std::error::Error for ScheduleSpecError {}
@@ -0,0 +1,222 @@
+use super::{calendar::resolve, clock, Schedule};
+use chrono::{DateTime, Days, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use std::time::Duration;
+
+/// How many days ahead to search for an allowed time before giving up. Windows repeat daily, so
+///   a time which is not allowed within two days is never allowed.
+const SEARCH_DAYS: u64 = 2;
+
+/// Daily periods in which tasks may run, and blackout periods in which they must not, as observed
+///   in a timezone. Applied to a schedule through [`Windowed`].
+///
+/// Without any allowed windows, every time which is not blocked is allowed. A period whose end is
+///   before its start spans midnight, eg; `22:00` to `06:00`. Blackouts take precedence over
+///   allowed windows.
+///
+/// ```
+/// use chrono::{NaiveTime, TimeZone, Utc};
+/// use periodically::TimeWindows;
+///
+/// let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+/// let windows = TimeWindows::new(Utc)
+///     .allow(time(22), time(6))
+///     .block(time(1), time(2));
+///
+/// let noon = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+/// let night = Utc.with_ymd_and_hms(2024, 3, 1, 22, 0, 0).unwrap();
+/// assert_eq!(windows.next_allowed(&noon), Some(night));
+/// let blocked = Utc.with_ymd_and_hms(2024, 3, 2, 1, 30, 0).unwrap();
+/// let unblocked = Utc.with_ymd_and_hms(2024, 3, 2, 2, 0, 0).unwrap();
+/// assert_eq!(windows.next_allowed(&blocked), Some(unblocked));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeWindows<Tz: TimeZone> {
+    timezone: Tz,
+    allowed: Vec<Period>,
+    blackouts: Vec<Period>,
+}
+
+/// A daily period, from `start` up to but excluding `end`.
+#[derive(Debug, Clone, Copy)]
+struct Period {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl Period {
+    fn spans_midnight(&self) -> bool {
+        self.end < self.start
+    }
+
+    fn contains(&self, time: NaiveTime) -> bool {
+        match self.spans_midnight() {
+            true => time >= self.start || time < self.end,
+            false => time >= self.start && time < self.end,
+        }
+    }
+
+    /// When the period containing `local` ends, or `None` if `local` is outside the period.
+    fn end_after(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        if !self.contains(local.time()) {
+            return None;
+        }
+        let date = local.date();
+        match self.spans_midnight() && local.time() >= self.start {
+            true => date.checked_add_days(Days::new(1)),
+            false => Some(date),
+        }
+        .map(|date| date.and_time(self.end))
+    }
+
+    /// When the period next starts, strictly after `local`.
+    fn start_after(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        let date = local.date();
+        match local.time() < self.start {
+            true => Some(date),
+            false => date.checked_add_days(Days::new(1)),
+        }
+        .map(|date| date.and_time(self.start))
+    }
+}
+
+impl<Tz: TimeZone> TimeWindows<Tz> {
+    /// Creates [`TimeWindows`] observed in `timezone`, which allow every time until restricted.
+    pub fn new(timezone: Tz) -> Self {
+        Self {
+            timezone,
+            allowed: Vec::new(),
+            blackouts: Vec::new(),
+        }
+    }
+
+    /// Allows tasks to run every day from `start` up to `end`. Once a window is allowed, times
+    ///   outside of every allowed window are not.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` and `end` are the same.
+    pub fn allow(mut self, start: NaiveTime, end: NaiveTime) -> Self {
+        assert_ne!(start, end, "the window must not be empty");
+        self.allowed.push(Period { start, end });
+        self
+    }
+
+    /// Stops tasks from running every day from `start` up to `end`, even within an allowed window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` and `end` are the same.
+    pub fn block(mut self, start: NaiveTime, end: NaiveTime) -> Self {
+        assert_ne!(start, end, "the blackout must not be empty");
+        self.blackouts.push(Period { start, end });
+        self
+    }
+
+    /// Returns `at` if it is allowed, or else the first allowed time after it. Returns `None` if no
+    ///   time is allowed, eg; because the blackouts cover every allowed window.
+    pub fn next_allowed(&self, at: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let from = at.naive_local();
+        let limit = from.checked_add_days(Days::new(SEARCH_DAYS))?;
+        let mut local = from;
+        // every step moves forwards onto one of the finitely many boundaries before the limit
+        while local <= limit {
+            if let Some(end) = self
+                .blackouts
+                .iter()
+                .filter_map(|blackout| blackout.end_after(local))
+                .max()
+            {
+                local = end;
+                continue;
+            }
+            if !self.allowed.is_empty()
+                && !self
+                    .allowed
+                    .iter()
+                    .any(|window| window.contains(local.time()))
+            {
+                local = self
+                    .allowed
+                    .iter()
+                    .filter_map(|window| window.start_after(local))
+                    .min()?;
+                continue;
+            }
+            return match local == from {
+                true => Some(at.clone()),
+                false => resolve(&self.timezone, local),
+            };
+        }
+        None
+    }
+}
+
+/// Wraps another [Schedule], pushing every run which would fall outside of its [`TimeWindows`] to
+///   the next allowed time.
+///
+/// Works with any schedule, eg; an `IntervalSchedule` to poll every 10 minutes only during office
+///   hours, or a `BackoffSchedule` whose retries must not land in a nightly maintenance window.
+///
+/// ```
+/// use chrono::{NaiveTime, Utc};
+/// use periodically::{IntervalSchedule, Schedule, TimeWindows, Windowed};
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+/// // runs every 8 hours, except at night
+/// let schedule = Windowed::new(
+///     IntervalSchedule::every(Duration::from_secs(8 * 60 * 60)),
+///     TimeWindows::new(Utc).block(time(22), time(6)),
+/// );
+///
+/// // the epoch is at midnight
+/// let hours: Vec<_> = schedule
+///     .preview(UNIX_EPOCH, || ())
+///     .take(4)
+///     .map(|at| at.duration_since(UNIX_EPOCH).unwrap().as_secs() / 60 / 60)
+///     .collect();
+/// assert_eq!(hours, vec![8, 16, 24 + 6, 24 + 14]);
+/// ```
+pub struct Windowed<S, Tz: TimeZone> {
+    schedule: S,
+    windows: TimeWindows<Tz>,
+}
+
+impl<S, Tz: TimeZone> Windowed<S, Tz> {
+    /// Creates a [Windowed] which keeps the runs of `schedule` within `windows`.
+    pub fn new(schedule: S, windows: TimeWindows<Tz>) -> Self {
+        Self { schedule, windows }
+    }
+
+    /// Extends `delay` until the run it leads to is allowed.
+    fn within_windows(&self, delay: Duration) -> Option<Duration> {
+        let Some(fire) = clock::now().checked_add(delay) else {
+            return Some(delay);
+        };
+        let fire = DateTime::<Utc>::from(fire).with_timezone(&self.windows.timezone);
+        let allowed = self.windows.next_allowed(&fire)?;
+        let pushed = allowed
+            .signed_duration_since(fire)
+            .to_std()
+            .unwrap_or_default();
+        delay.checked_add(pushed)
+    }
+}
+
+impl<T, S: Schedule<T>, Tz: TimeZone> Schedule<T> for Windowed<S, Tz> {
+    fn initial(&self) -> Option<Duration> {
+        self.within_windows(self.schedule.initial()?)
+    }
+
+    fn next(&self, task_output: T) -> Option<Duration> {
+        self.within_windows(self.schedule.next(task_output)?)
+    }
+
+    fn next_on_task_panic(&self) -> Option<Duration> {
+        self.within_windows(self.schedule.next_on_task_panic()?)
+    }
+
+    fn follows_wall_clock(&self) -> bool {
+        true
+    }
+}
@@ -1,16 +1,76 @@
-use crate::{schedule::Schedule, AsyncTask, Task};
+use crate::{schedule::Schedule, AsyncTask, CatchUp, Error, Persistent, StateStore, Task};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, Weak},
+};
+
+mod builder;
+pub(crate) use builder::TaskOptions;
+pub use builder::{Overlap, TaskBuilder};
+
+mod executor;
+pub use executor::{SyncExecutor, ThreadPool};
+
+mod group;
+pub(crate) use group::GroupId;
+pub use group::TaskGroup;
+
+mod supervision;
+pub(crate) use supervision::ScheduleFactory;
+pub use supervision::{Escalation, ScheduleFailure, Supervision, TaskStatus};
+
+mod identifiers;
+pub(crate) use identifiers::Identifiers;
+pub use identifiers::{ParseTaskIdentifierError, TaskIdentifier};
+
+mod dependencies;
+pub(crate) use dependencies::DependencyGraph;
+pub use dependencies::{Dependency, DependencyError, Trigger};
 
 cfg_feature! {
     "tokio",
     mod tokio_scheduler;
+    mod outputs;
+    pub use outputs::TaskFailure;
+    mod handle;
+    pub use handle::SchedulerHandle;
+    mod local;
+    mod maintenance;
+    pub use maintenance::InFlightRuns;
+}
+
+cfg_feature! {
+    "stream",
+    pub use outputs::OutputStream;
 }
 
 /// The core scheduler of `periodically`. Provides the interfaces for
 ///   scheduling individual tasks.
+///
+/// To manage tasks from other threads or from within tasks, see [`handle`][Scheduler::handle].
 pub struct Scheduler {
+    /// Shared with the scheduler's [`SchedulerHandle`]s.
+    core: Arc<Mutex<SchedulerCore>>,
+    #[cfg(feature = "tokio")]
+    handle: SchedulerHandle,
+    /// Dropped last, once the tasks running on it are no longer tracked.
+    #[cfg(feature = "tokio")]
+    owned_runtime: Option<tokio::runtime::Runtime>,
+}
+
+/// The state of a [Scheduler], which is changed either directly or through a [SchedulerHandle].
+pub(crate) struct SchedulerCore {
     flavour: SchedulerFlavour,
-    next_identifier: TaskIdentifier,
-    in_use_ids: Vec<TaskIdentifier>,
+    identifiers: Identifiers,
+    state_store: Option<(Arc<dyn StateStore>, CatchUp)>,
+    /// The identifiers of named tasks, by name.
+    names: HashMap<String, TaskIdentifier>,
+    /// See [TaskGroup].
+    groups: HashMap<GroupId, group::Group>,
+    /// The group of each task registered into one.
+    group_of: HashMap<TaskIdentifier, GroupId>,
+    /// See [Scheduler::with_pause_catch_up].
+    pause_catch_up: CatchUp,
 }
 
 enum SchedulerFlavour {
@@ -20,36 +80,96 @@ enum SchedulerFlavour {
 
 impl Scheduler {
     fn from_flavour(flavour: impl Into<SchedulerFlavour>) -> Self {
-        Self {
+        let core = Arc::new(Mutex::new(SchedulerCore {
             flavour: flavour.into(),
-            next_identifier: Default::default(),
-            in_use_ids: Default::default(),
+            identifiers: Identifiers::new(),
+            state_store: None,
+            names: HashMap::new(),
+            groups: HashMap::new(),
+            group_of: HashMap::new(),
+            pause_catch_up: CatchUp::default(),
+        }));
+        let (handle, commands) = SchedulerHandle::channel();
+        match &mut core.lock().unwrap().flavour {
+            #[cfg(feature = "tokio")]
+            SchedulerFlavour::Tokio(tok) => {
+                tok.serve(handle.clone(), commands, Arc::downgrade(&core))
+            }
+        }
+        Self {
+            core,
+            handle,
+            owned_runtime: None,
         }
     }
 
+    fn core(&self) -> MutexGuard<'_, SchedulerCore> {
+        self.core.lock().unwrap()
+    }
+
+    /// Saves the state of named tasks to `store`, so that they carry on where they left off
+    ///   after a process restart. Runs missed while the process was down are handled according
+    ///   to `catch_up`.
+    ///
+    /// Only applies to tasks registered with [`add_named_sync_task`][Scheduler::add_named_sync_task]
+    ///   or [`add_named_async_task`][Scheduler::add_named_async_task]. See [`Persistent`] for details.
+    pub fn with_state_store(self, store: impl StateStore + 'static, catch_up: CatchUp) -> Self {
+        self.core().state_store = Some((Arc::new(store), catch_up));
+        self
+    }
+
+    /// Registers a synchronous [`Task`] under a `name`, which must be unique among the tasks
+    ///   registered with this scheduler. If the scheduler has a state store, the task's schedule
+    ///   is restored from it. Cancelling the task, or its schedule finishing, frees up its name.
+    ///
+    /// Returns [`Error::DuplicateName`] if the name is taken. Otherwise identical to
+    ///   [`add_sync_task`][Scheduler::add_sync_task].
+    pub fn add_named_sync_task<T: Send + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        task: impl Task<T> + Send + Sync + 'static,
+        schedule: impl Schedule<T> + Send + 'static,
+    ) -> Result<TaskIdentifier, Error> {
+        let options = TaskOptions {
+            name: Some(name.into()),
+            ..Default::default()
+        };
+        self.core().register_sync_task(task, schedule, options)
+    }
+
+    /// Registers an asynchronous [`AsyncTask`] under a `name`, which must be unique among the tasks
+    ///   registered with this scheduler. If the scheduler has a state store, the task's schedule
+    ///   is restored from it. Cancelling the task, or its schedule finishing, frees up its name.
+    ///
+    /// Returns [`Error::DuplicateName`] if the name is taken. Otherwise identical to
+    ///   [`add_async_task`][Scheduler::add_async_task].
+    pub fn add_named_async_task<T: Send + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        task: impl AsyncTask<T> + Send + Sync + 'static,
+        schedule: impl Schedule<T> + Send + 'static,
+    ) -> Result<TaskIdentifier, Error> {
+        let options = TaskOptions {
+            name: Some(name.into()),
+            ..Default::default()
+        };
+        self.core().register_async_task(task, schedule, options)
+    }
+
     /// Registers a synchronous [`Task`] with this scheduler.
     ///
     /// Returns a [`TaskIdentifier`], which is associated with this
     ///   specific scheduler. This identifier can be used to cancel
     ///   the task at a later time using [`cancel_task`][Scheduler::cancel_task].
+    ///
+    /// Returns [`Error::RuntimeShutDown`] if the scheduler's runtime has shut down.
     pub fn add_sync_task<T: Send + 'static>(
         &mut self,
         task: impl Task<T> + Send + Sync + 'static,
         schedule: impl Schedule<T> + Send + 'static,
-    ) -> TaskIdentifier {
-        let next_id = self.next_identifier;
-        #[cfg(feature = "log")]
-        log::info!(
-            "Registering task [{}] with a TaskIdentifier of [{}].",
-            std::any::type_name_of_val(&task),
-            next_id
-        );
-        self.next_identifier = self.next_identifier.next();
-        match &mut self.flavour {
-            #[cfg(feature = "tokio")]
-            SchedulerFlavour::Tokio(tok) => tok.add_sync_task(task, schedule, next_id),
-        };
-        next_id
+    ) -> Result<TaskIdentifier, Error> {
+        self.core()
+            .register_sync_task(task, schedule, TaskOptions::default())
     }
 
     /// Registers an asynchronous [`AsyncTask`] with this scheduler.
@@ -57,42 +177,295 @@ impl Scheduler {
     /// Returns a [`TaskIdentifier`], which is associated with this
     ///   specific scheduler. This identifier can be used to cancel
     ///   the task at a later time using [`cancel_task`][Scheduler::cancel_task].
-    pub fn add_async_task<T>(
+    ///
+    /// Returns [`Error::RuntimeShutDown`] if the scheduler's runtime has shut down.
+    pub fn add_async_task<T: Send + 'static>(
         &mut self,
         task: impl AsyncTask<T> + Send + Sync + 'static,
         schedule: impl Schedule<T> + Send + 'static,
-    ) -> TaskIdentifier
-    where
-        T: Send + 'static + Sync,
-    {
-        let next_id = self.next_identifier;
-        #[cfg(feature = "log")]
-        log::info!(
-            "Registering task [{}] with a TaskIdentifier of [{}].",
-            std::any::type_name_of_val(&task),
-            next_id
-        );
-        self.next_identifier = self.next_identifier.next();
-        match &mut self.flavour {
-            #[cfg(feature = "tokio")]
-            SchedulerFlavour::Tokio(tok) => tok.add_async_task(task, schedule, next_id),
+    ) -> Result<TaskIdentifier, Error> {
+        self.core()
+            .register_async_task(task, schedule, TaskOptions::default())
+    }
+
+    /// Registers a synchronous [`Task`] which runs after its `dependencies`, rather than on a timer.
+    ///
+    /// The task runs once every dependency has been triggered since its previous run. The
+    ///   `schedule` is still consulted after each run: the returned delay is waited out between
+    ///   the dependencies being satisfied and the next run, and returning `None` stops the task.
+    ///   To pass the output of an upstream task along, see [`Piped`][crate::Piped]. Upstream tasks
+    ///   stop counting once they are cancelled or their schedule finishes, after their last run
+    ///   has triggered the task. Once every upstream task is gone, the task can never run again,
+    ///   and finishes.
+    ///
+    /// Returns [`Error::Dependency`] if `dependencies` is empty, or if any of them are not registered
+    ///   with this scheduler, and [`Error::RuntimeShutDown`] if the scheduler's runtime has shut down.
+    pub fn add_sync_task_after<T: Send + 'static>(
+        &mut self,
+        task: impl Task<T> + Send + Sync + 'static,
+        schedule: impl Schedule<T> + Send + 'static,
+        dependencies: impl IntoIterator<Item = Dependency>,
+    ) -> Result<TaskIdentifier, Error> {
+        let options = TaskOptions {
+            dependencies: Some(dependencies.into_iter().collect()),
+            ..Default::default()
+        };
+        self.core().register_sync_task(task, schedule, options)
+    }
+
+    /// Registers an asynchronous [`AsyncTask`] which runs after its `dependencies`, rather than on a timer.
+    ///
+    /// See [`add_sync_task_after`][Scheduler::add_sync_task_after] for how dependencies and
+    ///   the `schedule` interact.
+    pub fn add_async_task_after<T: Send + 'static>(
+        &mut self,
+        task: impl AsyncTask<T> + Send + Sync + 'static,
+        schedule: impl Schedule<T> + Send + 'static,
+        dependencies: impl IntoIterator<Item = Dependency>,
+    ) -> Result<TaskIdentifier, Error> {
+        let options = TaskOptions {
+            dependencies: Some(dependencies.into_iter().collect()),
+            ..Default::default()
         };
-        next_id
+        self.core().register_async_task(task, schedule, options)
+    }
+
+    /// Adds another dependency to a task registered with [`add_sync_task_after`][Scheduler::add_sync_task_after]
+    ///   or [`add_async_task_after`][Scheduler::add_async_task_after].
+    ///
+    /// Returns [`Error::Dependency`] if either task is unknown, if `task` is not a dependent task,
+    ///   or if the new dependency would form a cycle.
+    pub fn add_dependency(
+        &mut self,
+        task: TaskIdentifier,
+        dependency: Dependency,
+    ) -> Result<(), Error> {
+        self.core().add_dependency(task, dependency)
     }
 
-    #[allow(clippy::result_unit_err)]
     /// Stops a task from continuing to be scheduled. Running tasks may continue to run
     ///   until completion after being cancelled. See the documentation for the specific
     ///   flavour of Scheduler being used.
     ///
     /// Returns an `Ok(())` if the task was successfully marked for cancellation.
-    /// Returns an `Err(())` if the task was not registered with the scheduler.
-    pub fn cancel_task(&mut self, identifier: TaskIdentifier) -> Result<(), ()> {
-        let res = match &mut self.flavour {
+    /// Returns [`Error::UnknownTask`] if the task was not registered with the scheduler, or was
+    ///   already cancelled, and [`Error::AlreadyFinished`] if its schedule had already finished.
+    ///   Either way, the task is no longer registered afterwards.
+    ///
+    /// Tasks whose schedule finished are forgotten by the scheduler on their own, so they need not be
+    ///   cancelled. The scheduler remembers how the latest 1024 of them ended, and reports older ones
+    ///   as [`Error::UnknownTask`].
+    pub fn cancel_task(&mut self, identifier: TaskIdentifier) -> Result<(), Error> {
+        self.core().cancel_task(identifier)
+    }
+
+    /// Stops a task from running until it is [resumed][Scheduler::resume_task]. A run which is
+    ///   already in progress is not interrupted.
+    ///
+    /// The task's schedule keeps its place while paused. If a run fell due while the task was paused,
+    ///   it happens as soon as the task is resumed.
+    ///
+    /// Returns [`Error::UnknownTask`] if the task is not registered, and [`Error::AlreadyFinished`]
+    ///   if its schedule has finished.
+    pub fn pause_task(&mut self, identifier: TaskIdentifier) -> Result<(), Error> {
+        self.core().set_paused(identifier, true)
+    }
+
+    /// Lets a [paused][Scheduler::pause_task] task run again. Resuming a task which is not
+    ///   paused does nothing.
+    ///
+    /// Returns [`Error::UnknownTask`] if the task is not registered, and [`Error::AlreadyFinished`]
+    ///   if its schedule has finished.
+    pub fn resume_task(&mut self, identifier: TaskIdentifier) -> Result<(), Error> {
+        self.core().set_paused(identifier, false)
+    }
+
+    /// Runs a task as soon as possible, ahead of its schedule. Afterwards, the schedule is consulted
+    ///   as if the run happened on schedule.
+    ///
+    /// A dependent task runs without waiting for its dependencies, and a paused task runs once it is resumed.
+    ///   A task which is triggered while running runs again straight after.
+    ///
+    /// Returns [`Error::UnknownTask`] if the task is not registered, and [`Error::AlreadyFinished`]
+    ///   if its schedule has finished.
+    pub fn trigger_task(&mut self, identifier: TaskIdentifier) -> Result<(), Error> {
+        self.core().trigger(identifier)
+    }
+
+    /// Returns whether a task is waiting, running, paused, or has stopped. A task whose schedule
+    ///   panicked is reported as [`TaskStatus::Failed`], see [`Supervision`].
+    ///
+    /// Returns [`Error::UnknownTask`] if the task is not registered, or finished long enough ago to
+    ///   have been forgotten, see [`cancel_task`][Scheduler::cancel_task].
+    pub fn task_status(&self, identifier: TaskIdentifier) -> Result<TaskStatus, Error> {
+        self.core().status(identifier)
+    }
+}
+
+impl SchedulerCore {
+    fn claim_name(&self, name: &Option<String>) -> Result<(), Error> {
+        match name {
+            Some(name) if self.names.contains_key(name) => Err(Error::DuplicateName(name.clone())),
+            _ => Ok(()),
+        }
+    }
+
+    /// The registration path shared by all synchronous tasks.
+    pub(crate) fn register_sync_task<T: Send + 'static, S: Schedule<T> + Send + 'static>(
+        &mut self,
+        task: impl Task<T> + Send + Sync + 'static,
+        schedule: S,
+        mut options: TaskOptions,
+    ) -> Result<TaskIdentifier, Error> {
+        self.prepare(&mut options)?;
+        #[cfg(feature = "log")]
+        let task_name = std::any::type_name_of_val(&task);
+        let identifier = self.identifiers.next();
+        let name = options.name.clone();
+        let group = options.group.as_ref().map(|group| group.id);
+        match (&mut self.flavour, self.state_store.clone(), &name) {
+            #[cfg(feature = "tokio")]
+            (SchedulerFlavour::Tokio(tok), Some((store, catch_up)), Some(name)) => {
+                options.persist::<S>(name, &store, catch_up);
+                let schedule = Persistent::new(name.clone(), schedule, store, catch_up);
+                tok.add_sync_task(task, schedule, options, identifier)?
+            }
+            #[cfg(feature = "tokio")]
+            (SchedulerFlavour::Tokio(tok), _, _) => {
+                tok.add_sync_task(task, schedule, options, identifier)?
+            }
+        };
+        self.registered(identifier, name, group);
+        #[cfg(feature = "log")]
+        log::info!("Registered task [{task_name}] with a TaskIdentifier of [{identifier}].");
+        Ok(identifier)
+    }
+
+    /// The registration path shared by all asynchronous tasks.
+    pub(crate) fn register_async_task<T: Send + 'static, S: Schedule<T> + Send + 'static>(
+        &mut self,
+        task: impl AsyncTask<T> + Send + Sync + 'static,
+        schedule: S,
+        mut options: TaskOptions,
+    ) -> Result<TaskIdentifier, Error> {
+        self.prepare(&mut options)?;
+        #[cfg(feature = "log")]
+        let task_name = std::any::type_name_of_val(&task);
+        let identifier = self.identifiers.next();
+        let name = options.name.clone();
+        let group = options.group.as_ref().map(|group| group.id);
+        match (&mut self.flavour, self.state_store.clone(), &name) {
+            #[cfg(feature = "tokio")]
+            (SchedulerFlavour::Tokio(tok), Some((store, catch_up)), Some(name)) => {
+                options.persist::<S>(name, &store, catch_up);
+                let schedule = Persistent::new(name.clone(), schedule, store, catch_up);
+                tok.add_async_task(task, schedule, options, identifier)?
+            }
+            #[cfg(feature = "tokio")]
+            (SchedulerFlavour::Tokio(tok), _, _) => {
+                tok.add_async_task(task, schedule, options, identifier)?
+            }
+        };
+        self.registered(identifier, name, group);
+        #[cfg(feature = "log")]
+        log::info!("Registered task [{task_name}] with a TaskIdentifier of [{identifier}].");
+        Ok(identifier)
+    }
+
+    /// Checks the `options` of a task which is about to be registered.
+    fn prepare(&self, options: &mut TaskOptions) -> Result<(), Error> {
+        options.validate()?;
+        for dependency in options.dependencies.iter().flatten() {
+            self.identifiers.check(dependency.upstream())?;
+        }
+        self.claim_name(&options.name)?;
+        options.catch_up.get_or_insert(self.pause_catch_up);
+        self.prepare_group(options)
+    }
+
+    /// Book-keeping once a task was successfully registered.
+    fn registered(
+        &mut self,
+        identifier: TaskIdentifier,
+        name: Option<String>,
+        group: Option<GroupId>,
+    ) {
+        self.identifiers.claim(identifier);
+        if let Some(name) = name {
+            self.names.insert(name, identifier);
+        }
+        self.join_group(group, identifier);
+    }
+
+    /// See [Scheduler::add_dependency].
+    fn add_dependency(
+        &mut self,
+        task: TaskIdentifier,
+        dependency: Dependency,
+    ) -> Result<(), Error> {
+        self.identifiers.check(task)?;
+        self.identifiers.check(dependency.upstream())?;
+        let result = match &mut self.flavour {
+            #[cfg(feature = "tokio")]
+            SchedulerFlavour::Tokio(tok) => tok.add_dependency(task, dependency),
+        };
+        Ok(result?)
+    }
+
+    /// See [Scheduler::cancel_task].
+    fn cancel_task(&mut self, identifier: TaskIdentifier) -> Result<(), Error> {
+        self.identifiers.check(identifier)?;
+        self.names.retain(|_, named| *named != identifier);
+        self.leave_group(identifier);
+        let result = match &mut self.flavour {
             #[cfg(feature = "tokio")]
             SchedulerFlavour::Tokio(tok) => tok.cancel_task(identifier),
         };
-        res.ok_or(())
+        self.identifiers.release(identifier);
+        result.map_err(|err| self.identifiers.explain(err))
+    }
+
+    /// See [Scheduler::pause_task] and [Scheduler::resume_task].
+    fn set_paused(&mut self, identifier: TaskIdentifier, paused: bool) -> Result<(), Error> {
+        self.identifiers.check(identifier)?;
+        let result = match &mut self.flavour {
+            #[cfg(feature = "tokio")]
+            SchedulerFlavour::Tokio(tok) => tok.set_paused(identifier, paused),
+        };
+        result.map_err(|err| self.identifiers.explain(err))
+    }
+
+    /// See [Scheduler::trigger_task].
+    fn trigger(&mut self, identifier: TaskIdentifier) -> Result<(), Error> {
+        self.identifiers.check(identifier)?;
+        let result = match &mut self.flavour {
+            #[cfg(feature = "tokio")]
+            SchedulerFlavour::Tokio(tok) => tok.trigger(identifier),
+        };
+        result.map_err(|err| self.identifiers.explain(err))
+    }
+
+    /// See [Scheduler::task_status].
+    fn status(&self, identifier: TaskIdentifier) -> Result<TaskStatus, Error> {
+        self.identifiers.check(identifier)?;
+        let result = match &self.flavour {
+            #[cfg(feature = "tokio")]
+            SchedulerFlavour::Tokio(tok) => tok.status(identifier),
+        };
+        result.map_err(|err| self.identifiers.explain(err))
+    }
+
+    /// Forgets a task whose schedule finished, along with its name and group membership.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn reap(&mut self, identifier: TaskIdentifier) {
+        let reaped = match &mut self.flavour {
+            SchedulerFlavour::Tokio(tok) => tok.reap(identifier),
+        };
+        if reaped {
+            self.identifiers.release(identifier);
+            self.names.retain(|_, named| *named != identifier);
+            self.leave_group(identifier);
+        }
     }
 }
 
@@ -100,8 +473,13 @@ impl Drop for Scheduler {
     fn drop(&mut self) {
         // relevant for when we only own a handle to an external runtime,
         // so we need to cancel the ongoing work in that runtime.
-        while let Some(id) = self.in_use_ids.pop() {
-            let _ = self.cancel_task(id);
+        let mut core = self.core();
+        let tasks = match &core.flavour {
+            #[cfg(feature = "tokio")]
+            SchedulerFlavour::Tokio(tok) => tok.task_identifiers(),
+        };
+        for id in tasks {
+            let _ = core.cancel_task(id);
         }
     }
 }
@@ -113,34 +491,43 @@ trait SchedulerExt {
         &mut self,
         task: impl Task<T> + Send + Sync + 'static,
         schedule: impl Schedule<T> + Send + 'static,
+        options: TaskOptions,
         identifier: TaskIdentifier,
-    );
+    ) -> Result<(), Error>;
 
     /// See [Scheduler::add_async_task].
-    fn add_async_task<T: Send + Sync + 'static>(
+    fn add_async_task<T: Send + 'static>(
         &mut self,
         task: impl AsyncTask<T> + Send + Sync + 'static,
         schedule: impl Schedule<T> + Send + 'static,
+        options: TaskOptions,
         identifier: TaskIdentifier,
-    );
+    ) -> Result<(), Error>;
+
+    /// See [Scheduler::add_dependency].
+    fn add_dependency(
+        &mut self,
+        task: TaskIdentifier,
+        dependency: Dependency,
+    ) -> Result<(), DependencyError>;
 
     /// See [Scheduler::cancel_task].
-    fn cancel_task(&mut self, identifier: TaskIdentifier) -> Option<()>;
-}
+    fn cancel_task(&mut self, identifier: TaskIdentifier) -> Result<(), Error>;
 
-/// Task Identifiers are created by a [Scheduler] when registering a task, and
-///   are used for performing modifications on a running task with a scheduler.
-#[derive(Debug, Hash, Eq, PartialEq, Default, Clone, Copy)]
-pub struct TaskIdentifier(usize);
+    /// See [Scheduler::pause_task] and [Scheduler::resume_task].
+    fn set_paused(&mut self, identifier: TaskIdentifier, paused: bool) -> Result<(), Error>;
 
-impl std::fmt::Display for TaskIdentifier {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("TaskIdentifier({})", self.0))
-    }
-}
+    /// See [Scheduler::trigger_task].
+    fn trigger(&mut self, identifier: TaskIdentifier) -> Result<(), Error>;
 
-impl TaskIdentifier {
-    fn next(&self) -> Self {
-        Self(self.0 + 1)
-    }
+    /// See [Scheduler::task_status].
+    fn status(&self, identifier: TaskIdentifier) -> Result<TaskStatus, Error>;
+
+    /// Starts applying the `commands` sent through [SchedulerHandle]s to `core`.
+    fn serve(
+        &mut self,
+        scheduler_handle: SchedulerHandle,
+        commands: handle::CommandReceiver,
+        core: Weak<Mutex<SchedulerCore>>,
+    );
 }
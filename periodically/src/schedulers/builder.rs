@@ -0,0 +1,346 @@
+use super::{
+    group::GroupMembership, Dependency, ScheduleFactory, Scheduler, Supervision, SyncExecutor,
+    TaskGroup, TaskIdentifier, TaskStatus,
+};
+use crate::{AsyncTask, CatchUp, Error, Persistent, Schedule, StateStore, Task};
+use std::{sync::Arc, time::Duration};
+
+/// Builder-style registration of tasks, for when they need more than a schedule.
+impl Scheduler {
+    /// Starts registering a synchronous [`Task`]. See [`TaskBuilder`].
+    pub fn task<K>(&mut self, task: K) -> TaskBuilder<'_, kind::Sync<K>> {
+        TaskBuilder::new(self, kind::Sync(task))
+    }
+
+    /// Starts registering an asynchronous [`AsyncTask`]. See [`TaskBuilder`].
+    pub fn async_task<K>(&mut self, task: K) -> TaskBuilder<'_, kind::Async<K>> {
+        TaskBuilder::new(self, kind::Async(task))
+    }
+}
+
+/// Registers a task along with per-task options. Created by [`Scheduler::task`] and [`Scheduler::async_task`].
+///
+/// A [`schedule`][TaskBuilder::schedule] must be given before the task can be [registered][TaskBuilder::register].
+///   The other options are validated when registering.
+///
+/// ```
+/// use periodically::{IntervalSchedule, Scheduler, Task};
+/// use std::time::Duration;
+///
+/// struct Report;
+///
+/// impl Task for Report {
+///     fn run(&self) {
+///         // ..
+///         // generate the report
+///         // ..
+///     }
+/// }
+///
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+/// let mut scheduler = Scheduler::tokio_scheduler(runtime);
+/// let id = scheduler
+///     .task(Report)
+///     .schedule(IntervalSchedule::every(Duration::from_secs(60)))
+///     .name("report")
+///     .timeout(Duration::from_secs(30))
+///     .start_paused()
+///     .register()
+///     .unwrap();
+///
+/// // ..
+/// // once the report's inputs are available
+/// // ..
+/// scheduler.resume_task(id).unwrap();
+/// ```
+#[must_use = "the task is only scheduled once registered"]
+pub struct TaskBuilder<'a, K, S = kind::Unscheduled> {
+    scheduler: &'a mut Scheduler,
+    task: K,
+    schedule: S,
+    options: TaskOptions,
+}
+
+impl<'a, K> TaskBuilder<'a, K> {
+    fn new(scheduler: &'a mut Scheduler, task: K) -> Self {
+        Self {
+            scheduler,
+            task,
+            schedule: kind::Unscheduled,
+            options: TaskOptions::default(),
+        }
+    }
+}
+
+impl<'a, K, S> TaskBuilder<'a, K, S> {
+    /// Sets the [`Schedule`] which the task runs on.
+    pub fn schedule<S2>(self, schedule: S2) -> TaskBuilder<'a, K, S2> {
+        TaskBuilder {
+            scheduler: self.scheduler,
+            task: self.task,
+            schedule,
+            options: self.options,
+        }
+    }
+
+    /// Sets the [`Schedule`] which the task runs on to one made by `factory`, which also makes a fresh
+    ///   schedule whenever the task is [restarted][Supervision::Restart].
+    pub fn schedule_factory<S2: 'static>(
+        mut self,
+        factory: impl Fn() -> S2 + Send + Sync + 'static,
+    ) -> TaskBuilder<'a, K, S2> {
+        let schedule = factory();
+        self.options.schedule_factory = Some(ScheduleFactory::new(factory));
+        self.schedule(schedule)
+    }
+
+    /// Registers the task under a `name`, which must be unique among the tasks registered with the scheduler.
+    ///   See [`add_named_sync_task`][Scheduler::add_named_sync_task].
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.options.name = Some(name.into());
+        self
+    }
+
+    /// Gives up on runs which take longer than `timeout`.
+    ///
+    /// A run which times out is treated like a panicking one: the schedule is consulted through
+    ///   [`next_on_task_panic`][Schedule::next_on_task_panic], and subscribers receive
+    ///   [`TaskFailure::TimedOut`][crate::TaskFailure::TimedOut]. Asynchronous runs are cancelled,
+    ///   whereas synchronous runs cannot be interrupted, and carry on in the background.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    /// Delays every run by a random amount of up to `jitter`, on top of the delay given by the schedule.
+    ///
+    /// Spreads out tasks which share a schedule, eg; so that the instances of a service do not all
+    ///   hit the same database on the hour.
+    pub fn jitter(mut self, jitter: Duration) -> Self {
+        self.options.jitter = Some(jitter);
+        self
+    }
+
+    /// Registers the task as [paused][Scheduler::pause_task], so that it only runs once
+    ///   [resumed][Scheduler::resume_task].
+    pub fn start_paused(mut self) -> Self {
+        self.options.start_paused = true;
+        self
+    }
+
+    /// Registers the task into a [`TaskGroup`], which then manages it along with the other tasks in the group.
+    ///
+    /// The task starts paused if the group is paused. Registration fails with [`Error::InvalidOptions`]
+    ///   if the group was created by another scheduler.
+    pub fn group(mut self, group: &TaskGroup) -> Self {
+        self.options.group = Some(group.membership());
+        self
+    }
+
+    /// Decides what happens to runs which fall due while the task is [paused][Scheduler::pause_task].
+    ///   Defaults to the scheduler's [`with_pause_catch_up`][Scheduler::with_pause_catch_up].
+    pub fn catch_up(mut self, catch_up: CatchUp) -> Self {
+        self.options.catch_up = Some(catch_up);
+        self
+    }
+
+    /// Decides what happens if the task's schedule panics. Defaults to [`Supervision::MarkFailed`].
+    ///
+    /// [`Supervision::Restart`] needs the schedule to be given through
+    ///   [`schedule_factory`][TaskBuilder::schedule_factory], and registration fails with
+    ///   [`Error::InvalidOptions`] otherwise.
+    pub fn supervision(mut self, supervision: Supervision) -> Self {
+        self.options.supervision = supervision;
+        self
+    }
+
+    /// Calls `on_finish` once the task's schedule finishes, with either [`TaskStatus::Finished`], or
+    ///   [`TaskStatus::Failed`] if the schedule [failed][Supervision]. Not called if the task is cancelled.
+    ///
+    /// The scheduler forgets finished tasks straight afterwards, freeing up their name.
+    pub fn on_finish(
+        mut self,
+        on_finish: impl FnOnce(TaskIdentifier, TaskStatus) + Send + 'static,
+    ) -> Self {
+        self.options.on_finish = Some(Box::new(on_finish));
+        self
+    }
+
+    /// Runs the task after its `dependencies`, rather than on a timer.
+    ///   See [`add_sync_task_after`][Scheduler::add_sync_task_after].
+    pub fn after(mut self, dependencies: impl IntoIterator<Item = Dependency>) -> Self {
+        self.options.dependencies = Some(dependencies.into_iter().collect());
+        self
+    }
+}
+
+impl<K, S> TaskBuilder<'_, kind::Sync<K>, S> {
+    /// Sets where the task runs, eg; on a thread of its own. Defaults to tokio's blocking pool.
+    ///   See [`SyncExecutor`].
+    pub fn executor(mut self, executor: SyncExecutor) -> Self {
+        self.options.executor = executor;
+        self
+    }
+
+    /// Decides whether a run may start while a run which [timed out][TaskBuilder::timeout] carries on
+    ///   in the background. Defaults to [`Overlap::Allow`].
+    ///
+    /// Registration fails with [`Error::InvalidOptions`] if the task waits on overlapping runs, but has
+    ///   no timeout.
+    pub fn overlap(mut self, overlap: Overlap) -> Self {
+        self.options.overlap = overlap;
+        self
+    }
+
+    /// Validates the options, and registers the task with the scheduler.
+    ///
+    /// Returns [`Error::InvalidOptions`] if an option is invalid, and otherwise fails like
+    ///   the `add_*` methods of the [`Scheduler`].
+    pub fn register<T: Send + 'static>(self) -> Result<TaskIdentifier, Error>
+    where
+        K: Task<T> + Send + Sync + 'static,
+        S: Schedule<T> + Send + 'static,
+    {
+        self.scheduler
+            .core()
+            .register_sync_task(self.task.0, self.schedule, self.options)
+    }
+}
+
+impl<K, S> TaskBuilder<'_, kind::Async<K>, S> {
+    /// Validates the options, and registers the task with the scheduler.
+    ///
+    /// Returns [`Error::InvalidOptions`] if an option is invalid, and otherwise fails like
+    ///   the `add_*` methods of the [`Scheduler`].
+    pub fn register<T: Send + 'static>(self) -> Result<TaskIdentifier, Error>
+    where
+        K: AsyncTask<T> + Send + Sync + 'static,
+        S: Schedule<T> + Send + 'static,
+    {
+        self.scheduler
+            .core()
+            .register_async_task(self.task.0, self.schedule, self.options)
+    }
+}
+
+/// Markers for the type parameters of a [`TaskBuilder`].
+mod kind {
+    /// A synchronous task.
+    pub struct Sync<K>(pub(super) K);
+
+    /// An asynchronous task.
+    pub struct Async<K>(pub(super) K);
+
+    /// No schedule was given yet.
+    pub struct Unscheduled;
+}
+
+/// Whether the runs of a synchronous task may overlap. Set through [`TaskBuilder::overlap`].
+///
+/// The runs of a task happen one after the other, except that a sync run which [timed out][TaskBuilder::timeout]
+///   cannot be interrupted, and carries on in the background. So only sync tasks with a timeout can overlap.
+///
+/// ```
+/// use periodically::{IntervalSchedule, Overlap, Scheduler, Task};
+/// use std::time::Duration;
+///
+/// struct Export;
+///
+/// impl Task for Export {
+///     fn run(&self) {
+///         // ..
+///         // export the day's orders, which must not be written twice at once
+///         // ..
+///     }
+/// }
+///
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+/// let mut scheduler = Scheduler::tokio_scheduler(runtime);
+/// scheduler
+///     .task(Export)
+///     .schedule(IntervalSchedule::every(Duration::from_secs(60)))
+///     .timeout(Duration::from_secs(30))
+///     .overlap(Overlap::Wait)
+///     .register()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Overlap {
+    /// The next run starts on schedule, alongside a run which timed out.
+    #[default]
+    Allow,
+    /// The next run waits for a run which timed out to end before starting.
+    Wait,
+}
+
+/// Per-task options, which every registration path goes through.
+#[derive(Default)]
+pub(crate) struct TaskOptions {
+    pub(crate) name: Option<String>,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) jitter: Option<Duration>,
+    /// Only used by sync tasks.
+    pub(crate) overlap: Overlap,
+    pub(crate) start_paused: bool,
+    /// Only present for dependent tasks.
+    pub(crate) dependencies: Option<Vec<Dependency>>,
+    /// Only used by sync tasks.
+    pub(crate) executor: SyncExecutor,
+    pub(crate) group: Option<GroupMembership>,
+    /// Filled in with the scheduler's default when registering.
+    pub(crate) catch_up: Option<CatchUp>,
+    pub(crate) supervision: Supervision,
+    /// Only present if the schedule was given through [TaskBuilder::schedule_factory].
+    pub(crate) schedule_factory: Option<ScheduleFactory>,
+    pub(crate) on_finish: Option<OnFinish>,
+}
+
+/// See [TaskBuilder::on_finish].
+pub(crate) type OnFinish = Box<dyn FnOnce(TaskIdentifier, TaskStatus) + Send>;
+
+impl TaskOptions {
+    /// Makes the schedule factory, if any, wrap its schedules in a [`Persistent`] like the task's schedule.
+    pub(crate) fn persist<S: 'static>(
+        &mut self,
+        name: &str,
+        store: &Arc<dyn StateStore>,
+        catch_up: CatchUp,
+    ) {
+        let (name, store) = (name.to_string(), store.clone());
+        self.schedule_factory = self.schedule_factory.take().map(|factory| {
+            factory.map(move |schedule: S| {
+                Persistent::new(name.clone(), schedule, store.clone(), catch_up)
+            })
+        });
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        if self.name.as_ref().is_some_and(|name| name.is_empty()) {
+            return Err(Error::InvalidOptions("name must not be empty".to_string()));
+        }
+        if self.timeout.is_some_and(|timeout| timeout.is_zero()) {
+            return Err(Error::InvalidOptions(
+                "timeout must be greater than zero".to_string(),
+            ));
+        }
+        if self.jitter.is_some_and(|jitter| jitter.is_zero()) {
+            return Err(Error::InvalidOptions(
+                "jitter must be greater than zero".to_string(),
+            ));
+        }
+        if self.overlap == Overlap::Wait && self.timeout.is_none() {
+            return Err(Error::InvalidOptions(
+                "waiting on overlapping runs requires a timeout".to_string(),
+            ));
+        }
+        if matches!(self.supervision, Supervision::Restart { .. })
+            && self.schedule_factory.is_none()
+        {
+            return Err(Error::InvalidOptions(
+                "restarting requires a schedule_factory".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,263 @@
+use super::TaskIdentifier;
+use std::collections::{HashMap, HashSet};
+
+/// Decides which outcomes of an upstream task cause a dependent task to run.
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+pub enum Trigger {
+    /// Triggers when the upstream task runs without panicking.
+    OnSuccess,
+    /// Triggers when the upstream task panics.
+    OnFailure,
+    /// Triggers after every run of the upstream task, regardless of how it ended.
+    OnCompletion,
+}
+
+impl Trigger {
+    fn matches(&self, succeeded: bool) -> bool {
+        match self {
+            Trigger::OnSuccess => succeeded,
+            Trigger::OnFailure => !succeeded,
+            Trigger::OnCompletion => true,
+        }
+    }
+}
+
+/// A "run after" relationship between a dependent task and one of its upstream tasks.
+///
+/// A dependent task runs once every one of its dependencies has been triggered
+///   since its previous run. eg; a task depending on both `a` and `b` waits for
+///   a triggering run of `a` *and* of `b`.
+///
+/// ```
+/// use periodically::{Dependency, Scheduler, Trigger};
+/// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// # let mut scheduler = Scheduler::tokio_scheduler(runtime);
+/// # struct MyTask;
+/// # impl periodically::Task for MyTask { fn run(&self) {} }
+/// # let upstream = scheduler.add_sync_task(MyTask, periodically::OneShot::after(std::time::Duration::ZERO)).unwrap();
+/// let dependency = Dependency::on_success(upstream);
+/// assert_eq!(dependency.upstream(), upstream);
+/// assert_eq!(dependency.trigger(), Trigger::OnSuccess);
+/// ```
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+pub struct Dependency {
+    upstream: TaskIdentifier,
+    trigger: Trigger,
+}
+
+impl Dependency {
+    /// Creates a [`Dependency`] on `upstream` which fires according to `trigger`.
+    pub fn new(upstream: TaskIdentifier, trigger: Trigger) -> Self {
+        Self { upstream, trigger }
+    }
+
+    /// Creates a [`Dependency`] that fires after every successful run of `upstream`.
+    pub fn on_success(upstream: TaskIdentifier) -> Self {
+        Self::new(upstream, Trigger::OnSuccess)
+    }
+
+    /// Creates a [`Dependency`] that fires after every failed run of `upstream`.
+    pub fn on_failure(upstream: TaskIdentifier) -> Self {
+        Self::new(upstream, Trigger::OnFailure)
+    }
+
+    /// Creates a [`Dependency`] that fires after every run of `upstream`.
+    pub fn on_completion(upstream: TaskIdentifier) -> Self {
+        Self::new(upstream, Trigger::OnCompletion)
+    }
+
+    /// The task which must run before the dependent task.
+    pub fn upstream(&self) -> TaskIdentifier {
+        self.upstream
+    }
+
+    /// The outcomes of the upstream task which satisfy this dependency.
+    pub fn trigger(&self) -> Trigger {
+        self.trigger
+    }
+}
+
+/// Reasons why a [`Dependency`] could not be registered.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum DependencyError {
+    /// The referenced task is not registered with the scheduler.
+    UnknownTask(TaskIdentifier),
+    /// The task is scheduled on a timer, so it cannot have dependencies added to it.
+    NotDependent(TaskIdentifier),
+    /// Adding the dependency would create a cycle. Contains the tasks forming the cycle,
+    ///   starting and ending with the dependent task.
+    Cycle(Vec<TaskIdentifier>),
+    /// The dependent task was registered without any dependencies, so it would never run.
+    NoDependencies(TaskIdentifier),
+}
+
+impl std::fmt::Display for DependencyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DependencyError::UnknownTask(id) => write!(f, "[{id}] is not a registered task"),
+            DependencyError::NotDependent(id) => {
+                write!(
+                    f,
+                    "[{id}] is not a dependent task, and cannot have dependencies"
+                )
+            }
+            DependencyError::Cycle(cycle) => {
+                let cycle: Vec<String> = cycle.iter().map(ToString::to_string).collect();
+                write!(f, "dependency cycle detected: {}", cycle.join(" -> "))
+            }
+            DependencyError::NoDependencies(id) => {
+                write!(f, "[{id}] has no dependencies, so it would never run")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DependencyError {}
+
+/// Tracks the registered tasks of a scheduler and the dependencies between them.
+#[derive(Default)]
+pub(crate) struct DependencyGraph {
+    /// Every registered task. Dependent tasks map to their upstream dependencies,
+    ///   timer-driven tasks map to `None`.
+    nodes: HashMap<TaskIdentifier, Option<Vec<Dependency>>>,
+    /// The dependent tasks of each upstream task, so that finding them does not scale with
+    ///   the number of registered tasks.
+    downstreams: HashMap<TaskIdentifier, HashSet<TaskIdentifier>>,
+}
+
+impl DependencyGraph {
+    /// Registers a task which is driven by a timer rather than by dependencies.
+    pub(crate) fn insert_task(&mut self, task: TaskIdentifier) {
+        self.nodes.insert(task, None);
+    }
+
+    /// Validates and registers a dependent task.
+    pub(crate) fn insert_dependent(
+        &mut self,
+        task: TaskIdentifier,
+        dependencies: Vec<Dependency>,
+    ) -> Result<(), DependencyError> {
+        if dependencies.is_empty() {
+            return Err(DependencyError::NoDependencies(task));
+        }
+        for dependency in &dependencies {
+            if dependency.upstream == task {
+                return Err(DependencyError::Cycle(vec![task, task]));
+            }
+            self.check_known(dependency.upstream)?;
+        }
+        for dependency in &dependencies {
+            self.link(dependency.upstream, task);
+        }
+        self.nodes.insert(task, Some(dependencies));
+        Ok(())
+    }
+
+    /// Adds a dependency to an already registered dependent task.
+    pub(crate) fn add_dependency(
+        &mut self,
+        task: TaskIdentifier,
+        dependency: Dependency,
+    ) -> Result<(), DependencyError> {
+        self.check_known(task)?;
+        self.check_known(dependency.upstream)?;
+        if let Some(mut cycle) = self.path(dependency.upstream, task) {
+            cycle.insert(0, task);
+            return Err(DependencyError::Cycle(cycle));
+        }
+        match self.nodes.get_mut(&task) {
+            Some(Some(dependencies)) => {
+                dependencies.push(dependency);
+                self.link(dependency.upstream, task);
+                Ok(())
+            }
+            _ => Err(DependencyError::NotDependent(task)),
+        }
+    }
+
+    /// Unregisters a task, along with any dependencies on it. Returns the dependent tasks which
+    ///   were left without any upstream task, and so will never run again.
+    pub(crate) fn remove(&mut self, task: TaskIdentifier) -> Vec<TaskIdentifier> {
+        if let Some(Some(dependencies)) = self.nodes.remove(&task) {
+            for dependency in dependencies {
+                if let Some(downstreams) = self.downstreams.get_mut(&dependency.upstream) {
+                    downstreams.remove(&task);
+                }
+            }
+        }
+        let mut orphans = Vec::new();
+        for downstream in self.downstreams.remove(&task).unwrap_or_default() {
+            if let Some(Some(dependencies)) = self.nodes.get_mut(&downstream) {
+                dependencies.retain(|dependency| dependency.upstream != task);
+                if dependencies.is_empty() {
+                    orphans.push(downstream);
+                }
+            }
+        }
+        orphans
+    }
+
+    /// The dependent tasks triggered by a run of `upstream` with the given outcome.
+    pub(crate) fn triggered_by(
+        &self,
+        upstream: TaskIdentifier,
+        succeeded: bool,
+    ) -> Vec<TaskIdentifier> {
+        let Some(downstreams) = self.downstreams.get(&upstream) else {
+            return Vec::new();
+        };
+        downstreams
+            .iter()
+            .filter(|task| {
+                self.upstream_dependencies(**task)
+                    .any(|dep| dep.upstream == upstream && dep.trigger.matches(succeeded))
+            })
+            .copied()
+            .collect()
+    }
+
+    /// The tasks which must all be triggered before `task` can run.
+    pub(crate) fn upstreams_of(&self, task: TaskIdentifier) -> HashSet<TaskIdentifier> {
+        match self.nodes.get(&task) {
+            Some(Some(dependencies)) => dependencies.iter().map(|dep| dep.upstream).collect(),
+            _ => HashSet::new(),
+        }
+    }
+
+    fn upstream_dependencies(&self, task: TaskIdentifier) -> impl Iterator<Item = &Dependency> {
+        self.nodes.get(&task).into_iter().flatten().flatten()
+    }
+
+    fn link(&mut self, upstream: TaskIdentifier, task: TaskIdentifier) {
+        self.downstreams.entry(upstream).or_default().insert(task);
+    }
+
+    fn check_known(&self, task: TaskIdentifier) -> Result<(), DependencyError> {
+        if self.nodes.contains_key(&task) {
+            Ok(())
+        } else {
+            Err(DependencyError::UnknownTask(task))
+        }
+    }
+
+    /// Finds a chain of dependencies leading from `from` up to `to`, if any.
+    fn path(&self, from: TaskIdentifier, to: TaskIdentifier) -> Option<Vec<TaskIdentifier>> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![vec![from]];
+        while let Some(path) = stack.pop() {
+            let current = *path.last()?;
+            if current == to {
+                return Some(path);
+            }
+            if !visited.insert(current) {
+                continue;
+            }
+            for upstream in self.upstreams_of(current) {
+                let mut next = path.clone();
+                next.push(upstream);
+                stack.push(next);
+            }
+        }
+        None
+    }
+}
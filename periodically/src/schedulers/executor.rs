@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+/// Where the runs of a synchronous [`Task`][crate::Task] happen. Set through [`TaskBuilder::executor`][super::TaskBuilder::executor].
+///
+/// By default, sync tasks run on tokio's blocking pool, which is shared with other blocking work
+///   such as file I/O and DNS lookups. Tasks which run for a long time can starve that work, so
+///   they are better off on a thread of their own, or on a pool set aside for them.
+///
+/// ```
+/// use periodically::{IntervalSchedule, Scheduler, SyncExecutor, Task};
+/// use std::time::Duration;
+///
+/// struct Reindex;
+///
+/// impl Task for Reindex {
+///     fn run(&self) {
+///         // ..
+///         // rebuild the search index, which takes minutes
+///         // ..
+///         assert_eq!(std::thread::current().name(), Some("periodically-reindex"));
+///     }
+/// }
+///
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+/// let mut scheduler = Scheduler::tokio_scheduler(runtime);
+/// scheduler
+///     .task(Reindex)
+///     .schedule(IntervalSchedule::every(Duration::from_secs(60 * 60)))
+///     .name("reindex")
+///     .executor(SyncExecutor::DedicatedThread)
+///     .register()
+///     .unwrap();
+/// ```
+#[derive(Clone, Default)]
+#[non_exhaustive]
+pub enum SyncExecutor {
+    /// Tokio's [blocking pool][tokio::task::spawn_blocking].
+    #[default]
+    BlockingPool,
+    /// An OS thread which only runs this task, and stops once the task is cancelled or its schedule finishes.
+    ///
+    /// The thread is named `periodically-<name>` after the task's [name][super::TaskBuilder::name],
+    ///   or `periodically-task-<identifier>` for unnamed tasks.
+    DedicatedThread,
+    /// A [`ThreadPool`], which can be shared between tasks by cloning the executor. Naming its
+    ///   threads is up to the pool.
+    Pool(Arc<dyn ThreadPool>),
+}
+
+impl SyncExecutor {
+    /// Runs the task on `pool`. See [`SyncExecutor::Pool`].
+    pub fn pool(pool: impl ThreadPool + 'static) -> Self {
+        SyncExecutor::Pool(Arc::new(pool))
+    }
+}
+
+/// A pool of threads which runs synchronous tasks, see [`SyncExecutor::Pool`].
+///
+/// Under the `rayon` feature, this is implemented for [`rayon::ThreadPool`].
+pub trait ThreadPool: Send + Sync {
+    /// Runs `job` on one of the pool's threads.
+    ///
+    /// Jobs catch their own panics, so a panicking task does not take down the thread.
+    fn spawn(&self, job: Box<dyn FnOnce() + Send>);
+}
+
+impl<P: ThreadPool + ?Sized> ThreadPool for Arc<P> {
+    fn spawn(&self, job: Box<dyn FnOnce() + Send>) {
+        (**self).spawn(job)
+    }
+}
+
+cfg_feature! {
+    "rayon",
+    impl ThreadPool for rayon::ThreadPool {
+        fn spawn(&self, job: Box<dyn FnOnce() + Send>) {
+            rayon::ThreadPool::spawn(self, job)
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use super::{Scheduler, SchedulerCore, TaskIdentifier, TaskOptions};
+use crate::Error;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex, Weak,
+};
+use tokio::sync::Semaphore;
+
+/// Grouping of related tasks, which are then managed as a unit.
+impl Scheduler {
+    /// Creates an empty [`TaskGroup`]. Tasks join it when registered through
+    ///   [`TaskBuilder::group`][crate::TaskBuilder::group].
+    pub fn task_group(&mut self) -> TaskGroup {
+        let core = Arc::downgrade(&self.core);
+        self.core().add_group(core, None)
+    }
+
+    /// Creates an empty [`TaskGroup`], whose tasks run at most `limit` at a time.
+    ///
+    /// Runs which are due while the limit is reached wait for another run of the group to finish.
+    ///   A run which [timed out][crate::TaskBuilder::timeout] stops counting towards the limit,
+    ///   even if it carries on in the background.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is zero.
+    pub fn task_group_with_concurrency_limit(&mut self, limit: usize) -> TaskGroup {
+        assert!(limit > 0, "limit must be greater than zero");
+        let core = Arc::downgrade(&self.core);
+        self.core().add_group(core, Some(limit))
+    }
+}
+
+/// A family of tasks, eg; all the jobs of a tenant, which are cancelled, paused, resumed or inspected as a unit.
+///   Created by [`Scheduler::task_group`].
+///
+/// The group is a guard: dropping it cancels every task in it.
+///
+/// ```
+/// use periodically::{IntervalSchedule, Scheduler, Task};
+/// use std::time::Duration;
+///
+/// struct SyncMailbox(&'static str);
+///
+/// impl Task for SyncMailbox {
+///     fn run(&self) {
+///         // ..
+///         // sync the tenant's mailbox
+///         // ..
+///     }
+/// }
+///
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+/// let mut scheduler = Scheduler::tokio_scheduler(runtime);
+/// let tenant = scheduler.task_group_with_concurrency_limit(1);
+/// for mailbox in ["sales", "support"] {
+///     scheduler
+///         .task(SyncMailbox(mailbox))
+///         .schedule(IntervalSchedule::every(Duration::from_secs(60)))
+///         .group(&tenant)
+///         .register()
+///         .unwrap();
+/// }
+/// assert_eq!(tenant.tasks().unwrap().len(), 2);
+///
+/// // ..
+/// // the tenant is suspended
+/// // ..
+/// tenant.pause().unwrap();
+///
+/// // ..
+/// // the tenant is deleted
+/// // ..
+/// drop(tenant);
+/// ```
+pub struct TaskGroup {
+    membership: GroupMembership,
+    core: Weak<Mutex<SchedulerCore>>,
+}
+
+/// Identifies a [TaskGroup]. Unique across schedulers, so that groups cannot be mixed up between them.
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+pub(crate) struct GroupId(usize);
+
+impl GroupId {
+    fn next() -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// What a task needs to know about the group it is registered into.
+#[derive(Clone)]
+pub(crate) struct GroupMembership {
+    pub(crate) id: GroupId,
+    /// Shared by the runs of every task in the group.
+    pub(crate) limit: Option<Arc<Semaphore>>,
+}
+
+/// The scheduler's side of a [TaskGroup].
+#[derive(Default)]
+pub(crate) struct Group {
+    /// In the order they were registered.
+    tasks: Vec<TaskIdentifier>,
+    paused: bool,
+}
+
+impl TaskGroup {
+    /// Returns the tasks in the group, in the order they were registered. Tasks leave the group once cancelled, or once
+    ///   their schedule finishes.
+    ///
+    /// Returns [`Error::SchedulerDropped`] if the scheduler has been dropped.
+    pub fn tasks(&self) -> Result<Vec<TaskIdentifier>, Error> {
+        self.with_group(|_, group| group.tasks.clone())
+    }
+
+    /// Whether the group is [paused][TaskGroup::pause].
+    ///
+    /// Returns [`Error::SchedulerDropped`] if the scheduler has been dropped.
+    pub fn is_paused(&self) -> Result<bool, Error> {
+        self.with_group(|_, group| group.paused)
+    }
+
+    /// [Pauses][Scheduler::pause_task] every task in the group. Tasks which join the group while it
+    ///   is paused start paused.
+    ///
+    /// Returns [`Error::SchedulerDropped`] if the scheduler has been dropped.
+    pub fn pause(&self) -> Result<(), Error> {
+        self.set_paused(true)
+    }
+
+    /// [Resumes][Scheduler::resume_task] every task in the group, including the ones which were
+    ///   paused individually.
+    ///
+    /// Returns [`Error::SchedulerDropped`] if the scheduler has been dropped.
+    pub fn resume(&self) -> Result<(), Error> {
+        self.set_paused(false)
+    }
+
+    /// [Cancels][Scheduler::cancel_task] every task in the group. The group stays usable, and
+    ///   can be joined by new tasks afterwards.
+    ///
+    /// Returns [`Error::SchedulerDropped`] if the scheduler has been dropped.
+    pub fn cancel(&self) -> Result<(), Error> {
+        self.with_group(|core, group| {
+            for task in std::mem::take(&mut group.tasks) {
+                core.group_of.remove(&task);
+                // finished tasks are only forgotten
+                let _ = core.cancel_task(task);
+            }
+        })
+    }
+
+    pub(crate) fn membership(&self) -> GroupMembership {
+        self.membership.clone()
+    }
+
+    fn set_paused(&self, paused: bool) -> Result<(), Error> {
+        self.with_group(|core, group| {
+            group.paused = paused;
+            for task in &group.tasks {
+                // finished tasks are left alone
+                let _ = core.set_paused(*task, paused);
+            }
+        })
+    }
+
+    /// Runs `f` on the group, while it is taken out of the scheduler.
+    fn with_group<R>(
+        &self,
+        f: impl FnOnce(&mut SchedulerCore, &mut Group) -> R,
+    ) -> Result<R, Error> {
+        let core = self.core.upgrade().ok_or(Error::SchedulerDropped)?;
+        let mut core = core.lock().unwrap();
+        let id = self.membership.id;
+        let mut group = core.groups.remove(&id).unwrap_or_default();
+        let result = f(&mut core, &mut group);
+        core.groups.insert(id, group);
+        Ok(result)
+    }
+}
+
+impl Drop for TaskGroup {
+    fn drop(&mut self) {
+        let _ = self.cancel();
+        if let Some(core) = self.core.upgrade() {
+            core.lock().unwrap().groups.remove(&self.membership.id);
+        }
+    }
+}
+
+impl SchedulerCore {
+    fn add_group(&mut self, core: Weak<Mutex<SchedulerCore>>, limit: Option<usize>) -> TaskGroup {
+        let id = GroupId::next();
+        self.groups.insert(id, Group::default());
+        TaskGroup {
+            membership: GroupMembership {
+                id,
+                limit: limit.map(|limit| Arc::new(Semaphore::new(limit))),
+            },
+            core,
+        }
+    }
+
+    /// Checks that a task can join the group in its `options`, and starts it paused if the group is.
+    pub(crate) fn prepare_group(&self, options: &mut TaskOptions) -> Result<(), Error> {
+        let Some(membership) = &options.group else {
+            return Ok(());
+        };
+        let group = self.groups.get(&membership.id).ok_or_else(|| {
+            Error::InvalidOptions("the group belongs to another scheduler".to_string())
+        })?;
+        options.start_paused |= group.paused;
+        Ok(())
+    }
+
+    /// Adds a freshly registered task to its group.
+    pub(crate) fn join_group(&mut self, group: Option<GroupId>, identifier: TaskIdentifier) {
+        let Some(id) = group else {
+            return;
+        };
+        if let Some(group) = self.groups.get_mut(&id) {
+            group.tasks.push(identifier);
+            self.group_of.insert(identifier, id);
+        }
+    }
+
+    /// Whether the task belongs to a [paused][TaskGroup::pause] group.
+    pub(crate) fn in_paused_group(&self, identifier: TaskIdentifier) -> bool {
+        self.group_of
+            .get(&identifier)
+            .and_then(|group| self.groups.get(group))
+            .is_some_and(|group| group.paused)
+    }
+
+    /// Removes a cancelled task from its group.
+    pub(crate) fn leave_group(&mut self, identifier: TaskIdentifier) {
+        let Some(group) = self.group_of.remove(&identifier) else {
+            return;
+        };
+        if let Some(group) = self.groups.get_mut(&group) {
+            group.tasks.retain(|task| *task != identifier);
+        }
+    }
+}
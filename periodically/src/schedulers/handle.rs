@@ -0,0 +1,196 @@
+use super::{Scheduler, SchedulerCore, TaskIdentifier, TaskOptions};
+use crate::{AsyncTask, Error, Schedule, Task};
+use std::{
+    cell::RefCell,
+    future::Future,
+    sync::{Mutex, Weak},
+};
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+    oneshot,
+};
+
+/// A change to a [Scheduler], sent through a [SchedulerHandle].
+pub(crate) type Command = Box<dyn FnOnce(&mut SchedulerCore) + Send>;
+
+pub(crate) type CommandReceiver = UnboundedReceiver<Command>;
+
+impl Scheduler {
+    /// Returns a [`SchedulerHandle`], which manages the tasks of this scheduler from other threads and tasks.
+    pub fn handle(&self) -> SchedulerHandle {
+        self.handle.clone()
+    }
+}
+
+/// A cheap, cloneable handle to a [`Scheduler`], which can be shared between threads and tasks.
+///
+/// Commands are sent to the scheduler through a channel, and applied on its runtime. So unlike the
+///   [`Scheduler`] itself, a handle does not need to be wrapped in a mutex to be shared, eg; with the
+///   handlers of a web server. Once the scheduler is dropped, every command fails with
+///   [`Error::SchedulerDropped`].
+///
+/// Tasks can get a handle to the scheduler running them through [`SchedulerHandle::current`],
+///   eg; to schedule follow-up work.
+///
+/// ```
+/// use periodically::{AsyncTask, OneShot, Scheduler, SchedulerHandle};
+/// use std::time::Duration;
+///
+/// struct SendReminder;
+///
+/// impl AsyncTask for SendReminder {
+///     async fn run(&self) {
+///         // ..
+///         // send the reminder
+///         // ..
+///     }
+/// }
+///
+/// struct SignUp;
+///
+/// impl AsyncTask for SignUp {
+///     async fn run(&self) {
+///         // ..
+///         // sign the user up
+///         // ..
+///         let scheduler = SchedulerHandle::current().unwrap();
+///         let reminder = OneShot::after(Duration::from_secs(24 * 60 * 60));
+///         scheduler.add_async_task(SendReminder, reminder).await.unwrap();
+///     }
+/// }
+///
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+/// let mut scheduler = Scheduler::tokio_scheduler(runtime);
+/// scheduler.add_async_task(SignUp, OneShot::after(Duration::ZERO)).unwrap();
+/// ```
+#[derive(Clone)]
+pub struct SchedulerHandle {
+    commands: UnboundedSender<Command>,
+}
+
+impl SchedulerHandle {
+    pub(crate) fn channel() -> (Self, CommandReceiver) {
+        let (commands, receiver) = unbounded_channel();
+        (Self { commands }, receiver)
+    }
+
+    /// Returns a handle to the scheduler running the current task, or `None` when called from
+    ///   outside of a task.
+    ///
+    /// Synchronous tasks run outside of an async context, but can still wait on the handle's
+    ///   commands through [`Handle::block_on`][tokio::runtime::Handle::block_on].
+    pub fn current() -> Option<SchedulerHandle> {
+        CURRENT_TASK
+            .try_with(Clone::clone)
+            .ok()
+            .or_else(|| CURRENT_THREAD.with_borrow(Clone::clone))
+    }
+
+    /// Registers a synchronous [`Task`]. See [`Scheduler::add_sync_task`].
+    pub async fn add_sync_task<T: Send + 'static>(
+        &self,
+        task: impl Task<T> + Send + Sync + 'static,
+        schedule: impl Schedule<T> + Send + 'static,
+    ) -> Result<TaskIdentifier, Error> {
+        self.send(|core| core.register_sync_task(task, schedule, TaskOptions::default()))
+            .await?
+    }
+
+    /// Registers an asynchronous [`AsyncTask`]. See [`Scheduler::add_async_task`].
+    pub async fn add_async_task<T: Send + 'static>(
+        &self,
+        task: impl AsyncTask<T> + Send + Sync + 'static,
+        schedule: impl Schedule<T> + Send + 'static,
+    ) -> Result<TaskIdentifier, Error> {
+        self.send(|core| core.register_async_task(task, schedule, TaskOptions::default()))
+            .await?
+    }
+
+    /// Cancels a task. See [`Scheduler::cancel_task`].
+    pub async fn cancel(&self, identifier: TaskIdentifier) -> Result<(), Error> {
+        self.send(move |core| core.cancel_task(identifier)).await?
+    }
+
+    /// Pauses a task. See [`Scheduler::pause_task`].
+    pub async fn pause(&self, identifier: TaskIdentifier) -> Result<(), Error> {
+        self.send(move |core| core.set_paused(identifier, true))
+            .await?
+    }
+
+    /// Resumes a paused task. See [`Scheduler::resume_task`].
+    pub async fn resume(&self, identifier: TaskIdentifier) -> Result<(), Error> {
+        self.send(move |core| core.set_paused(identifier, false))
+            .await?
+    }
+
+    /// Runs a task ahead of its schedule. See [`Scheduler::trigger_task`].
+    pub async fn trigger(&self, identifier: TaskIdentifier) -> Result<(), Error> {
+        self.send(move |core| core.trigger(identifier)).await?
+    }
+
+    /// Sends `command` to the scheduler, without waiting for it to be applied.
+    pub(crate) fn notify(&self, command: impl FnOnce(&mut SchedulerCore) + Send + 'static) {
+        // the scheduler being dropped leaves nothing to notify
+        let _ = self.commands.send(Box::new(command));
+    }
+
+    /// Sends `command` to the scheduler, and waits for it to be applied.
+    async fn send<R: Send + 'static>(
+        &self,
+        command: impl FnOnce(&mut SchedulerCore) -> R + Send + 'static,
+    ) -> Result<R, Error> {
+        let (reply, replied) = oneshot::channel();
+        let command: Command = Box::new(move |core| {
+            let _ = reply.send(command(core));
+        });
+        self.commands
+            .send(command)
+            .map_err(|_| Error::SchedulerDropped)?;
+        replied.await.map_err(|_| Error::SchedulerDropped)
+    }
+}
+
+/// Applies the commands sent through [SchedulerHandle]s, for as long as the scheduler is alive.
+pub(crate) async fn serve_commands(
+    core: Weak<Mutex<SchedulerCore>>,
+    mut commands: CommandReceiver,
+) {
+    while let Some(command) = commands.recv().await {
+        let Some(core) = core.upgrade() else {
+            return;
+        };
+        command(&mut core.lock().unwrap());
+    }
+}
+
+tokio::task_local! {
+    /// The handle of async tasks, see [SchedulerHandle::current].
+    static CURRENT_TASK: SchedulerHandle;
+}
+
+thread_local! {
+    /// The handle of sync tasks, see [SchedulerHandle::current].
+    static CURRENT_THREAD: RefCell<Option<SchedulerHandle>> = const { RefCell::new(None) };
+}
+
+/// Runs `run` with `handle` as the [current][SchedulerHandle::current] handle of the thread.
+pub(crate) fn enter<R>(handle: Option<SchedulerHandle>, run: impl FnOnce() -> R) -> R {
+    struct Restore(Option<SchedulerHandle>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT_THREAD.set(self.0.take());
+        }
+    }
+
+    let _restore = Restore(CURRENT_THREAD.replace(handle));
+    run()
+}
+
+/// Runs `future` with `handle` as the [current][SchedulerHandle::current] handle of the task.
+pub(crate) async fn scope<F: Future>(handle: Option<SchedulerHandle>, future: F) -> F::Output {
+    match handle {
+        Some(handle) => CURRENT_TASK.scope(handle, future).await,
+        None => future.await,
+    }
+}
@@ -0,0 +1,229 @@
+use crate::Error;
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    hash::BuildHasher,
+    str::FromStr,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::SystemTime,
+};
+
+/// Task Identifiers are created by a [Scheduler][super::Scheduler] when registering a task, and
+///   are used for performing modifications on a running task with that scheduler.
+///
+/// An identifier names the scheduler it was created by, so using it with another scheduler fails
+///   with [`Error::ForeignTask`] rather than touching an unrelated task. Once a task is cancelled
+///   or finishes, its slot may be handed to a newer task, and the old identifier then fails with
+///   [`Error::StaleTask`].
+///
+/// Identifiers are written as the scheduler's number and random nonce, followed by the slot and the
+///   generation of the slot, eg; `"1-8c6e0a2ff4b1d093-42-0"`, so they can be put in URLs. The nonce
+///   tells apart schedulers with the same number, eg; in another process, or after a restart. They can be parsed back with [`str::parse`],
+///   and with the `serde` feature, they are serialized as that string.
+///
+/// ```
+/// use periodically::{IntervalSchedule, Scheduler, Task, TaskIdentifier};
+/// use std::time::Duration;
+///
+/// struct Report;
+///
+/// impl Task for Report {
+///     fn run(&self) {
+///         // ..
+///         // send the report
+///         // ..
+///     }
+/// }
+///
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+/// let mut scheduler = Scheduler::tokio_scheduler(runtime);
+/// let id = scheduler
+///     .add_sync_task(Report, IntervalSchedule::every(Duration::from_secs(60)))
+///     .unwrap();
+///
+/// let url = format!("/admin/tasks/{id}/cancel");
+/// let parsed: TaskIdentifier = url.split('/').nth(3).unwrap().parse().unwrap();
+/// assert_eq!(parsed, id);
+/// scheduler.cancel_task(parsed).unwrap();
+/// ```
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+pub struct TaskIdentifier {
+    scheduler: SchedulerId,
+    slot: usize,
+    generation: u32,
+}
+
+impl fmt::Display for TaskIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let SchedulerId { number, nonce } = self.scheduler;
+        write!(f, "{number}-{nonce:016x}-{}-{}", self.slot, self.generation)
+    }
+}
+
+impl FromStr for TaskIdentifier {
+    type Err = ParseTaskIdentifierError;
+
+    fn from_str(identifier: &str) -> Result<Self, Self::Err> {
+        let error = || ParseTaskIdentifierError {
+            identifier: identifier.to_owned(),
+        };
+        let mut parts = identifier.split('-');
+        let (Some(number), Some(nonce), Some(slot), Some(generation), None) = (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        ) else {
+            return Err(error());
+        };
+        Ok(Self {
+            scheduler: SchedulerId {
+                number: number.parse().map_err(|_| error())?,
+                nonce: u64::from_str_radix(nonce, 16).map_err(|_| error())?,
+            },
+            slot: slot.parse().map_err(|_| error())?,
+            generation: generation.parse().map_err(|_| error())?,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for TaskIdentifier {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TaskIdentifier {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let identifier = String::deserialize(deserializer)?;
+        identifier.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Returned when parsing a string which is not a [`TaskIdentifier`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTaskIdentifierError {
+    identifier: String,
+}
+
+impl fmt::Display for ParseTaskIdentifierError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid task identifier [{}], expected a scheduler, its nonce, a slot and a generation, eg; 1-8c6e0a2ff4b1d093-42-0",
+            self.identifier
+        )
+    }
+}
+
+impl std::error::Error for ParseTaskIdentifierError {}
+
+/// Identifies a [Scheduler][super::Scheduler]. The number is unique within the process, like
+///   [GroupId][super::GroupId], and the nonce sets it apart from schedulers in other processes.
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+struct SchedulerId {
+    number: usize,
+    nonce: u64,
+}
+
+impl SchedulerId {
+    fn next() -> Self {
+        // starts at 1, so that identifiers made up with zeros never match a scheduler
+        static NEXT: AtomicUsize = AtomicUsize::new(1);
+        let number = NEXT.fetch_add(1, Ordering::Relaxed);
+        // randomly keyed, so that processes started at the same time still differ
+        let nonce = RandomState::new().hash_one((number, std::process::id(), SystemTime::now()));
+        Self { number, nonce }
+    }
+}
+
+/// Hands out the [TaskIdentifier]s of a scheduler, reusing the slots of tasks which are no longer
+///   registered under a new generation.
+pub(crate) struct Identifiers {
+    scheduler: SchedulerId,
+    slots: Vec<Slot>,
+    /// Slots which are no longer taken, the most recently freed last.
+    free: Vec<usize>,
+}
+
+struct Slot {
+    generation: u32,
+    taken: bool,
+}
+
+impl Identifiers {
+    pub(crate) fn new() -> Self {
+        Self {
+            scheduler: SchedulerId::next(),
+            slots: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    /// The identifier the next registered task gets, once [claimed][Identifiers::claim].
+    pub(crate) fn next(&self) -> TaskIdentifier {
+        let (slot, generation) = match self.free.last() {
+            Some(&slot) => (slot, self.slots[slot].generation.wrapping_add(1)),
+            None => (self.slots.len(), 0),
+        };
+        TaskIdentifier {
+            scheduler: self.scheduler,
+            slot,
+            generation,
+        }
+    }
+
+    /// Takes the slot of `identifier`, which was returned by [Identifiers::next].
+    pub(crate) fn claim(&mut self, identifier: TaskIdentifier) {
+        let slot = Slot {
+            generation: identifier.generation,
+            taken: true,
+        };
+        if self.free.last() == Some(&identifier.slot) {
+            self.free.pop();
+            self.slots[identifier.slot] = slot;
+        } else {
+            self.slots.push(slot);
+        }
+    }
+
+    /// Frees the slot of a task which is no longer registered, so that a newer task can reuse it.
+    pub(crate) fn release(&mut self, identifier: TaskIdentifier) {
+        if identifier.scheduler != self.scheduler {
+            return;
+        }
+        if let Some(slot) = self.slots.get_mut(identifier.slot) {
+            if slot.taken && slot.generation == identifier.generation {
+                slot.taken = false;
+                self.free.push(identifier.slot);
+            }
+        }
+    }
+
+    /// Returns [`Error::ForeignTask`] if `identifier` was created by another scheduler.
+    pub(crate) fn check(&self, identifier: TaskIdentifier) -> Result<(), Error> {
+        match identifier.scheduler == self.scheduler {
+            true => Ok(()),
+            false => Err(Error::ForeignTask(identifier)),
+        }
+    }
+
+    /// Turns [`Error::UnknownTask`] into [`Error::StaleTask`] if the slot of the task was reused.
+    pub(crate) fn explain(&self, err: Error) -> Error {
+        match err {
+            Error::UnknownTask(identifier) if self.is_stale(identifier) => {
+                Error::StaleTask(identifier)
+            }
+            err => err,
+        }
+    }
+
+    fn is_stale(&self, identifier: TaskIdentifier) -> bool {
+        self.slots
+            .get(identifier.slot)
+            .is_some_and(|slot| slot.generation != identifier.generation)
+    }
+}
@@ -0,0 +1,87 @@
+use super::{Scheduler, SchedulerCore, SchedulerFlavour, TaskIdentifier, TaskOptions};
+use crate::{Error, LocalAsyncTask, Persistent, Schedule};
+use tokio::task::LocalSet;
+
+/// Scheduling of tasks which are not [`Send`].
+impl Scheduler {
+    /// Registers a [`LocalAsyncTask`], which runs on `local_set` rather than on the scheduler's runtime.
+    ///   Neither the task, its schedule, the futures it returns, nor its outputs need to be [`Send`], so
+    ///   tasks can hold on to eg; an [`Rc`][std::rc::Rc] or a connection which is bound to a single thread.
+    ///   In turn, the outputs of local tasks can not be [subscribed to][Scheduler::subscribe].
+    ///
+    /// The task only makes progress while `local_set` is being driven, eg; through
+    ///   [`LocalSet::block_on`] or [`LocalSet::run_until`], on a runtime with timers enabled.
+    ///   Otherwise, it is scheduled and cancelled just like an [`AsyncTask`][crate::AsyncTask]:
+    ///   each run is spawned separately, so a panicking run is reported to the schedule through
+    ///   [`next_on_task_panic`][Schedule::next_on_task_panic] and the task keeps its place.
+    ///
+    /// Returns [`Error::RuntimeShutDown`] if `local_set` was already shut down.
+    ///
+    /// ```
+    /// use periodically::{IntervalSchedule, LocalAsyncTask, Scheduler};
+    /// use std::{cell::Cell, rc::Rc, time::Duration};
+    /// use tokio::task::LocalSet;
+    ///
+    /// struct Count(Rc<Cell<u32>>);
+    ///
+    /// impl LocalAsyncTask for Count {
+    ///     async fn run(&self) {
+    ///         self.0.set(self.0.get() + 1);
+    ///     }
+    /// }
+    ///
+    /// let runtime = tokio::runtime::Builder::new_current_thread()
+    ///     .enable_time()
+    ///     .build()
+    ///     .unwrap();
+    /// let mut scheduler = Scheduler::tokio_scheduler_with_handle(runtime.handle().clone());
+    /// let local_set = LocalSet::new();
+    /// let count = Rc::new(Cell::new(0));
+    /// scheduler
+    ///     .add_local_task(&local_set, Count(count.clone()), IntervalSchedule::every(Duration::from_millis(10)))
+    ///     .unwrap();
+    ///
+    /// local_set.block_on(&runtime, async { tokio::time::sleep(Duration::from_millis(100)).await });
+    /// assert!(count.get() > 0);
+    /// ```
+    pub fn add_local_task<T: 'static>(
+        &mut self,
+        local_set: &LocalSet,
+        task: impl LocalAsyncTask<T> + 'static,
+        schedule: impl Schedule<T> + 'static,
+    ) -> Result<TaskIdentifier, Error> {
+        self.core()
+            .register_local_task(local_set, task, schedule, TaskOptions::default())
+    }
+}
+
+impl SchedulerCore {
+    /// The registration path of [LocalAsyncTask]s.
+    fn register_local_task<T: 'static>(
+        &mut self,
+        local_set: &LocalSet,
+        task: impl LocalAsyncTask<T> + 'static,
+        schedule: impl Schedule<T> + 'static,
+        mut options: TaskOptions,
+    ) -> Result<TaskIdentifier, Error> {
+        self.prepare(&mut options)?;
+        #[cfg(feature = "log")]
+        let task_name = std::any::type_name_of_val(&task);
+        let identifier = self.identifiers.next();
+        let name = options.name.clone();
+        let group = options.group.as_ref().map(|group| group.id);
+        match (&mut self.flavour, self.state_store.clone(), &name) {
+            (SchedulerFlavour::Tokio(tok), Some((store, catch_up)), Some(name)) => {
+                let schedule = Persistent::new(name.clone(), schedule, store, catch_up);
+                tok.add_local_task(local_set, task, schedule, options, identifier)?
+            }
+            (SchedulerFlavour::Tokio(tok), _, _) => {
+                tok.add_local_task(local_set, task, schedule, options, identifier)?
+            }
+        };
+        self.registered(identifier, name, group);
+        #[cfg(feature = "log")]
+        log::info!("Registered local task [{task_name}] with a TaskIdentifier of [{identifier}].");
+        Ok(identifier)
+    }
+}
@@ -0,0 +1,170 @@
+use super::{Scheduler, SchedulerCore, SchedulerFlavour, TaskIdentifier};
+use crate::CatchUp;
+use std::{
+    collections::HashMap,
+    future::{Future, IntoFuture},
+    pin::Pin,
+};
+use tokio::{runtime::Handle, sync::watch};
+
+/// Maintenance mode, eg; for stopping every task while a database is migrated.
+impl Scheduler {
+    /// Decides what happens to runs which fall due while a task is paused, unless the task was
+    ///   registered with its own [`catch_up`][crate::TaskBuilder::catch_up] policy.
+    ///
+    /// Defaults to [`CatchUp::RunOnce`], where a task which fell due while paused runs once as
+    ///   soon as it is resumed. [`CatchUp::Skip`] restarts the task's schedule from its
+    ///   [`initial`][crate::Schedule::initial] delay instead, and [`CatchUp::RunAll`] runs the task
+    ///   back to back until it has caught up, like [`Persistent`][crate::Persistent] does.
+    ///
+    /// Only applies to tasks registered afterwards.
+    pub fn with_pause_catch_up(self, catch_up: CatchUp) -> Self {
+        self.core().pause_catch_up = catch_up;
+        self
+    }
+
+    /// [Pauses][Scheduler::pause_task] every registered task.
+    ///
+    /// Runs which are already in progress are not interrupted. The returned [`InFlightRuns`] waits
+    ///   for them to finish, after which no task runs until it is resumed.
+    ///
+    /// ```
+    /// use periodically::{IntervalSchedule, Scheduler, Task};
+    /// use std::time::Duration;
+    ///
+    /// struct Compact;
+    ///
+    /// impl Task for Compact {
+    ///     fn run(&self) {
+    ///         // ..
+    ///         // compact the database tables
+    ///         // ..
+    ///     }
+    /// }
+    ///
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let mut scheduler = Scheduler::tokio_scheduler(runtime);
+    /// scheduler
+    ///     .add_sync_task(Compact, IntervalSchedule::every(Duration::from_secs(60)))
+    ///     .unwrap();
+    ///
+    /// scheduler.pause_all().wait();
+    /// // ..
+    /// // migrate the database
+    /// // ..
+    /// scheduler.resume_all();
+    /// ```
+    pub fn pause_all(&mut self) -> InFlightRuns {
+        self.pause_matching(|_, _| true)
+    }
+
+    /// Like [`pause_all`][Scheduler::pause_all], but only pauses the tasks for which `filter`
+    ///   returns `true`, given their identifier and name.
+    pub fn pause_matching(
+        &mut self,
+        filter: impl FnMut(TaskIdentifier, Option<&str>) -> bool,
+    ) -> InFlightRuns {
+        let mut core = self.core();
+        let tasks = core.matching(filter);
+        let mut in_flight = Vec::new();
+        for task in tasks {
+            // finished tasks are left alone
+            if core.set_paused(task, true).is_err() {
+                continue;
+            }
+            match &core.flavour {
+                SchedulerFlavour::Tokio(tok) => in_flight.extend(tok.in_flight(task)),
+            }
+        }
+        let runtime_handle = match &core.flavour {
+            SchedulerFlavour::Tokio(tok) => tok.runtime_handle(),
+        };
+        InFlightRuns {
+            in_flight,
+            runtime_handle,
+        }
+    }
+
+    /// [Resumes][Scheduler::resume_task] every registered task, including the ones which were paused
+    ///   individually. Runs which fell due in the meantime are caught up on according to each task's
+    ///   [catch up policy][Scheduler::with_pause_catch_up].
+    ///
+    /// Tasks in a [paused group][crate::TaskGroup::pause] stay paused until the group is resumed.
+    pub fn resume_all(&mut self) {
+        self.resume_matching(|_, _| true)
+    }
+
+    /// Like [`resume_all`][Scheduler::resume_all], but only resumes the tasks for which `filter`
+    ///   returns `true`, given their identifier and name.
+    pub fn resume_matching(&mut self, filter: impl FnMut(TaskIdentifier, Option<&str>) -> bool) {
+        let mut core = self.core();
+        for task in core.matching(filter) {
+            if core.in_paused_group(task) {
+                continue;
+            }
+            // finished tasks are left alone
+            let _ = core.set_paused(task, false);
+        }
+    }
+}
+
+impl SchedulerCore {
+    /// The registered tasks for which `filter` returns `true`.
+    fn matching(
+        &self,
+        mut filter: impl FnMut(TaskIdentifier, Option<&str>) -> bool,
+    ) -> Vec<TaskIdentifier> {
+        let names: HashMap<_, _> = self
+            .names
+            .iter()
+            .map(|(name, task)| (*task, name.as_str()))
+            .collect();
+        let tasks = match &self.flavour {
+            SchedulerFlavour::Tokio(tok) => tok.task_identifiers(),
+        };
+        tasks
+            .into_iter()
+            .filter(|task| filter(*task, names.get(task).copied()))
+            .collect()
+    }
+}
+
+/// The runs which were in progress when tasks were paused through [`Scheduler::pause_all`].
+///
+/// Can be `.await`ed, or blocked on with [`wait`][InFlightRuns::wait], until those runs have finished.
+pub struct InFlightRuns {
+    in_flight: Vec<watch::Receiver<bool>>,
+    runtime_handle: Handle,
+}
+
+impl InFlightRuns {
+    /// Blocks the current thread until the runs have finished.
+    ///
+    /// Must not be called from an async context, since it would block the executor. `.await` the
+    ///   [`InFlightRuns`] instead.
+    pub fn wait(self) {
+        let runtime_handle = self.runtime_handle.clone();
+        runtime_handle.block_on(self.into_future());
+    }
+
+    /// Whether the runs have finished.
+    pub fn is_finished(&self) -> bool {
+        self.in_flight
+            .iter()
+            .all(|in_flight| !*in_flight.borrow() || in_flight.has_changed().is_err())
+    }
+}
+
+impl IntoFuture for InFlightRuns {
+    type Output = ();
+    type IntoFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            for mut in_flight in self.in_flight {
+                // the task going away means that its run ended too
+                let _ = in_flight.wait_for(|in_flight| !in_flight).await;
+            }
+        })
+    }
+}
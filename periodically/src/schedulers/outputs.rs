@@ -0,0 +1,200 @@
+use super::{supervision, Scheduler, SchedulerFlavour, TaskIdentifier};
+use crate::Error;
+use std::{
+    any::Any,
+    fmt,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+use tokio::sync::broadcast;
+
+/// How many outputs a subscriber can fall behind by before it starts missing outputs.
+const OUTPUT_CAPACITY: usize = 64;
+
+/// Subscriptions to the outputs of registered tasks.
+///
+/// Outputs are published independently of the task's [`Schedule`][crate::Schedule], so tasks can
+///   be observed without writing a custom schedule. Only outputs produced after subscribing are
+///   received, and subscriptions end once the task's schedule finishes or the task is cancelled.
+impl Scheduler {
+    /// Subscribes to the outputs of a task, as a [`broadcast`] receiver.
+    ///
+    /// Each run of the task publishes its output, or a [`TaskFailure`] if the run did not complete.
+    ///   A receiver which falls more than 64 outputs behind misses the oldest ones, and gets
+    ///   [`Lagged`][broadcast::error::RecvError::Lagged] instead.
+    ///
+    /// Returns [`Error::UnknownTask`] if the task is not registered, [`Error::AlreadyFinished`] if its
+    ///   schedule has finished, [`Error::OutputType`] if the task does not output a `T`, and
+    ///   [`Error::LocalOutputs`] if it is a [local task][Scheduler::add_local_task].
+    ///
+    /// ```
+    /// use periodically::{IntervalSchedule, Scheduler, Task};
+    /// use std::time::Duration;
+    ///
+    /// struct HealthCheck;
+    ///
+    /// impl Task<bool> for HealthCheck {
+    ///     fn run(&self) -> bool {
+    ///         // ..
+    ///         // poll the service
+    ///         // ..
+    ///         true
+    ///     }
+    /// }
+    ///
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let mut scheduler = Scheduler::tokio_scheduler(runtime);
+    /// let id = scheduler
+    ///     .add_sync_task(HealthCheck, IntervalSchedule::every(Duration::from_millis(10)))
+    ///     .unwrap();
+    ///
+    /// // eg; feed a status page
+    /// let mut statuses = scheduler.subscribe::<bool>(id).unwrap();
+    /// assert_eq!(statuses.blocking_recv().unwrap(), Ok(true));
+    /// ```
+    pub fn subscribe<T: Clone + Send + 'static>(
+        &self,
+        identifier: TaskIdentifier,
+    ) -> Result<broadcast::Receiver<Result<T, TaskFailure>>, Error> {
+        let core = self.core();
+        core.identifiers.check(identifier)?;
+        let outputs = match &core.flavour {
+            SchedulerFlavour::Tokio(tok) => tok.outputs_of(identifier),
+        }
+        .map_err(|err| core.identifiers.explain(err))?;
+        let outputs = outputs
+            .downcast::<TaskOutputs<T>>()
+            .map_err(|_| Error::OutputType(identifier))?;
+        Ok(outputs.subscribe())
+    }
+
+    /// Subscribes to the outputs of a task, as a [`Stream`][tokio_stream::Stream].
+    ///
+    /// Identical to [`subscribe`][Scheduler::subscribe], except that outputs which a slow
+    ///   consumer missed are skipped over. The stream ends once the task's schedule finishes or
+    ///   the task is cancelled.
+    #[cfg(feature = "stream")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+    pub fn output_stream<T: Clone + Send + 'static>(
+        &self,
+        identifier: TaskIdentifier,
+    ) -> Result<OutputStream<T>, Error> {
+        Ok(OutputStream(tokio_stream::wrappers::BroadcastStream::new(
+            self.subscribe(identifier)?,
+        )))
+    }
+}
+
+/// Why a run of a task did not produce an output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TaskFailure {
+    /// The task panicked. Holds the panic message, if it was a string.
+    Panicked(Option<String>),
+    /// The run was cancelled before it finished, eg; because the runtime shut down.
+    Cancelled,
+    /// The run took longer than the task's [`timeout`][crate::TaskBuilder::timeout].
+    TimedOut(Duration),
+}
+
+impl fmt::Display for TaskFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskFailure::Panicked(Some(message)) => write!(f, "task panicked: {message}"),
+            TaskFailure::Panicked(None) => write!(f, "task panicked"),
+            TaskFailure::Cancelled => write!(f, "task was cancelled"),
+            TaskFailure::TimedOut(timeout) => write!(f, "task timed out after [{timeout:?}]"),
+        }
+    }
+}
+
+impl std::error::Error for TaskFailure {}
+
+impl TaskFailure {
+    pub(crate) fn from_panic(payload: Box<dyn Any + Send>) -> Self {
+        TaskFailure::Panicked(supervision::panic_message(payload))
+    }
+}
+
+/// Publishes the outputs of a single task to its subscribers.
+///
+/// The channel is only created by the first subscription, since that is the first time `T` is
+///   known to be [`Clone`].
+pub(crate) struct TaskOutputs<T> {
+    publisher: OnceLock<Publisher<T>>,
+}
+
+type OutputSender<T> = broadcast::Sender<Result<T, TaskFailure>>;
+
+struct Publisher<T> {
+    sender: OutputSender<T>,
+    /// Clones outputs into the channel. Captures the [`Clone`] bound that `T` only has when subscribing.
+    publish: fn(&OutputSender<T>, Result<&T, &TaskFailure>),
+}
+
+impl<T> TaskOutputs<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            publisher: OnceLock::new(),
+        }
+    }
+
+    /// Sends `output` to any subscribers.
+    pub(crate) fn publish(&self, output: Result<&T, &TaskFailure>) {
+        if let Some(publisher) = self.publisher.get() {
+            (publisher.publish)(&publisher.sender, output);
+        }
+    }
+}
+
+impl<T: Clone> TaskOutputs<T> {
+    fn subscribe(&self) -> broadcast::Receiver<Result<T, TaskFailure>> {
+        self.publisher
+            .get_or_init(|| Publisher {
+                sender: broadcast::channel(OUTPUT_CAPACITY).0,
+                publish: |sender, output| {
+                    // no receivers is fine, they may have all been dropped
+                    let _ = sender.send(output.cloned().map_err(Clone::clone));
+                },
+            })
+            .sender
+            .subscribe()
+    }
+}
+
+cfg_feature! {
+    "stream",
+    /// A [`Stream`][tokio_stream::Stream] of the outputs of a task. See [`Scheduler::output_stream`].
+    pub struct OutputStream<T>(tokio_stream::wrappers::BroadcastStream<Result<T, TaskFailure>>);
+}
+
+#[cfg(feature = "stream")]
+impl<T: Clone + Send + 'static> tokio_stream::Stream for OutputStream<T> {
+    type Item = Result<T, TaskFailure>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        use std::task::Poll;
+        loop {
+            match std::pin::Pin::new(&mut self.0).poll_next(cx) {
+                Poll::Ready(Some(Err(_lagged))) => {
+                    #[cfg(feature = "log")]
+                    log::warn!("Output stream fell behind: {_lagged}");
+                }
+                Poll::Ready(Some(Ok(output))) => return Poll::Ready(Some(output)),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// A type-erased [`TaskOutputs`], held by the scheduler for as long as the task's nanny is alive.
+pub(crate) type ErasedOutputs = std::sync::Weak<dyn Any + Send + Sync>;
+
+pub(crate) fn erase<T: Send + 'static>(outputs: &Arc<TaskOutputs<T>>) -> ErasedOutputs {
+    let outputs: Arc<dyn Any + Send + Sync> = outputs.clone();
+    Arc::downgrade(&outputs)
+}
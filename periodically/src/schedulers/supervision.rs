@@ -0,0 +1,153 @@
+use super::TaskIdentifier;
+use std::{
+    any::Any,
+    fmt,
+    sync::{Arc, OnceLock},
+};
+
+/// What happens to a task whose [`Schedule`][crate::Schedule] panics. Set through
+///   [`TaskBuilder::supervision`][super::TaskBuilder::supervision].
+///
+/// The panic is caught either way, rather than taking down whatever drives the task. The task's
+///   [status][super::Scheduler::task_status] then reports [`TaskStatus::Failed`], unless it was restarted.
+///
+/// ```
+/// use periodically::{IntervalSchedule, Scheduler, Supervision, Task};
+/// use std::time::Duration;
+///
+/// struct Inventory;
+///
+/// impl Task for Inventory {
+///     fn run(&self) {
+///         // ..
+///         // sync the inventory
+///         // ..
+///     }
+/// }
+///
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+/// let mut scheduler = Scheduler::tokio_scheduler(runtime);
+/// scheduler
+///     .task(Inventory)
+///     .schedule_factory(|| IntervalSchedule::every(Duration::from_secs(60)))
+///     .supervision(Supervision::Restart { max_restarts: 3 })
+///     .register()
+///     .unwrap();
+/// ```
+#[derive(Clone, Default)]
+#[non_exhaustive]
+pub enum Supervision {
+    /// Stops scheduling the task, and marks it as failed.
+    #[default]
+    MarkFailed,
+    /// Replaces the schedule with a fresh one from the task's
+    ///   [`schedule_factory`][super::TaskBuilder::schedule_factory], which starts over from its
+    ///   [`initial`][crate::Schedule::initial] delay.
+    ///
+    /// The task is marked as failed once the schedule panics `max_restarts` times in a row, where
+    ///   a run whose schedule was consulted successfully ends the streak.
+    Restart {
+        /// How many times in a row the schedule may be restarted.
+        max_restarts: usize,
+    },
+    /// Marks the task as failed, like [`Supervision::MarkFailed`], and hands the failure to a
+    ///   callback, eg; to page someone or to shut the service down.
+    Escalate(Escalation),
+}
+
+/// The callback of [`Supervision::Escalate`], given the failed task and why its schedule failed.
+pub type Escalation = Arc<dyn Fn(TaskIdentifier, &ScheduleFailure) + Send + Sync>;
+
+impl Supervision {
+    /// Escalates failures to `callback`. See [`Supervision::Escalate`].
+    pub fn escalate(
+        callback: impl Fn(TaskIdentifier, &ScheduleFailure) + Send + Sync + 'static,
+    ) -> Self {
+        Supervision::Escalate(Arc::new(callback))
+    }
+}
+
+/// Why a task's [`Schedule`][crate::Schedule] stopped being consulted. See [`Supervision`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleFailure {
+    message: Option<String>,
+}
+
+impl ScheduleFailure {
+    pub(crate) fn from_panic(payload: Box<dyn Any + Send>) -> Self {
+        Self {
+            message: panic_message(payload),
+        }
+    }
+
+    /// The message the schedule panicked with, if it was a string.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+impl fmt::Display for ScheduleFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "schedule panicked: {message}"),
+            None => write!(f, "schedule panicked"),
+        }
+    }
+}
+
+impl std::error::Error for ScheduleFailure {}
+
+/// The state of a registered task. See [`Scheduler::task_status`][super::Scheduler::task_status].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TaskStatus {
+    /// Waiting until it is due, or until its dependencies are satisfied.
+    Scheduled,
+    /// A run is in progress.
+    Running,
+    /// [Paused][super::Scheduler::pause_task], and not running.
+    Paused,
+    /// The schedule finished, by returning `None`.
+    Finished,
+    /// The schedule panicked, and the task was not restarted. See [`Supervision`].
+    Failed(ScheduleFailure),
+}
+
+/// The panic message in `payload`, if it was a string.
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> Option<String> {
+    match payload.downcast::<String>() {
+        Ok(message) => Some(*message),
+        Err(payload) => payload.downcast_ref::<&str>().map(|s| s.to_string()),
+    }
+}
+
+/// Makes fresh schedules for a task which is [restarted][Supervision::Restart]. The schedule's type
+///   is erased, since it is only known to the [`TaskBuilder`][super::TaskBuilder] and the scheduler.
+pub(crate) struct ScheduleFactory(Box<dyn Any + Send + Sync>);
+
+/// A [ScheduleFactory] whose schedule type is known again.
+pub(crate) type Factory<S> = Arc<dyn Fn() -> S + Send + Sync>;
+
+impl ScheduleFactory {
+    pub(crate) fn new<S: 'static>(factory: impl Fn() -> S + Send + Sync + 'static) -> Self {
+        Self(Box::new(Arc::new(factory) as Factory<S>))
+    }
+
+    /// Wraps the schedules made by the factory, eg; in a [`Persistent`][crate::Persistent].
+    pub(crate) fn map<S: 'static, S2: 'static>(
+        self,
+        wrap: impl Fn(S) -> S2 + Send + Sync + 'static,
+    ) -> Self {
+        match self.downcast::<S>() {
+            Some(factory) => Self::new(move || wrap(factory())),
+            None => unreachable!("the factory makes the task's schedules"),
+        }
+    }
+
+    pub(crate) fn downcast<S: 'static>(self) -> Option<Factory<S>> {
+        self.0.downcast::<Factory<S>>().ok().map(|factory| *factory)
+    }
+}
+
+/// Where a task's driver records why its schedule failed, for [`Scheduler::task_status`][super::Scheduler::task_status].
+pub(crate) type FailureSlot = Arc<OnceLock<ScheduleFailure>>;
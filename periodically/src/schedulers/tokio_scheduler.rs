@@ -1,12 +1,51 @@
-use super::{Scheduler, SchedulerExt, SchedulerFlavour, TaskIdentifier};
-use crate::{AsyncTask, Schedule, Task};
-use std::{collections::HashMap, future::Future, sync::Arc, time::Duration};
-use tokio::task::{spawn_blocking, JoinError, JoinHandle};
+use super::{
+    builder::OnFinish,
+    handle::{self, CommandReceiver, SchedulerHandle},
+    outputs::{self, ErasedOutputs, TaskFailure, TaskOutputs},
+    supervision::FailureSlot,
+    Dependency, DependencyError, DependencyGraph, Overlap, Scheduler, SchedulerCore, SchedulerExt,
+    SchedulerFlavour, TaskIdentifier, TaskOptions,
+};
+use crate::{
+    schedule::with_panic_message, AsyncTask, CatchUp, Error, LocalAsyncTask, Schedule, Supervision,
+    Task, TaskStatus,
+};
+use jitter::Jittered;
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet, VecDeque},
+    future::{poll_fn, Future},
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::{pin, Pin},
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, Weak,
+    },
+    task::{Context, Poll, Waker},
+    time::{Duration, Instant, SystemTime},
+};
+use supervisor::Supervised;
+use sync_executor::SyncRunner;
+use timer_driver::{TimedTask, TimerDriver, TimerQueue};
+use tokio::{
+    sync::{
+        mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+        watch, Notify, OwnedSemaphorePermit, Semaphore,
+    },
+    task::{spawn_local, JoinHandle, LocalSet},
+};
+
+mod jitter;
+mod supervisor;
+mod sync_executor;
+mod timer_driver;
 
 /// Constructors for [Tokio][tokio]-based schedulers.
 ///
-/// Tokio-based schedulers spawns a future to schedule each registered task. [AsyncTasks][AsyncTask] run on
-/// the runtime's executor, and [Tasks][Task] run on the blocking pool.
+/// Tokio-based schedulers spawns a future to schedule each registered task, unless they are
+/// [timer driven][Scheduler::with_timer_driver]. [AsyncTasks][AsyncTask] run on the runtime's
+/// executor, and [Tasks][Task] run on the blocking pool.
 impl Scheduler {
     /// Creates a tokio-based scheduler with an owned runtime. The provided runtime will be used to
     ///   schedule and run all tasks. This is best used when working with periodic tasks that require
@@ -16,7 +55,9 @@ impl Scheduler {
     /// This Scheduler will own the given runtime, and perform a best effort shutdown of it
     ///   when dropped.
     pub fn tokio_scheduler(runtime: tokio::runtime::Runtime) -> Self {
-        Scheduler::from_flavour(TokioScheduler::new_from_runtime(runtime))
+        let mut scheduler = Scheduler::from_flavour(TokioScheduler::new(runtime.handle().clone()));
+        scheduler.owned_runtime = Some(runtime);
+        scheduler
     }
 
     /// Creates a tokio-based scheduler using a runtime handle. The associated runtime will be
@@ -26,7 +67,7 @@ impl Scheduler {
     /// This Scheduler will perform a best effort stop of all running tasks on the given
     ///   runtime when dropped.
     pub fn tokio_scheduler_with_handle(handle: tokio::runtime::Handle) -> Self {
-        Scheduler::from_flavour(TokioScheduler::new_from_handle(handle))
+        Scheduler::from_flavour(TokioScheduler::new(handle))
     }
 
     /// Creates a tokio-based scheduler using the current runtime context. The associated runtime will be
@@ -37,13 +78,174 @@ impl Scheduler {
     ///   runtime when dropped.
     pub fn tokio_scheduler_with_current() -> Self {
         let handle = tokio::runtime::Handle::current();
-        Scheduler::from_flavour(TokioScheduler::new_from_handle(handle))
+        Scheduler::from_flavour(TokioScheduler::new(handle))
+    }
+
+    /// Drives the tasks registered afterwards from a single timer, rather than from a future per task.
+    ///
+    /// By default, each task gets a future of its own, which sleeps until the task is due. That is
+    ///   cheap for a handful of tasks, but with tens of thousands of them, the futures and their
+    ///   timers add up. With a timer driver, waiting tasks only take up an entry in a queue, and a
+    ///   single loop spawns each run as it falls due. Tasks behave the same either way.
+    ///
+    /// Only tasks which run on a timer are driven this way. Dependent tasks and
+    ///   [local tasks][Scheduler::add_local_task] still get a future of their own.
+    ///
+    /// ```
+    /// use periodically::{IntervalSchedule, Scheduler, Task};
+    /// use std::time::Duration;
+    ///
+    /// struct Tenant(u32);
+    ///
+    /// impl Task for Tenant {
+    ///     fn run(&self) {
+    ///         // ..
+    ///         // do the tenant's work
+    ///         // ..
+    ///     }
+    /// }
+    ///
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let mut scheduler = Scheduler::tokio_scheduler(runtime).with_timer_driver();
+    /// for tenant in 0..10_000 {
+    ///     let schedule = IntervalSchedule::every(Duration::from_secs(60));
+    ///     scheduler.add_sync_task(Tenant(tenant), schedule).unwrap();
+    /// }
+    /// ```
+    pub fn with_timer_driver(self) -> Self {
+        match &mut self.core().flavour {
+            SchedulerFlavour::Tokio(tok) => tok.start_timer_driver(),
+        }
+        self
     }
 }
 
 pub struct TokioScheduler {
-    runtime_handle: RuntimeHandle,
-    task_handles: HashMap<TaskIdentifier, JoinHandle<()>>,
+    runtime_handle: tokio::runtime::Handle,
+    tasks: HashMap<TaskIdentifier, TaskEntry>,
+    dependents: SharedDependents,
+    /// Handed to running tasks, see [SchedulerHandle::current].
+    scheduler_handle: Option<SchedulerHandle>,
+    /// Applies the commands sent through [SchedulerHandle]s.
+    command_loop: Option<JoinHandle<()>>,
+    /// Drives timer-driven tasks instead of their own nannies, see [Scheduler::with_timer_driver].
+    timer_driver: Option<TimerDriver>,
+    /// How the tasks which were reaped ended.
+    endings: Endings,
+}
+
+/// How many reaped tasks are remembered, so that they are reported as finished rather than unknown.
+const RETAINED_ENDINGS: usize = 1024;
+
+/// How the latest [RETAINED_ENDINGS] reaped tasks ended, see [TokioScheduler::reap].
+#[derive(Default)]
+struct Endings {
+    /// Oldest first.
+    order: VecDeque<TaskIdentifier>,
+    statuses: HashMap<TaskIdentifier, TaskStatus>,
+}
+
+impl Endings {
+    fn record(&mut self, task_identifier: TaskIdentifier, status: TaskStatus) {
+        if self.order.len() == RETAINED_ENDINGS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.statuses.remove(&oldest);
+            }
+        }
+        self.order.push_back(task_identifier);
+        self.statuses.insert(task_identifier, status);
+    }
+
+    fn get(&self, task_identifier: TaskIdentifier) -> Option<&TaskStatus> {
+        self.statuses.get(&task_identifier)
+    }
+
+    /// The error for a task which is no longer registered.
+    fn missing(&self, task_identifier: TaskIdentifier) -> Error {
+        match self.get(task_identifier) {
+            Some(_) => Error::AlreadyFinished(task_identifier),
+            None => Error::UnknownTask(task_identifier),
+        }
+    }
+}
+
+/// The scheduler's side of a registered task.
+struct TaskEntry {
+    driver: TaskDriver,
+    /// See [Scheduler::subscribe]. `None` for [local tasks][Scheduler::add_local_task], whose
+    ///   outputs need not be [Send].
+    outputs: Option<ErasedOutputs>,
+    /// See [RunContext::in_flight].
+    in_flight: watch::Receiver<bool>,
+    /// See [RunContext::failure].
+    failure: FailureSlot,
+}
+
+/// What drives a registered task.
+enum TaskDriver {
+    /// A future dedicated to the task.
+    Nanny {
+        nanny: JoinHandle<()>,
+        /// See [NannyContext::finished].
+        finished: Arc<AtomicBool>,
+        /// See [RunContext::paused].
+        pause_switch: watch::Sender<bool>,
+        /// See [Scheduler::trigger_task].
+        run_now: Arc<Notify>,
+    },
+    /// The scheduler's [TimerDriver], which is shared with other tasks.
+    Timer {
+        queue: Arc<TimerQueue>,
+        /// See [RunContext::paused].
+        pause_switch: watch::Sender<bool>,
+    },
+}
+
+impl TaskDriver {
+    fn is_finished(&self, task_identifier: TaskIdentifier) -> bool {
+        match self {
+            TaskDriver::Nanny {
+                nanny, finished, ..
+            } => finished.load(Ordering::Acquire) || nanny.is_finished(),
+            TaskDriver::Timer { queue, .. } => queue.is_finished(task_identifier),
+        }
+    }
+
+    fn abort(self, task_identifier: TaskIdentifier) {
+        match self {
+            TaskDriver::Nanny { nanny, .. } => nanny.abort(),
+            TaskDriver::Timer { queue, .. } => queue.remove(task_identifier),
+        }
+    }
+
+    fn set_paused(&self, task_identifier: TaskIdentifier, paused: bool) {
+        match self {
+            TaskDriver::Nanny { pause_switch, .. } => {
+                pause_switch.send_replace(paused);
+            }
+            TaskDriver::Timer {
+                queue,
+                pause_switch,
+            } => {
+                pause_switch.send_replace(paused);
+                queue.set_paused(task_identifier, paused);
+            }
+        }
+    }
+
+    fn trigger(&self, task_identifier: TaskIdentifier) {
+        match self {
+            TaskDriver::Nanny { run_now, .. } => run_now.notify_one(),
+            TaskDriver::Timer { queue, .. } => queue.trigger(task_identifier),
+        }
+    }
+
+    fn is_paused(&self, task_identifier: TaskIdentifier) -> bool {
+        match self {
+            TaskDriver::Nanny { pause_switch, .. } => *pause_switch.borrow(),
+            TaskDriver::Timer { queue, .. } => queue.is_paused(task_identifier),
+        }
+    }
 }
 
 impl From<TokioScheduler> for SchedulerFlavour {
@@ -53,19 +255,302 @@ impl From<TokioScheduler> for SchedulerFlavour {
 }
 
 impl TokioScheduler {
-    fn new_from_runtime(runtime: tokio::runtime::Runtime) -> Self {
+    fn new(runtime_handle: tokio::runtime::Handle) -> Self {
         Self {
-            runtime_handle: RuntimeHandle::Runtime(runtime),
-            task_handles: HashMap::new(),
+            runtime_handle,
+            tasks: HashMap::new(),
+            dependents: Default::default(),
+            scheduler_handle: None,
+            command_loop: None,
+            timer_driver: None,
+            endings: Endings::default(),
         }
     }
 
-    fn new_from_handle(handle: tokio::runtime::Handle) -> Self {
-        Self {
-            runtime_handle: RuntimeHandle::Handle(handle),
-            task_handles: HashMap::new(),
+    /// Registers `task_identifier`, driving it on the timer driver if it has one and the task
+    ///   runs on a timer, or else with a nanny spawned on the runtime.
+    fn add<T, R, S>(
+        &mut self,
+        mut options: TaskOptions,
+        task_identifier: TaskIdentifier,
+        run: R,
+        schedule: S,
+    ) -> Result<(), Error>
+    where
+        T: Send + 'static,
+        R: Fn(RunPermit) -> JoinHandle<T> + Send + Sync + 'static,
+        S: Schedule<T> + Send + 'static,
+    {
+        if self.timer_driver.is_some() && options.dependencies.is_none() {
+            return self.add_timed(options, task_identifier, run, schedule);
+        }
+        let runtime_handle = self.runtime_handle.clone();
+        let factory = options.schedule_factory.take();
+        let jitter = options.jitter;
+        let outputs = Arc::new(TaskOutputs::new());
+        let erased = outputs::erase(&outputs);
+        self.add_with(options, task_identifier, Some(erased), |context| {
+            let schedule = Jittered::new(Supervised::new(schedule, factory, &context.runs), jitter);
+            runtime_handle.spawn(nanny(run, schedule, context, outputs))
+        })
+    }
+
+    /// Registers `task_identifier`, and drives it with the nanny spawned by `spawn`. `outputs` is
+    ///   `None` if the task's outputs can not be subscribed to.
+    fn add_with(
+        &mut self,
+        mut options: TaskOptions,
+        task_identifier: TaskIdentifier,
+        outputs: Option<ErasedOutputs>,
+        spawn: impl FnOnce(NannyContext) -> JoinHandle<()>,
+    ) -> Result<(), Error> {
+        let (pause_switch, paused) = watch::channel(options.start_paused);
+        let runs = self.run_context(task_identifier, &mut options, paused);
+        let in_flight = runs.in_flight.subscribe();
+        let failure = runs.failure.clone();
+        let triggers = match options.dependencies {
+            Some(dependencies) => Some(self.register_dependent(dependencies, task_identifier)?),
+            None => {
+                self.dependents
+                    .lock()
+                    .unwrap()
+                    .graph
+                    .insert_task(task_identifier);
+                None
+            }
+        };
+        let run_now = Arc::new(Notify::new());
+        let finished = Arc::new(AtomicBool::new(false));
+        let context = NannyContext {
+            runs,
+            triggers,
+            run_now: run_now.clone(),
+            catching_up: CatchingUp::new(options.catch_up.unwrap_or_default()),
+            finished: finished.clone(),
+            held_back: false,
+        };
+
+        let mut handle = spawn(context);
+        if was_cancelled(&mut handle) {
+            self.forget(task_identifier);
+            return Err(Error::RuntimeShutDown);
+        }
+        let entry = TaskEntry {
+            driver: TaskDriver::Nanny {
+                nanny: handle,
+                finished,
+                pause_switch,
+                run_now,
+            },
+            outputs,
+            in_flight,
+            failure,
+        };
+        self.tasks.insert(task_identifier, entry);
+        Ok(())
+    }
+
+    /// Registers `task_identifier`, and queues it on the timer driver.
+    fn add_timed<T, R, S>(
+        &mut self,
+        mut options: TaskOptions,
+        task_identifier: TaskIdentifier,
+        run: R,
+        schedule: S,
+    ) -> Result<(), Error>
+    where
+        T: Send + 'static,
+        R: Fn(RunPermit) -> JoinHandle<T> + Send + Sync + 'static,
+        S: Schedule<T> + Send + 'static,
+    {
+        let Some(timer_driver) = &self.timer_driver else {
+            unreachable!("only called with a timer driver");
+        };
+        if timer_driver.has_shut_down() {
+            return Err(Error::RuntimeShutDown);
+        }
+        let queue = timer_driver.queue();
+        self.dependents
+            .lock()
+            .unwrap()
+            .graph
+            .insert_task(task_identifier);
+        let outputs = Arc::new(TaskOutputs::new());
+        let (pause_switch, paused) = watch::channel(options.start_paused);
+        let context = self.run_context(task_identifier, &mut options, paused);
+        let in_flight = context.in_flight.subscribe();
+        let failure = context.failure.clone();
+        let schedule = Supervised::new(schedule, options.schedule_factory.take(), &context);
+        let schedule = Jittered::new(schedule, options.jitter);
+        let job = TimedTask {
+            run,
+            schedule,
+            context,
+            outputs: outputs.clone(),
+            catching_up: CatchingUp::new(options.catch_up.unwrap_or_default()),
+        };
+        queue.insert(task_identifier, Box::new(job), options.start_paused);
+        let entry = TaskEntry {
+            driver: TaskDriver::Timer {
+                queue,
+                pause_switch,
+            },
+            outputs: Some(outputs::erase(&outputs)),
+            in_flight,
+            failure,
+        };
+        self.tasks.insert(task_identifier, entry);
+        Ok(())
+    }
+
+    fn run_context(
+        &self,
+        task_identifier: TaskIdentifier,
+        options: &mut TaskOptions,
+        paused: watch::Receiver<bool>,
+    ) -> RunContext {
+        RunContext {
+            task_identifier,
+            dependents: self.dependents.clone(),
+            timeout: options.timeout,
+            limit: options.group.as_ref().and_then(|group| group.limit.clone()),
+            overlap: (options.overlap == Overlap::Wait).then(|| Arc::new(Semaphore::new(1))),
+            in_flight: watch::channel(false).0,
+            paused,
+            supervision: options.supervision.clone(),
+            failure: FailureSlot::default(),
+            on_finish: Mutex::new(options.on_finish.take()),
+            reaper: self.scheduler_handle.clone(),
+        }
+    }
+
+    /// See [Scheduler::with_timer_driver].
+    fn start_timer_driver(&mut self) {
+        if self.timer_driver.is_none() {
+            self.timer_driver = Some(TimerDriver::start(&self.runtime_handle));
+        }
+    }
+
+    /// Registers `task_identifier` as a dependent task, returning the triggers its nanny waits on.
+    fn register_dependent(
+        &mut self,
+        dependencies: Vec<Dependency>,
+        task_identifier: TaskIdentifier,
+    ) -> Result<Triggers, DependencyError> {
+        let mut dependents = self.dependents.lock().unwrap();
+        dependents
+            .graph
+            .insert_dependent(task_identifier, dependencies)?;
+        let (sender, receiver) = unbounded_channel();
+        dependents.listeners.insert(task_identifier, sender);
+        Ok(Triggers {
+            receiver,
+            satisfied: HashSet::new(),
+            orphaned: false,
+        })
+    }
+
+    /// Removes a task from the dependency graph.
+    fn forget(&mut self, task_identifier: TaskIdentifier) {
+        let mut dependents = self.dependents.lock().unwrap();
+        let orphans = dependents.graph.remove(task_identifier);
+        dependents.listeners.remove(&task_identifier);
+        // wakes up the dependents which lost their last upstream task, so that they finish
+        for orphan in orphans {
+            if let Some(listener) = dependents.listeners.get(&orphan) {
+                let _ = listener.send(Signal::Gone);
+            }
+        }
+    }
+
+    /// Looks up a task whose schedule has not finished yet.
+    fn running(&self, task_identifier: TaskIdentifier) -> Result<&TaskEntry, Error> {
+        let entry = self
+            .tasks
+            .get(&task_identifier)
+            .ok_or_else(|| self.endings.missing(task_identifier))?;
+        match entry.driver.is_finished(task_identifier) {
+            true => Err(Error::AlreadyFinished(task_identifier)),
+            false => Ok(entry),
         }
     }
+
+    /// Looks up the outputs of a task, which only exist until its schedule finishes.
+    pub(super) fn outputs_of(
+        &self,
+        task_identifier: TaskIdentifier,
+    ) -> Result<Arc<dyn Any + Send + Sync>, Error> {
+        let entry = self
+            .tasks
+            .get(&task_identifier)
+            .ok_or_else(|| self.endings.missing(task_identifier))?;
+        let Some(outputs) = &entry.outputs else {
+            return Err(Error::LocalOutputs(task_identifier));
+        };
+        outputs
+            .upgrade()
+            .ok_or(Error::AlreadyFinished(task_identifier))
+    }
+
+    /// Subscribes to whether a task has a run in flight, see [Scheduler::pause_all].
+    pub(super) fn in_flight(
+        &self,
+        task_identifier: TaskIdentifier,
+    ) -> Option<watch::Receiver<bool>> {
+        let entry = self.tasks.get(&task_identifier)?;
+        Some(entry.in_flight.clone())
+    }
+
+    pub(super) fn runtime_handle(&self) -> tokio::runtime::Handle {
+        self.runtime_handle.clone()
+    }
+
+    /// The identifiers of every registered task.
+    pub(super) fn task_identifiers(&self) -> Vec<TaskIdentifier> {
+        self.tasks.keys().copied().collect()
+    }
+
+    /// Forgets a task whose schedule finished. Returns whether it was still registered.
+    pub(super) fn reap(&mut self, task_identifier: TaskIdentifier) -> bool {
+        let Some(entry) = self.tasks.get(&task_identifier) else {
+            // cancelled in the meantime
+            return false;
+        };
+        if !entry.driver.is_finished(task_identifier) {
+            return false;
+        }
+        let status = match entry.failure.get() {
+            Some(failure) => TaskStatus::Failed(failure.clone()),
+            None => TaskStatus::Finished,
+        };
+        self.tasks.remove(&task_identifier);
+        // its dependents were already sent its last runs, and still get to run on them
+        self.forget(task_identifier);
+        self.endings.record(task_identifier, status);
+        #[cfg(feature = "log")]
+        log::debug!("Reaped finished task [{task_identifier}].");
+        true
+    }
+
+    /// See [Scheduler::add_local_task].
+    pub(super) fn add_local_task<T: 'static>(
+        &mut self,
+        local_set: &LocalSet,
+        task: impl LocalAsyncTask<T> + 'static,
+        schedule: impl Schedule<T> + 'static,
+        mut options: TaskOptions,
+        task_identifier: TaskIdentifier,
+    ) -> Result<(), Error> {
+        let run = local_runs(task, self.scheduler_handle.clone());
+        let factory = options.schedule_factory.take();
+        let jitter = options.jitter;
+        // nobody subscribes to the outputs, so they are never published
+        let outputs = Arc::new(TaskOutputs::new());
+        self.add_with(options, task_identifier, None, |context| {
+            let schedule = Jittered::new(Supervised::new(schedule, factory, &context.runs), jitter);
+            local_set.spawn_local(nanny(run, schedule, context, outputs))
+        })
+    }
 }
 
 impl SchedulerExt for TokioScheduler {
@@ -73,111 +558,560 @@ impl SchedulerExt for TokioScheduler {
         &mut self,
         task: impl crate::Task<T> + Send + Sync + 'static,
         schedule: impl Schedule<T> + Send + 'static,
+        options: TaskOptions,
         task_identifier: TaskIdentifier,
-    ) {
-        let handle = self
-            .runtime_handle
-            .spawn(sync_nanny(task, schedule, task_identifier));
-        self.task_handles.insert(task_identifier, handle);
+    ) -> Result<(), Error> {
+        let thread_name = match &options.name {
+            Some(name) => format!("periodically-{name}"),
+            None => format!("periodically-task-{task_identifier}"),
+        };
+        let runner = SyncRunner::new(options.executor.clone(), thread_name, &self.runtime_handle)?;
+        let run = sync_runs(task, runner, self.scheduler_handle.clone());
+        self.add(options, task_identifier, run, schedule)
     }
 
-    fn add_async_task<T>(
+    fn add_async_task<T: Send + 'static>(
         &mut self,
         task: impl AsyncTask<T> + Send + Sync + 'static,
         schedule: impl Schedule<T> + Send + 'static,
+        options: TaskOptions,
         task_identifier: TaskIdentifier,
-    ) where
-        T: 'static + Send + Sync,
-    {
-        let handle = self
-            .runtime_handle
-            .spawn(async_nanny(task, schedule, task_identifier));
-        self.task_handles.insert(task_identifier, handle);
+    ) -> Result<(), Error> {
+        let run = async_runs(task, self.scheduler_handle.clone());
+        self.add(options, task_identifier, run, schedule)
     }
 
-    fn cancel_task(&mut self, task_identifier: TaskIdentifier) -> Option<()> {
-        self.task_handles.remove(&task_identifier).map(|handle| {
-            handle.abort();
-        })
+    fn add_dependency(
+        &mut self,
+        task_identifier: TaskIdentifier,
+        dependency: Dependency,
+    ) -> Result<(), DependencyError> {
+        self.dependents
+            .lock()
+            .unwrap()
+            .graph
+            .add_dependency(task_identifier, dependency)
+    }
+
+    fn cancel_task(&mut self, task_identifier: TaskIdentifier) -> Result<(), Error> {
+        // checked before forgetting the task, since that ends the nanny of a dependent task
+        let entry = self
+            .tasks
+            .get(&task_identifier)
+            .ok_or_else(|| self.endings.missing(task_identifier))?;
+        if entry.driver.is_finished(task_identifier) {
+            // reaped straight away, so that it is remembered as finished rather than forgotten
+            self.reap(task_identifier);
+            return Err(Error::AlreadyFinished(task_identifier));
+        }
+        self.forget(task_identifier);
+        if let Some(entry) = self.tasks.remove(&task_identifier) {
+            entry.driver.abort(task_identifier);
+        }
+        Ok(())
+    }
+
+    fn set_paused(&mut self, task_identifier: TaskIdentifier, paused: bool) -> Result<(), Error> {
+        self.running(task_identifier)?
+            .driver
+            .set_paused(task_identifier, paused);
+        Ok(())
+    }
+
+    fn trigger(&mut self, task_identifier: TaskIdentifier) -> Result<(), Error> {
+        self.running(task_identifier)?
+            .driver
+            .trigger(task_identifier);
+        Ok(())
+    }
+
+    fn status(&self, task_identifier: TaskIdentifier) -> Result<TaskStatus, Error> {
+        let Some(entry) = self.tasks.get(&task_identifier) else {
+            return match self.endings.get(task_identifier) {
+                Some(status) => Ok(status.clone()),
+                None => Err(Error::UnknownTask(task_identifier)),
+            };
+        };
+        // the failure is recorded before the schedule finishes
+        if let Some(failure) = entry.failure.get() {
+            return Ok(TaskStatus::Failed(failure.clone()));
+        }
+        let status = if entry.driver.is_finished(task_identifier) {
+            TaskStatus::Finished
+        } else if *entry.in_flight.borrow() {
+            TaskStatus::Running
+        } else if entry.driver.is_paused(task_identifier) {
+            TaskStatus::Paused
+        } else {
+            TaskStatus::Scheduled
+        };
+        Ok(status)
+    }
+
+    fn serve(
+        &mut self,
+        scheduler_handle: SchedulerHandle,
+        commands: CommandReceiver,
+        core: Weak<Mutex<SchedulerCore>>,
+    ) {
+        self.scheduler_handle = Some(scheduler_handle);
+        self.command_loop = Some(
+            self.runtime_handle
+                .spawn(handle::serve_commands(core, commands)),
+        );
     }
 }
 
-async fn sync_nanny<T>(
-    task: impl Task<T> + Sync + Send + 'static,
-    schedule: impl Schedule<T>,
+impl Drop for TokioScheduler {
+    fn drop(&mut self) {
+        if let Some(command_loop) = &self.command_loop {
+            command_loop.abort();
+        }
+    }
+}
+
+/// The dependency graph of a [TokioScheduler], shared with the nannies of its tasks.
+type SharedDependents = Arc<Mutex<Dependents>>;
+
+#[derive(Default)]
+struct Dependents {
+    graph: DependencyGraph,
+    /// Wakes up dependent tasks, see [Signal]. Only sent to while holding the lock, so that
+    ///   the signals are in step with the graph.
+    listeners: HashMap<TaskIdentifier, UnboundedSender<Signal>>,
+}
+
+/// What a dependent task is told about its upstream tasks.
+enum Signal {
+    /// The upstream task finished a run which triggers the dependent task.
+    Triggered(TaskIdentifier),
+    /// An upstream task is gone, after which the dependent task might not have any left.
+    Gone,
+}
+
+/// Per-task state that a nanny needs besides the task and its schedule.
+struct NannyContext {
+    runs: RunContext,
+    /// Only present for dependent tasks.
+    triggers: Option<Triggers>,
+    /// Notified when the task should run ahead of its schedule, see [Scheduler::trigger_task].
+    run_now: Arc<Notify>,
+    catching_up: CatchingUp,
+    /// Set once the task's schedule finishes, before the scheduler is asked to reap the task,
+    ///   which may happen before the nanny itself has returned.
+    finished: Arc<AtomicBool>,
+    /// Set when the task was paused while its run waited on its permits, after which it falls due
+    ///   again as soon as it is resumed.
+    held_back: bool,
+}
+
+/// Per-task state needed to run a task and handle its result, however the task is driven.
+struct RunContext {
     task_identifier: TaskIdentifier,
-) where
-    T: Send + 'static,
-{
-    let mut next = schedule.initial();
-    let task = Arc::new(task);
+    dependents: SharedDependents,
+    timeout: Option<Duration>,
+    /// The concurrency limit of the task's [TaskGroup][crate::TaskGroup], if any.
+    limit: Option<Arc<Semaphore>>,
+    /// Only present if runs must not overlap, see [Overlap::Wait].
+    overlap: Option<Arc<Semaphore>>,
+    /// Whether a run is in progress, from once it holds its permits until it ends. See [Scheduler::pause_all].
+    in_flight: watch::Sender<bool>,
+    /// Whether the task is paused, see [Scheduler::pause_task].
+    paused: watch::Receiver<bool>,
+    /// Applied to the task's schedule by [Supervised].
+    supervision: Supervision,
+    /// Set once the task's schedule failed, see [Scheduler::task_status].
+    failure: FailureSlot,
+    /// Taken once the task's schedule finishes, see [TaskBuilder::on_finish][crate::TaskBuilder::on_finish].
+    on_finish: Mutex<Option<OnFinish>>,
+    /// Reaps the task once its schedule finishes.
+    reaper: Option<SchedulerHandle>,
+}
+
+/// Clears [RunContext::in_flight] once a run ends, even if the run was aborted.
+struct Landed<'a>(&'a watch::Sender<bool>);
+
+impl Drop for Landed<'_> {
+    fn drop(&mut self) {
+        self.0.send_replace(false);
+    }
+}
+
+/// Applies a task's [CatchUp] policy to the runs which fell due while it was paused.
+struct CatchingUp {
+    policy: CatchUp,
+    /// While running every missed run, the time at which the latest missed run was due.
+    behind: Option<Instant>,
+}
+
+impl CatchingUp {
+    fn new(policy: CatchUp) -> Self {
+        Self {
+            policy,
+            behind: None,
+        }
+    }
+
+    /// Called when a task is resumed after falling due `missed` ago. Returns how long until
+    ///   the task should run, like [Schedule::next].
+    fn resumed<T>(&mut self, missed: Duration, schedule: &impl Schedule<T>) -> Option<Duration> {
+        match self.policy {
+            CatchUp::Skip => schedule.initial(),
+            CatchUp::RunOnce => Some(Duration::ZERO),
+            CatchUp::RunAll => {
+                self.behind = Instant::now().checked_sub(missed);
+                Some(Duration::ZERO)
+            }
+        }
+    }
+
+    /// Applies any ongoing catch up to the schedule's `next` delay, after a run.
+    fn next(&mut self, next: Option<Duration>) -> Option<Duration> {
+        let (Some(behind), Some(delay)) = (self.behind.take(), next) else {
+            return next;
+        };
+        let now = Instant::now();
+        match behind.checked_add(delay) {
+            Some(due) if due <= now => {
+                self.behind = Some(due);
+                Some(Duration::ZERO)
+            }
+            Some(due) => Some(due - now),
+            None => next,
+        }
+    }
+}
+
+struct Triggers {
+    receiver: UnboundedReceiver<Signal>,
+    /// The upstream tasks which triggered the task since its last run, including ones which are
+    ///   gone since.
+    satisfied: HashSet<TaskIdentifier>,
+    /// Set once every upstream task is gone, after which the task never runs again.
+    orphaned: bool,
+}
+
+impl Triggers {
+    fn receive(&mut self, signal: Signal) {
+        if let Signal::Triggered(upstream) = signal {
+            self.satisfied.insert(upstream);
+        }
+    }
+}
+
+impl NannyContext {
+    /// Waits until the task is due to run again, or is triggered, and is not paused. Returns `None`
+    ///   if it never will be, either because it was cancelled or because it was
+    ///   [orphaned][Triggers::orphaned]. Otherwise, returns how long ago the task fell due if it was
+    ///   paused at the time, and was not triggered since.
+    ///
+    /// `wall_clock` is whether the task's schedule [follows the wall clock][Schedule::follows_wall_clock].
+    async fn wait(&mut self, duration: Duration, wall_clock: bool) -> Option<Option<Duration>> {
+        let run_now = self.run_now.clone();
+        let held_back = std::mem::take(&mut self.held_back);
+        let due = async {
+            if let Some(triggers) = &mut self.triggers {
+                loop {
+                    let upstreams = {
+                        let dependents = self.runs.dependents.lock().unwrap();
+                        // catches up on the last runs of upstream tasks which are gone from the graph
+                        while let Ok(signal) = triggers.receiver.try_recv() {
+                            triggers.receive(signal);
+                        }
+                        dependents.graph.upstreams_of(self.runs.task_identifier)
+                    };
+                    if upstreams.is_empty() && triggers.satisfied.is_empty() {
+                        #[cfg(feature = "log")]
+                        log::warn!(
+                            "Every upstream of task [{}] is gone, so it will not run again.",
+                            self.runs.task_identifier
+                        );
+                        triggers.orphaned = true;
+                        return false;
+                    }
+                    if upstreams.is_subset(&triggers.satisfied) {
+                        triggers.satisfied.clear();
+                        break;
+                    }
+                    match triggers.receiver.recv().await {
+                        Some(signal) => triggers.receive(signal),
+                        None => return false,
+                    }
+                }
+            }
+            sleep(duration, wall_clock).await;
+            true
+        };
+        let triggered = match held_back {
+            true => false,
+            false => match unless(due, run_now.notified()).await {
+                Some(false) => return None,
+                Some(true) => false,
+                None => true,
+            },
+        };
+        let fell_due = Instant::now();
+        let was_paused = *self.runs.paused.borrow();
+        // the scheduler dropping the pause switch means that the task was cancelled
+        self.runs.paused.wait_for(|paused| !paused).await.ok()?;
+        Some((was_paused && !triggered).then(|| fell_due.elapsed()))
+    }
+}
+
+impl RunContext {
+    /// Runs the task once, giving up on the run if it takes longer than the task's timeout. Returns
+    ///   `None` without running the task if it was paused while the run waited on its permits.
+    async fn run<T>(
+        &self,
+        run: &impl Fn(RunPermit) -> JoinHandle<T>,
+    ) -> Option<Result<T, TaskFailure>> {
+        let overlap = match &self.overlap {
+            // the semaphore is never closed
+            Some(overlap) => overlap.clone().acquire_owned().await.ok(),
+            None => None,
+        };
+        let _permit = match &self.limit {
+            // the semaphore is never closed
+            Some(limit) => limit.acquire().await.ok(),
+            None => None,
+        };
+        self.in_flight.send_replace(true);
+        let _landed = Landed(&self.in_flight);
+        // pausing the task in the meantime must either be seen here, or see the run in flight
+        if *self.paused.borrow() {
+            return None;
+        }
+        let mut join_handle = run(overlap);
+        let joined = match self.timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, &mut join_handle).await {
+                Ok(joined) => joined,
+                Err(_elapsed) => {
+                    join_handle.abort();
+                    return Some(Err(TaskFailure::TimedOut(timeout)));
+                }
+            },
+            None => join_handle.await,
+        };
+        Some(joined.map_err(|err| match err.try_into_panic() {
+            Ok(payload) => TaskFailure::from_panic(payload),
+            Err(_) => TaskFailure::Cancelled,
+        }))
+    }
+
+    /// Called once the task's schedule finishes. Runs the task's `on_finish` callback, and then has
+    ///   the scheduler reap the task.
+    fn finished(&self) {
+        let task_identifier = self.task_identifier;
+        let on_finish = self.on_finish.lock().unwrap().take();
+        if let Some(on_finish) = on_finish {
+            let status = match self.failure.get() {
+                Some(failure) => TaskStatus::Failed(failure.clone()),
+                None => TaskStatus::Finished,
+            };
+            let on_finish = AssertUnwindSafe(|| on_finish(task_identifier, status));
+            if catch_unwind(on_finish).is_err() {
+                #[cfg(feature = "log")]
+                log::error!("The on_finish callback of task [{task_identifier}] panicked.");
+            }
+        }
+        if let Some(reaper) = &self.reaper {
+            reaper.notify(move |core| core.reap(task_identifier));
+        }
+    }
+
+    /// Lets any dependent tasks know that this task finished a run.
+    fn notify_dependents(&self, succeeded: bool) {
+        let dependents = self.dependents.lock().unwrap();
+        for dependent in dependents
+            .graph
+            .triggered_by(self.task_identifier, succeeded)
+        {
+            if let Some(listener) = dependents.listeners.get(&dependent) {
+                let _ = listener.send(Signal::Triggered(self.task_identifier));
+            }
+        }
+    }
+}
+
+/// The longest a task which [follows the wall clock][Schedule::follows_wall_clock] sleeps before
+///   checking the wall clock. See [sleep].
+const WALL_CLOCK_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Sleeps for `duration`. If `wall_clock` is set, wakes up early if the wall clock says that it
+///   has already passed.
+///
+/// Tokio's timers are monotonic, and on some platforms the monotonic clock stops while the
+///   host is suspended. So after a suspend, a plain [tokio::time::sleep] would keep waiting
+///   for however long was left when the host went to sleep. That is what an interval means, but
+///   a task which runs at a time of day would run late. For those, the wall clock is checked
+///   periodically, and the sleep ends once either clock says the time is up.
+async fn sleep(duration: Duration, wall_clock: bool) {
+    let started = Instant::now();
+    let wall_started = SystemTime::now();
+    let Some(deadline) = started.checked_add(duration) else {
+        // too far away to ever arrive
+        return std::future::pending().await;
+    };
+    if !wall_clock {
+        return tokio::time::sleep_until(deadline.into()).await;
+    }
     loop {
-        match next {
-            Some(duration) => {
-                tokio::time::sleep(duration).await;
-                let task = task.clone();
-                let join_handle = spawn_blocking(move || task.run());
-                next = handle_task_result(join_handle.await, &schedule, task_identifier);
+        let now = Instant::now();
+        if now >= deadline {
+            return;
+        }
+        tokio::time::sleep((deadline - now).min(WALL_CLOCK_CHECK_INTERVAL)).await;
+        let wall_elapsed = wall_started.elapsed().unwrap_or_default();
+        if wall_elapsed >= duration {
+            #[cfg(feature = "log")]
+            if wall_elapsed > started.elapsed() + WALL_CLOCK_CHECK_INTERVAL {
+                log::warn!(
+                    "Wall clock jumped forward by [{:?}], waking up early.",
+                    wall_elapsed - started.elapsed()
+                );
             }
-            None => return,
+            return;
         }
     }
 }
 
-async fn async_nanny<T>(
+/// Held by a run until it ends, even if it timed out. See [Overlap::Wait].
+type RunPermit = Option<OwnedSemaphorePermit>;
+
+/// Spawns the runs of a synchronous task on its [SyncExecutor][crate::SyncExecutor].
+fn sync_runs<T: Send + 'static>(
+    task: impl Task<T> + Send + Sync + 'static,
+    runner: SyncRunner,
+    scheduler_handle: Option<SchedulerHandle>,
+) -> impl Fn(RunPermit) -> JoinHandle<T> + Send + Sync + 'static {
+    let task = Arc::new(task);
+    move |permit| {
+        let task = task.clone();
+        let scheduler_handle = scheduler_handle.clone();
+        runner.spawn(move || {
+            let _permit = permit;
+            handle::enter(scheduler_handle, || task.run())
+        })
+    }
+}
+
+/// Spawns the runs of an asynchronous task on the runtime's executor.
+fn async_runs<T: Send + 'static>(
     task: impl AsyncTask<T> + Send + Sync + 'static,
+    scheduler_handle: Option<SchedulerHandle>,
+) -> impl Fn(RunPermit) -> JoinHandle<T> + Send + Sync + 'static {
+    let task = Arc::new(task);
+    move |permit| {
+        let task = task.clone();
+        let run = async move {
+            let _permit = permit;
+            task.run().await
+        };
+        tokio::spawn(handle::scope(scheduler_handle.clone(), run))
+    }
+}
+
+/// Like [async_runs], except that the runs are spawned on the current [LocalSet].
+fn local_runs<T: 'static>(
+    task: impl LocalAsyncTask<T> + 'static,
+    scheduler_handle: Option<SchedulerHandle>,
+) -> impl Fn(RunPermit) -> JoinHandle<T> + 'static {
+    let task = Rc::new(task);
+    move |permit| {
+        let task = task.clone();
+        let run = async move {
+            let _permit = permit;
+            task.run().await
+        };
+        spawn_local(handle::scope(scheduler_handle.clone(), run))
+    }
+}
+
+/// Drives a single task: waits for it to be due, runs it, and consults the schedule.
+async fn nanny<T>(
+    run: impl Fn(RunPermit) -> JoinHandle<T>,
     schedule: impl Schedule<T>,
-    task_identifier: TaskIdentifier,
-) where
-    T: Send + Sync + 'static,
-{
+    mut context: NannyContext,
+    outputs: Arc<TaskOutputs<T>>,
+) {
     let mut next = schedule.initial();
-    let task = Arc::new(task);
-    loop {
-        match next {
-            Some(duration) => {
-                tokio::time::sleep(duration).await;
-                let task = task.clone();
-                let join_handle = tokio::spawn(async move { task.run().await });
-                next = handle_task_result(join_handle.await, &schedule, task_identifier);
+    while let Some(duration) = next {
+        let Some(missed) = context.wait(duration, schedule.follows_wall_clock()).await else {
+            // an orphaned task finishes like any other, whereas a cancelled one is already forgotten
+            match context
+                .triggers
+                .as_ref()
+                .is_some_and(|triggers| triggers.orphaned)
+            {
+                true => break,
+                false => return,
+            }
+        };
+        if let Some(missed) = missed {
+            next = context.catching_up.resumed(missed, &schedule);
+            if next != Some(Duration::ZERO) {
+                continue;
             }
-            None => return,
         }
+        let Some(result) = context.runs.run(&run).await else {
+            context.held_back = true;
+            continue;
+        };
+        next = handle_task_result(result, &schedule, &context.runs, &outputs);
+        next = context.catching_up.next(next);
     }
+    context.finished.store(true, Ordering::Release);
+    context.runs.finished();
 }
 
 fn handle_task_result<T>(
-    join_handle: Result<T, JoinError>,
+    result: Result<T, TaskFailure>,
     schedule: &impl Schedule<T>,
-    task_identifier: TaskIdentifier,
+    context: &RunContext,
+    outputs: &TaskOutputs<T>,
 ) -> Option<Duration> {
-    let next = match join_handle {
-        Ok(task_result) => schedule.next(task_result),
-        Err(err) => {
+    let succeeded = result.is_ok();
+    let next = match result {
+        Ok(task_result) => {
+            outputs.publish(Ok(&task_result));
+            schedule.next(task_result)
+        }
+        Err(failure) => {
             #[cfg(feature = "log")]
-            log::error!("Cannot join: {err:?}");
-            schedule.next_on_task_panic()
+            log::error!("Task [{}] failed: {failure}", context.task_identifier);
+            outputs.publish(Err(&failure));
+            let message = match failure {
+                TaskFailure::Panicked(message) => message,
+                _ => None,
+            };
+            with_panic_message(message, || schedule.next_on_task_panic())
         }
     };
+    context.notify_dependents(succeeded);
     #[cfg(feature = "log")]
-    log::trace!("Next event for task [{task_identifier}] will be in [{next:?}]");
+    log::trace!(
+        "Next event for task [{}] will be in [{next:?}]",
+        context.task_identifier
+    );
     next
 }
 
-enum RuntimeHandle {
-    Runtime(tokio::runtime::Runtime),
-    Handle(tokio::runtime::Handle),
+/// Runs `future` to completion, unless `interrupt` completes first, in which case `None` is returned.
+async fn unless<F: Future>(future: F, interrupt: impl Future<Output = ()>) -> Option<F::Output> {
+    let mut future = pin!(future);
+    let mut interrupt = pin!(interrupt);
+    poll_fn(|cx| match future.as_mut().poll(cx) {
+        Poll::Ready(output) => Poll::Ready(Some(output)),
+        Poll::Pending => interrupt.as_mut().poll(cx).map(|()| None),
+    })
+    .await
 }
 
-impl RuntimeHandle {
-    fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
-    where
-        F: Future + Send + 'static,
-        F::Output: Send + 'static,
-    {
-        match self {
-            RuntimeHandle::Runtime(r) => r.spawn(future),
-            RuntimeHandle::Handle(h) => h.spawn(future),
-        }
-    }
+/// Whether a freshly spawned future was cancelled straight away, which happens when its runtime has shut down.
+fn was_cancelled(handle: &mut JoinHandle<()>) -> bool {
+    let mut context = Context::from_waker(Waker::noop());
+    matches!(
+        Pin::new(handle).poll(&mut context),
+        Poll::Ready(Err(err)) if err.is_cancelled()
+    )
 }
@@ -0,0 +1,55 @@
+use crate::Schedule;
+use std::{
+    collections::hash_map::RandomState,
+    hash::BuildHasher,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// Delays the runs of a task by a random amount of up to its [jitter][crate::TaskBuilder::jitter].
+pub(super) struct Jittered<S> {
+    schedule: S,
+    jitter: Option<Duration>,
+}
+
+impl<S> Jittered<S> {
+    pub(super) fn new(schedule: S, jitter: Option<Duration>) -> Self {
+        Self { schedule, jitter }
+    }
+
+    fn jitter(&self, delay: Option<Duration>) -> Option<Duration> {
+        let (Some(delay), Some(jitter)) = (delay, self.jitter) else {
+            return delay;
+        };
+        // delays too long to add to are never going to arrive anyway
+        delay
+            .checked_add(jitter.mul_f64(random_fraction()))
+            .or(Some(delay))
+    }
+}
+
+impl<T, S: Schedule<T>> Schedule<T> for Jittered<S> {
+    fn initial(&self) -> Option<Duration> {
+        self.jitter(self.schedule.initial())
+    }
+
+    fn next(&self, task_output: T) -> Option<Duration> {
+        self.jitter(self.schedule.next(task_output))
+    }
+
+    fn next_on_task_panic(&self) -> Option<Duration> {
+        self.jitter(self.schedule.next_on_task_panic())
+    }
+
+    fn follows_wall_clock(&self) -> bool {
+        self.schedule.follows_wall_clock()
+    }
+}
+
+/// A random number in `[0, 1)`. Good enough to spread runs out, but not for anything else.
+fn random_fraction() -> f64 {
+    static DRAWS: AtomicU64 = AtomicU64::new(0);
+    let random = RandomState::new().hash_one(DRAWS.fetch_add(1, Ordering::Relaxed));
+    // the top 53 bits, which is as many as an f64 can represent exactly
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
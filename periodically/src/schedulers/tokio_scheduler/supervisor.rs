@@ -0,0 +1,136 @@
+use super::RunContext;
+use crate::{
+    schedulers::{
+        supervision::{Factory, FailureSlot},
+        ScheduleFactory, TaskIdentifier,
+    },
+    Schedule, ScheduleFailure, Supervision,
+};
+use std::{
+    cell::{Cell, RefCell},
+    panic::{catch_unwind, AssertUnwindSafe},
+    time::Duration,
+};
+
+/// Wraps the schedule of a task, so that a panicking schedule is handled according to the task's
+///   [Supervision] rather than taking down its nanny, or the run spawned by the timer driver.
+pub(super) struct Supervised<S> {
+    schedule: RefCell<S>,
+    /// Only present if the schedule was given through a schedule factory.
+    factory: Option<Factory<S>>,
+    supervision: Supervision,
+    /// How many times in a row the schedule was restarted.
+    restarts: Cell<usize>,
+    task_identifier: TaskIdentifier,
+    failure: FailureSlot,
+}
+
+impl<S: 'static> Supervised<S> {
+    pub(super) fn new(schedule: S, factory: Option<ScheduleFactory>, context: &RunContext) -> Self {
+        Self {
+            schedule: RefCell::new(schedule),
+            factory: factory.and_then(ScheduleFactory::downcast),
+            supervision: context.supervision.clone(),
+            restarts: Cell::new(0),
+            task_identifier: context.task_identifier,
+            failure: context.failure.clone(),
+        }
+    }
+}
+
+impl<S> Supervised<S> {
+    /// Consults the schedule through `consult`. `after_run` ends a streak of restarts.
+    fn consult<T>(
+        &self,
+        consult: impl FnOnce(&S) -> Option<Duration>,
+        after_run: bool,
+    ) -> Option<Duration>
+    where
+        S: Schedule<T>,
+    {
+        match catch_unwind(AssertUnwindSafe(|| consult(&self.schedule.borrow()))) {
+            Ok(next) => {
+                if after_run {
+                    self.restarts.set(0);
+                }
+                next
+            }
+            Err(payload) => self.failed(ScheduleFailure::from_panic(payload)),
+        }
+    }
+
+    /// Applies the task's supervision to a schedule which panicked. Returns how long until the task
+    ///   should run, like [Schedule::next].
+    fn failed<T>(&self, mut failure: ScheduleFailure) -> Option<Duration>
+    where
+        S: Schedule<T>,
+    {
+        #[cfg(feature = "log")]
+        log::error!(
+            "Schedule of task [{}] failed: {failure}",
+            self.task_identifier
+        );
+        while let (Supervision::Restart { max_restarts }, Some(factory)) =
+            (&self.supervision, &self.factory)
+        {
+            let restarts = self.restarts.get();
+            if restarts >= *max_restarts {
+                break;
+            }
+            self.restarts.set(restarts + 1);
+            #[cfg(feature = "log")]
+            log::warn!(
+                "Restarting the schedule of task [{}], restart [{}] of [{max_restarts}].",
+                self.task_identifier,
+                restarts + 1
+            );
+            let restarted = catch_unwind(AssertUnwindSafe(|| {
+                let schedule = factory();
+                let initial = schedule.initial();
+                (schedule, initial)
+            }));
+            match restarted {
+                Ok((schedule, initial)) => {
+                    *self.schedule.borrow_mut() = schedule;
+                    return initial;
+                }
+                Err(payload) => failure = ScheduleFailure::from_panic(payload),
+            }
+            #[cfg(feature = "log")]
+            log::error!(
+                "Schedule of task [{}] failed: {failure}",
+                self.task_identifier
+            );
+        }
+        let failure = self.failure.get_or_init(|| failure);
+        if let Supervision::Escalate(escalate) = &self.supervision {
+            let escalate = AssertUnwindSafe(|| escalate(self.task_identifier, failure));
+            if catch_unwind(escalate).is_err() {
+                #[cfg(feature = "log")]
+                log::error!(
+                    "Escalating the failure of task [{}] panicked.",
+                    self.task_identifier
+                );
+            }
+        }
+        None
+    }
+}
+
+impl<T, S: Schedule<T>> Schedule<T> for Supervised<S> {
+    fn initial(&self) -> Option<Duration> {
+        self.consult(|schedule| schedule.initial(), false)
+    }
+
+    fn next(&self, task_output: T) -> Option<Duration> {
+        self.consult(|schedule| schedule.next(task_output), true)
+    }
+
+    fn next_on_task_panic(&self) -> Option<Duration> {
+        self.consult(|schedule| schedule.next_on_task_panic(), true)
+    }
+
+    fn follows_wall_clock(&self) -> bool {
+        self.schedule.borrow().follows_wall_clock()
+    }
+}
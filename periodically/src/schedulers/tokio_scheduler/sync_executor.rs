@@ -0,0 +1,92 @@
+use crate::{Error, SyncExecutor, ThreadPool};
+use std::{
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    sync::{mpsc, Arc},
+};
+use tokio::{
+    runtime::Handle,
+    sync::oneshot,
+    task::{spawn_blocking, JoinHandle},
+};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Spawns the runs of a synchronous task on its [SyncExecutor].
+pub(super) enum SyncRunner {
+    BlockingPool,
+    /// Dropping the sender stops the thread, once it finishes the run in progress.
+    DedicatedThread(mpsc::Sender<Job>),
+    Pool(Arc<dyn ThreadPool>),
+}
+
+impl SyncRunner {
+    pub(super) fn new(
+        executor: SyncExecutor,
+        thread_name: String,
+        runtime_handle: &Handle,
+    ) -> Result<Self, Error> {
+        Ok(match executor {
+            SyncExecutor::BlockingPool => SyncRunner::BlockingPool,
+            SyncExecutor::DedicatedThread => {
+                let (sender, receiver) = mpsc::channel::<Job>();
+                let runtime_handle = runtime_handle.clone();
+                std::thread::Builder::new()
+                    .name(thread_name)
+                    .spawn(move || {
+                        // like the blocking pool, so that tasks can use `Handle::current`
+                        let _runtime = runtime_handle.enter();
+                        for job in receiver {
+                            job();
+                        }
+                    })
+                    .map_err(Error::ThreadSpawn)?;
+                SyncRunner::DedicatedThread(sender)
+            }
+            SyncExecutor::Pool(pool) => SyncRunner::Pool(pool),
+        })
+    }
+
+    /// Spawns a single run. The returned [JoinHandle] behaves like the one of [spawn_blocking],
+    ///   including for panics.
+    pub(super) fn spawn<T: Send + 'static>(
+        &self,
+        run: impl FnOnce() -> T + Send + 'static,
+    ) -> JoinHandle<T> {
+        match self {
+            SyncRunner::BlockingPool => spawn_blocking(run),
+            SyncRunner::DedicatedThread(sender) => off_runtime(run, |job| {
+                // the thread only stops once the sender is dropped, so it cannot have gone away
+                let _ = sender.send(job);
+            }),
+            SyncRunner::Pool(pool) => {
+                let runtime_handle = Handle::current();
+                off_runtime(
+                    move || {
+                        let _runtime = runtime_handle.enter();
+                        run()
+                    },
+                    |job| pool.spawn(job),
+                )
+            }
+        }
+    }
+}
+
+/// Hands `run` over to `spawn`, and joins on it from a future on the runtime.
+fn off_runtime<T: Send + 'static>(
+    run: impl FnOnce() -> T + Send + 'static,
+    spawn: impl FnOnce(Job),
+) -> JoinHandle<T> {
+    let (sender, receiver) = oneshot::channel();
+    spawn(Box::new(move || {
+        // no receiver is fine, the run may have timed out
+        let _ = sender.send(catch_unwind(AssertUnwindSafe(run)));
+    }));
+    tokio::spawn(async move {
+        match receiver.await {
+            Ok(Ok(output)) => output,
+            Ok(Err(payload)) => resume_unwind(payload),
+            Err(_) => panic!("the run was dropped by the task's executor without running"),
+        }
+    })
+}
@@ -0,0 +1,461 @@
+use super::{
+    handle_task_result, unless, CatchingUp, RunContext, RunPermit, WALL_CLOCK_CHECK_INTERVAL,
+};
+use crate::{
+    schedulers::{outputs::TaskOutputs, TaskIdentifier},
+    Schedule,
+};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::{
+    runtime::Handle,
+    sync::Notify,
+    task::{AbortHandle, JoinHandle},
+};
+
+/// The most runs the driver starts before yielding to other futures on its worker.
+const DISPATCH_BATCH: usize = 1024;
+
+/// Drives every timer-driven task of a scheduler from a single loop, see [Scheduler::with_timer_driver][crate::Scheduler::with_timer_driver].
+///
+/// Waiting tasks are kept in a [TimerQueue] rather than in futures of their own. When a task falls due,
+///   the driver spawns a future for that one run, which hands the task back to the queue once the
+///   schedule has been consulted.
+pub(super) struct TimerDriver {
+    queue: Arc<TimerQueue>,
+    driver_loop: JoinHandle<()>,
+}
+
+impl TimerDriver {
+    pub(super) fn start(runtime_handle: &Handle) -> Self {
+        let queue = Arc::new(TimerQueue {
+            state: Mutex::new(State {
+                due: BinaryHeap::new(),
+                slots: HashMap::new(),
+                next_generation: 0,
+                clock: Clock::new(),
+                follows_wall_clock: false,
+            }),
+            wake: Notify::new(),
+            runtime_handle: runtime_handle.clone(),
+        });
+        let driver_loop = runtime_handle.spawn(drive(queue.clone()));
+        Self { queue, driver_loop }
+    }
+
+    pub(super) fn queue(&self) -> Arc<TimerQueue> {
+        self.queue.clone()
+    }
+
+    /// Whether the driver stopped, which happens when its runtime has shut down.
+    pub(super) fn has_shut_down(&self) -> bool {
+        self.driver_loop.is_finished()
+    }
+}
+
+impl Drop for TimerDriver {
+    fn drop(&mut self) {
+        self.driver_loop.abort();
+    }
+}
+
+/// A task waiting on the [TimerQueue], along with its schedule.
+pub(super) trait Job: Send {
+    /// See [Schedule::initial].
+    fn initial(&self) -> Option<Duration>;
+
+    /// Runs the task once, and returns how long until it is next due.
+    fn run(&mut self) -> Pin<Box<dyn Future<Output = Option<Duration>> + Send + '_>>;
+
+    /// Called when the task is resumed after falling due `missed` ago. Returns how long until it is next due.
+    fn resumed(&mut self, missed: Duration) -> Option<Duration>;
+
+    /// Called once the task is marked finished in the queue, so that the scheduler can reap it.
+    fn finished(&self);
+
+    /// See [Schedule::follows_wall_clock].
+    fn follows_wall_clock(&self) -> bool;
+}
+
+/// The [Job] of a sync or async task. The counterpart of the arguments to a nanny.
+pub(super) struct TimedTask<R, S, T> {
+    pub(super) run: R,
+    pub(super) schedule: S,
+    pub(super) context: RunContext,
+    pub(super) outputs: Arc<TaskOutputs<T>>,
+    pub(super) catching_up: CatchingUp,
+}
+
+impl<R, S, T> Job for TimedTask<R, S, T>
+where
+    R: Fn(RunPermit) -> JoinHandle<T> + Send + Sync,
+    S: Schedule<T> + Send,
+    T: Send + 'static,
+{
+    fn initial(&self) -> Option<Duration> {
+        self.schedule.initial()
+    }
+
+    fn run(&mut self) -> Pin<Box<dyn Future<Output = Option<Duration>> + Send + '_>> {
+        Box::pin(async move {
+            let Some(result) = self.context.run(&self.run).await else {
+                // falls due again straight away, and is caught up on once resumed
+                return Some(Duration::ZERO);
+            };
+            let next = handle_task_result(result, &self.schedule, &self.context, &self.outputs);
+            self.catching_up.next(next)
+        })
+    }
+
+    fn resumed(&mut self, missed: Duration) -> Option<Duration> {
+        self.catching_up.resumed(missed, &self.schedule)
+    }
+
+    fn finished(&self) {
+        self.context.finished();
+    }
+
+    fn follows_wall_clock(&self) -> bool {
+        self.schedule.follows_wall_clock()
+    }
+}
+
+/// The tasks of a [TimerDriver], ordered by when they are next due.
+pub(super) struct TimerQueue {
+    state: Mutex<State>,
+    /// Wakes up the driver when a task is queued ahead of the ones it is waiting on.
+    wake: Notify,
+    runtime_handle: Handle,
+}
+
+struct State {
+    /// Entries are not removed when a task is requeued or cancelled. Instead, they go stale, and
+    ///   are skipped once they come up.
+    due: BinaryHeap<Reverse<Due>>,
+    slots: HashMap<TaskIdentifier, Slot>,
+    next_generation: u64,
+    clock: Clock,
+    /// Whether a task which [follows the wall clock][Schedule::follows_wall_clock] was ever
+    ///   queued, after which the driver keeps an eye on the wall clock.
+    follows_wall_clock: bool,
+}
+
+/// A registered task.
+#[derive(Default)]
+struct Slot {
+    /// Taken while the task runs, and dropped once its schedule finishes.
+    job: Option<Box<dyn Job>>,
+    /// The generation of the task's live entry in the queue, if any.
+    queued: Option<u64>,
+    running: Option<AbortHandle>,
+    /// See [Scheduler::pause_task][crate::Scheduler::pause_task].
+    paused: bool,
+    /// Whether the task should run as soon as it can, because it was triggered.
+    pending: bool,
+    /// When the task fell due while paused.
+    missed: Option<Instant>,
+    finished: bool,
+}
+
+struct Due {
+    at: Instant,
+    generation: u64,
+    task: TaskIdentifier,
+    /// Whether the task's schedule [follows the wall clock][Schedule::follows_wall_clock]. If it
+    ///   does not, `at` moves along with the clock when the wall clock jumps ahead.
+    wall_clock: bool,
+}
+
+impl PartialEq for Due {
+    fn eq(&self, other: &Self) -> bool {
+        self.generation == other.generation
+    }
+}
+
+impl Eq for Due {}
+
+impl PartialOrd for Due {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Due {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.at, self.generation).cmp(&(other.at, other.generation))
+    }
+}
+
+/// A monotonic clock, which follows the wall clock whenever that jumps ahead. See [super::sleep].
+struct Clock {
+    started: Instant,
+    wall_started: SystemTime,
+    /// How far the wall clock has jumped ahead of the monotonic clock, eg; while the host was suspended.
+    skew: Duration,
+}
+
+impl Clock {
+    fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            wall_started: SystemTime::now(),
+            skew: Duration::ZERO,
+        }
+    }
+
+    fn now(&mut self) -> Instant {
+        let now = Instant::now();
+        let elapsed = now - self.started;
+        let wall_elapsed = self.wall_started.elapsed().unwrap_or_default();
+        if wall_elapsed > elapsed + self.skew {
+            #[cfg(feature = "log")]
+            if wall_elapsed > elapsed + self.skew + WALL_CLOCK_CHECK_INTERVAL {
+                log::warn!(
+                    "Wall clock jumped forward by [{:?}], waking up early.",
+                    wall_elapsed - elapsed - self.skew
+                );
+            }
+            self.skew = wall_elapsed - elapsed;
+        }
+        now + self.skew
+    }
+}
+
+impl State {
+    /// Queues a task to run after `delay`. Returns whether it is now the first task due.
+    fn queue(&mut self, task: TaskIdentifier, delay: Duration) -> bool {
+        let generation = self.next_generation;
+        self.next_generation += 1;
+        let Some(slot) = self.slots.get_mut(&task) else {
+            return false;
+        };
+        slot.queued = Some(generation);
+        let wall_clock = slot
+            .job
+            .as_ref()
+            .is_some_and(|job| job.follows_wall_clock());
+        self.follows_wall_clock |= wall_clock;
+        let Some(at) = self.now().checked_add(delay) else {
+            // too far away to ever arrive, unless triggered
+            return false;
+        };
+        let first = self.due.peek().is_none_or(|Reverse(due)| at < due.at);
+        self.due.push(Reverse(Due {
+            at,
+            generation,
+            task,
+            wall_clock,
+        }));
+        first
+    }
+
+    /// The time according to the [Clock]. When the wall clock jumped ahead, the tasks which do not
+    ///   [follow the wall clock][Schedule::follows_wall_clock] are pushed back by as much, so that
+    ///   they keep to their intervals.
+    fn now(&mut self) -> Instant {
+        let skew = self.clock.skew;
+        let now = self.clock.now();
+        let jumped = self.clock.skew - skew;
+        if !jumped.is_zero() {
+            let due = std::mem::take(&mut self.due);
+            self.due = due
+                .into_iter()
+                .map(|Reverse(mut due)| {
+                    if !due.wall_clock {
+                        due.at = due.at.checked_add(jumped).unwrap_or(due.at);
+                    }
+                    Reverse(due)
+                })
+                .collect();
+        }
+        now
+    }
+}
+
+impl TimerQueue {
+    pub(super) fn insert(&self, task: TaskIdentifier, job: Box<dyn Job>, paused: bool) {
+        let initial = job.initial();
+        let mut state = self.state.lock().unwrap();
+        let slot = Slot {
+            paused,
+            finished: initial.is_none(),
+            ..Default::default()
+        };
+        state.slots.insert(task, slot);
+        match initial {
+            Some(initial) => {
+                state.slots.get_mut(&task).unwrap().job = Some(job);
+                self.queue(&mut state, task, initial);
+            }
+            None => self.finished(job),
+        }
+    }
+
+    pub(super) fn is_finished(&self, task: TaskIdentifier) -> bool {
+        let state = self.state.lock().unwrap();
+        state.slots.get(&task).is_none_or(|slot| slot.finished)
+    }
+
+    pub(super) fn is_paused(&self, task: TaskIdentifier) -> bool {
+        let state = self.state.lock().unwrap();
+        state.slots.get(&task).is_some_and(|slot| slot.paused)
+    }
+
+    pub(super) fn remove(&self, task: TaskIdentifier) {
+        let slot = self.state.lock().unwrap().slots.remove(&task);
+        if let Some(running) = slot.and_then(|slot| slot.running) {
+            running.abort();
+        }
+    }
+
+    pub(super) fn set_paused(&self, task: TaskIdentifier, paused: bool) {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        let now = state.now();
+        let Some(slot) = state.slots.get_mut(&task) else {
+            return;
+        };
+        slot.paused = paused;
+        if paused {
+            return;
+        }
+        let missed = slot.missed.take();
+        let Some(job) = &mut slot.job else {
+            return;
+        };
+        let delay = match missed {
+            _ if slot.pending => Duration::ZERO,
+            Some(missed) => {
+                let missed = now.saturating_duration_since(missed);
+                let Some(delay) = job.resumed(missed) else {
+                    slot.finished = true;
+                    if let Some(job) = slot.job.take() {
+                        self.finished(job);
+                    }
+                    return;
+                };
+                delay
+            }
+            None => return,
+        };
+        self.queue(state, task, delay);
+    }
+
+    pub(super) fn trigger(&self, task: TaskIdentifier) {
+        let mut state = self.state.lock().unwrap();
+        let Some(slot) = state.slots.get_mut(&task) else {
+            return;
+        };
+        match slot.job.is_some() && !slot.paused {
+            true => self.queue(&mut state, task, Duration::ZERO),
+            // runs once resumed, or straight after the current run
+            false => slot.pending = true,
+        }
+    }
+
+    /// Finishes a task whose schedule finished while the queue is locked, away from the lock.
+    fn finished(&self, job: Box<dyn Job>) {
+        self.runtime_handle.spawn(async move { job.finished() });
+    }
+
+    fn queue(&self, state: &mut State, task: TaskIdentifier, delay: Duration) {
+        if state.queue(task, delay) {
+            self.wake.notify_one();
+        }
+    }
+
+    /// Starts the runs of every task which is due. Returns how long until the next task is due,
+    ///   which is zero if there are more runs to start straight away.
+    fn dispatch(self: &Arc<Self>) -> Option<Duration> {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        let now = state.now();
+        let mut dispatched = 0;
+        while let Some(Reverse(due)) = state.due.peek() {
+            if due.at > now {
+                return Some(due.at - now);
+            }
+            if dispatched == DISPATCH_BATCH {
+                return Some(Duration::ZERO);
+            }
+            let Some(Reverse(due)) = state.due.pop() else {
+                break;
+            };
+            let Some(slot) = state.slots.get_mut(&due.task) else {
+                continue;
+            };
+            if slot.queued != Some(due.generation) {
+                continue;
+            }
+            slot.queued = None;
+            if slot.paused {
+                slot.missed.get_or_insert(due.at);
+                continue;
+            }
+            let Some(mut job) = slot.job.take() else {
+                continue;
+            };
+            slot.pending = false;
+            slot.missed = None;
+            let queue = self.clone();
+            let task = due.task;
+            let run = self.runtime_handle.spawn(async move {
+                let next = job.run().await;
+                queue.finish(task, job, next);
+            });
+            slot.running = Some(run.abort_handle());
+            dispatched += 1;
+        }
+        None
+    }
+
+    /// Hands a task back to the queue after a run.
+    fn finish(&self, task: TaskIdentifier, job: Box<dyn Job>, next: Option<Duration>) {
+        let mut state = self.state.lock().unwrap();
+        let Some(slot) = state.slots.get_mut(&task) else {
+            // cancelled while running
+            return;
+        };
+        slot.running = None;
+        let Some(next) = next else {
+            // marked before the task is reaped, so that the scheduler sees it finished
+            slot.finished = true;
+            drop(state);
+            job.finished();
+            return;
+        };
+        slot.job = Some(job);
+        let delay = match slot.pending {
+            true => Duration::ZERO,
+            false => next,
+        };
+        self.queue(&mut state, task, delay);
+    }
+}
+
+/// Starts the runs of tasks as they fall due, for as long as the scheduler is alive.
+async fn drive(queue: Arc<TimerQueue>) {
+    loop {
+        let mut until_due = queue.dispatch();
+        if until_due.is_some_and(|until| until.is_zero()) {
+            tokio::task::yield_now().await;
+            continue;
+        }
+        if queue.state.lock().unwrap().follows_wall_clock {
+            let until = until_due.unwrap_or(WALL_CLOCK_CHECK_INTERVAL);
+            until_due = Some(until.min(WALL_CLOCK_CHECK_INTERVAL));
+        }
+        match until_due {
+            Some(until) => {
+                unless(tokio::time::sleep(until), queue.wake.notified()).await;
+            }
+            None => queue.wake.notified().await,
+        }
+    }
+}
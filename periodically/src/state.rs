@@ -0,0 +1,58 @@
+use std::{io, sync::Arc, time::SystemTime};
+
+mod memory;
+pub use memory::MemoryStateStore;
+
+cfg_feature! {
+    "persistence",
+    mod file;
+    pub use file::FileStateStore;
+}
+
+/// The persisted state of a named task. Times are wall-clock times, so they remain
+///   meaningful across process restarts.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub struct TaskState {
+    /// When the task last finished running, if it has run at all.
+    pub last_run: Option<SystemTime>,
+    /// When the task is next due to run. `None` means the task has finished, and should not run again.
+    pub next_due: Option<SystemTime>,
+}
+
+/// Stores the [`TaskState`] of named tasks, so that their schedules survive process restarts.
+///
+/// See [`Persistent`][crate::Persistent] and [`Scheduler::with_state_store`][crate::Scheduler::with_state_store].
+pub trait StateStore: Send + Sync {
+    /// Loads the state last saved for the task `name`. Returns `None` if nothing was ever saved.
+    fn load(&self, name: &str) -> io::Result<Option<TaskState>>;
+
+    /// Saves the state of the task `name`, replacing anything previously saved.
+    ///
+    /// Called by [`Persistent`][crate::Persistent] after every run of the task, on the scheduler's runtime,
+    ///   so it must not block. Stores backed by slow storage should write in the background, like
+    ///   [`FileStateStore`][crate::FileStateStore] does.
+    fn save(&self, name: &str, state: TaskState) -> io::Result<()>;
+}
+
+impl<S: StateStore + ?Sized> StateStore for Arc<S> {
+    fn load(&self, name: &str) -> io::Result<Option<TaskState>> {
+        (**self).load(name)
+    }
+
+    fn save(&self, name: &str, state: TaskState) -> io::Result<()> {
+        (**self).save(name, state)
+    }
+}
+
+/// Decides what happens to runs which were missed while the process was not running, see [`Persistent`][crate::Persistent],
+///   or while the task was paused, see [`Scheduler::with_pause_catch_up`][crate::Scheduler::with_pause_catch_up].
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy, Default)]
+pub enum CatchUp {
+    /// Missed runs are dropped, and the task resumes its normal schedule.
+    Skip,
+    /// Missed runs are collapsed into a single immediate run.
+    #[default]
+    RunOnce,
+    /// Every missed run is executed back to back, until the task has caught up.
+    RunAll,
+}
@@ -0,0 +1,206 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Condvar, Mutex},
+    thread::{self, JoinHandle},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{StateStore, TaskState};
+
+/// Keeps task state in a JSON file, so that it survives process restarts.
+///
+/// The whole file is rewritten after a task's state is saved. Writes happen in the background, on a
+///   thread of the store's own, so that saving never blocks the scheduler. Saves made while a write is
+///   in progress are collapsed into the next write. [`flush`][FileStateStore::flush] waits for pending
+///   writes, and dropping the store flushes it.
+///
+/// Writes go to a temporary file first, which then replaces the original, so a crash while writing
+///   does not corrupt previously saved state.
+///
+/// ```
+/// use periodically::{FileStateStore, StateStore, TaskState};
+/// use std::time::{Duration, SystemTime, UNIX_EPOCH};
+///
+/// # let path = std::env::temp_dir().join(format!("periodically-doctest-{}.json", std::process::id()));
+/// let store = FileStateStore::open(&path).unwrap();
+/// let state = TaskState { last_run: Some(UNIX_EPOCH + Duration::from_secs(60)), next_due: None };
+/// store.save("my-task", state).unwrap();
+/// store.flush().unwrap();
+///
+/// let reopened = FileStateStore::open(&path).unwrap();
+/// assert_eq!(reopened.load("my-task").unwrap(), Some(state));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct FileStateStore {
+    shared: Arc<Shared>,
+    writer: Option<JoinHandle<()>>,
+}
+
+/// The part of a [FileStateStore] shared with its writer thread.
+#[derive(Debug)]
+struct Shared {
+    path: PathBuf,
+    contents: Mutex<Contents>,
+    /// Notified whenever the contents are saved or written, and when the store is dropped.
+    changed: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct Contents {
+    states: BTreeMap<String, StoredState>,
+    /// Bumped on every save.
+    version: u64,
+    /// The version last written to the file.
+    written: u64,
+    /// The error of the latest failed write, until [flushed][FileStateStore::flush].
+    error: Option<io::Error>,
+    closing: bool,
+}
+
+impl FileStateStore {
+    /// Opens the store at `path`, loading any state it already contains. The file is
+    ///   created the first time a task's state is saved.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_owned();
+        let states = match fs::read(&path) {
+            Ok(contents) => serde_json::from_slice(&contents)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err),
+        };
+        let shared = Arc::new(Shared {
+            path,
+            contents: Mutex::new(Contents {
+                states,
+                ..Default::default()
+            }),
+            changed: Condvar::new(),
+        });
+        let writer = thread::Builder::new()
+            .name("periodically-state-store".to_string())
+            .spawn({
+                let shared = shared.clone();
+                move || shared.write_behind()
+            })?;
+        Ok(Self {
+            shared,
+            writer: Some(writer),
+        })
+    }
+
+    /// Waits until every saved state has been written to the file.
+    ///
+    /// Returns the error of the latest write which failed since the store was last flushed, if any.
+    pub fn flush(&self) -> io::Result<()> {
+        let contents = self.shared.contents.lock().unwrap();
+        let mut contents = self
+            .shared
+            .changed
+            .wait_while(contents, |contents| contents.written != contents.version)
+            .unwrap();
+        contents.error.take().map_or(Ok(()), Err)
+    }
+}
+
+impl Shared {
+    /// Writes the contents to the file whenever they change, until the store is dropped.
+    fn write_behind(&self) {
+        let mut contents = self.contents.lock().unwrap();
+        loop {
+            contents = self
+                .changed
+                .wait_while(contents, |contents| {
+                    contents.written == contents.version && !contents.closing
+                })
+                .unwrap();
+            if contents.written == contents.version {
+                // closing, and nothing left to write
+                return;
+            }
+            let version = contents.version;
+            let serialized = serde_json::to_vec_pretty(&contents.states);
+            drop(contents);
+            let result = serialized
+                .map_err(io::Error::from)
+                .and_then(|serialized| self.write(&serialized));
+            contents = self.contents.lock().unwrap();
+            contents.written = version;
+            if let Err(err) = result {
+                #[cfg(feature = "log")]
+                log::warn!(
+                    "Cannot write task state to [{}]: {err}",
+                    self.path.display()
+                );
+                contents.error = Some(err);
+            }
+            self.changed.notify_all();
+        }
+    }
+
+    fn write(&self, serialized: &[u8]) -> io::Result<()> {
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        fs::write(&temporary, serialized)?;
+        fs::rename(&temporary, &self.path)
+    }
+}
+
+impl Drop for FileStateStore {
+    fn drop(&mut self) {
+        self.shared.contents.lock().unwrap().closing = true;
+        self.shared.changed.notify_all();
+        if let Some(writer) = self.writer.take() {
+            let _ = writer.join();
+        }
+    }
+}
+
+impl StateStore for FileStateStore {
+    fn load(&self, name: &str) -> io::Result<Option<TaskState>> {
+        let contents = self.shared.contents.lock().unwrap();
+        Ok(contents.states.get(name).map(TaskState::from))
+    }
+
+    /// Never fails, since the state is written in the background. See [`flush`][FileStateStore::flush].
+    fn save(&self, name: &str, state: TaskState) -> io::Result<()> {
+        let mut contents = self.shared.contents.lock().unwrap();
+        contents.states.insert(name.to_owned(), state.into());
+        contents.version += 1;
+        self.shared.changed.notify_all();
+        Ok(())
+    }
+}
+
+/// The on-disk format of a [TaskState], with times as milliseconds since the unix epoch.
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredState {
+    last_run_ms: Option<u64>,
+    next_due_ms: Option<u64>,
+}
+
+impl From<&StoredState> for TaskState {
+    fn from(stored: &StoredState) -> Self {
+        let from_millis = |millis| UNIX_EPOCH + Duration::from_millis(millis);
+        TaskState {
+            last_run: stored.last_run_ms.map(from_millis),
+            next_due: stored.next_due_ms.map(from_millis),
+        }
+    }
+}
+
+impl From<TaskState> for StoredState {
+    fn from(state: TaskState) -> Self {
+        let to_millis = |time: SystemTime| {
+            let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+            since_epoch.as_millis().try_into().unwrap_or(u64::MAX)
+        };
+        StoredState {
+            last_run_ms: state.last_run.map(to_millis),
+            next_due_ms: state.next_due.map(to_millis),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::{collections::HashMap, io, sync::Mutex};
+
+use super::{StateStore, TaskState};
+
+/// Keeps task state in memory.
+///
+/// Does not survive a process restart, but can be shared between schedulers in the same
+///   process, and is useful for testing.
+///
+/// ```
+/// use periodically::{MemoryStateStore, StateStore, TaskState};
+/// use std::time::SystemTime;
+///
+/// let store = MemoryStateStore::default();
+/// let state = TaskState { last_run: Some(SystemTime::now()), next_due: None };
+/// store.save("my-task", state).unwrap();
+/// assert_eq!(store.load("my-task").unwrap(), Some(state));
+/// assert_eq!(store.load("other-task").unwrap(), None);
+/// ```
+#[derive(Debug, Default)]
+pub struct MemoryStateStore {
+    states: Mutex<HashMap<String, TaskState>>,
+}
+
+impl StateStore for MemoryStateStore {
+    fn load(&self, name: &str) -> io::Result<Option<TaskState>> {
+        Ok(self.states.lock().unwrap().get(name).copied())
+    }
+
+    fn save(&self, name: &str, state: TaskState) -> io::Result<()> {
+        self.states.lock().unwrap().insert(name.to_owned(), state);
+        Ok(())
+    }
+}
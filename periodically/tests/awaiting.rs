@@ -0,0 +1,89 @@
+pub mod tasks;
+
+use periodically::{AsyncTask, Awaitable, IntervalSchedule, OneShot, Scheduler, Task, TaskOutcome};
+use std::time::Duration;
+use tasks::PanickingTask;
+use tokio::runtime::Runtime;
+
+struct Answer;
+
+impl Task<usize> for Answer {
+    fn run(&self) -> usize {
+        42
+    }
+}
+
+impl AsyncTask<usize> for Answer {
+    async fn run(&self) -> usize {
+        42
+    }
+}
+
+/// Never finishes a run within a test.
+struct Stuck;
+
+impl AsyncTask<usize> for Stuck {
+    async fn run(&self) -> usize {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        42
+    }
+}
+
+#[test]
+fn waiting_returns_the_last_output() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let (schedule, completion) = Awaitable::new(OneShot::after(Duration::ZERO));
+    scheduler.add_sync_task(Answer, schedule).unwrap();
+
+    assert_eq!(completion.wait(), TaskOutcome::Finished(Some(42)));
+}
+
+#[test]
+fn completions_can_be_awaited() {
+    let runtime = Runtime::new().unwrap();
+    let mut scheduler = Scheduler::tokio_scheduler_with_handle(runtime.handle().clone());
+    let (schedule, completion) = Awaitable::new(OneShot::after(Duration::from_millis(10)));
+    scheduler.add_async_task(Answer, schedule).unwrap();
+
+    let outcome = runtime.block_on(completion);
+    assert_eq!(outcome, TaskOutcome::Finished(Some(42)));
+}
+
+#[test]
+fn panicking_tasks_are_reported() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let (schedule, completion) = Awaitable::new(OneShot::after(Duration::ZERO));
+    scheduler
+        .add_sync_task(PanickingTask::with_modulo(1), schedule)
+        .unwrap();
+
+    assert_eq!(
+        completion.wait(),
+        TaskOutcome::Panicked(Some("Task panicked!".to_string()))
+    );
+}
+
+#[test]
+fn cancelled_tasks_are_reported() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let (schedule, completion) = Awaitable::new(IntervalSchedule::every(Duration::MAX));
+    let id = scheduler.add_sync_task(Answer, schedule).unwrap();
+    assert!(!completion.is_finished());
+
+    scheduler.cancel_task(id).unwrap();
+    assert_eq!(completion.wait(), TaskOutcome::<usize>::Cancelled);
+}
+
+#[test]
+fn timed_out_tasks_are_reported_without_a_message() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let (schedule, completion) = Awaitable::new(OneShot::after(Duration::ZERO));
+    scheduler
+        .async_task(Stuck)
+        .schedule(schedule)
+        .timeout(Duration::from_millis(10))
+        .register()
+        .unwrap();
+
+    assert_eq!(completion.wait(), TaskOutcome::Panicked(None));
+}
@@ -0,0 +1,200 @@
+pub mod tasks;
+
+use periodically::{
+    AsyncTask, Error, IntervalSchedule, OneShot, Overlap, Scheduler, Task, TaskFailure,
+};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread::sleep,
+    time::Duration,
+};
+use tasks::SpyingTask;
+use tokio::runtime::Runtime;
+
+struct Stuck;
+
+impl AsyncTask for Stuck {
+    async fn run(&self) {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+    }
+}
+
+#[test]
+fn builder_registers_sync_and_async_tasks() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let sync_task = SpyingTask::default();
+    let sync_counter = sync_task.counter();
+    let async_task = SpyingTask::default();
+    let async_counter = async_task.counter();
+
+    scheduler
+        .task(sync_task)
+        .schedule(OneShot::after(Duration::ZERO))
+        .register()
+        .unwrap();
+    scheduler
+        .async_task(async_task)
+        .schedule(OneShot::after(Duration::ZERO))
+        .register()
+        .unwrap();
+
+    sleep(Duration::from_millis(100));
+    assert_eq!(sync_counter.load(Ordering::Relaxed), 1);
+    assert_eq!(async_counter.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn builder_names_are_shared_with_named_tasks() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    scheduler
+        .task(SpyingTask::default())
+        .schedule(IntervalSchedule::every(Duration::MAX))
+        .name("report")
+        .register()
+        .unwrap();
+
+    let duplicate = scheduler.add_named_sync_task(
+        "report",
+        SpyingTask::default(),
+        IntervalSchedule::every(Duration::MAX),
+    );
+    assert!(matches!(duplicate, Err(Error::DuplicateName(name)) if name == "report"));
+}
+
+#[test]
+fn invalid_options_are_rejected() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let zero_timeout = scheduler
+        .task(SpyingTask::default())
+        .schedule(OneShot::after(Duration::ZERO))
+        .timeout(Duration::ZERO)
+        .register();
+    assert!(matches!(zero_timeout, Err(Error::InvalidOptions(_))));
+
+    let empty_name = scheduler
+        .async_task(SpyingTask::default())
+        .schedule(OneShot::after(Duration::ZERO))
+        .name("")
+        .register();
+    assert!(matches!(empty_name, Err(Error::InvalidOptions(_))));
+
+    let zero_jitter = scheduler
+        .task(SpyingTask::default())
+        .schedule(OneShot::after(Duration::ZERO))
+        .jitter(Duration::ZERO)
+        .register();
+    assert!(matches!(zero_jitter, Err(Error::InvalidOptions(_))));
+
+    let overlap_without_timeout = scheduler
+        .task(SpyingTask::default())
+        .schedule(OneShot::after(Duration::ZERO))
+        .overlap(Overlap::Wait)
+        .register();
+    assert!(matches!(
+        overlap_without_timeout,
+        Err(Error::InvalidOptions(_))
+    ));
+}
+
+#[test]
+fn paused_tasks_run_once_resumed() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let task = SpyingTask::default();
+    let counter = task.counter();
+    let id = scheduler
+        .task(task)
+        .schedule(IntervalSchedule::every(Duration::from_millis(10)))
+        .start_paused()
+        .register()
+        .unwrap();
+
+    sleep(Duration::from_millis(100));
+    assert_eq!(counter.load(Ordering::Relaxed), 0);
+
+    scheduler.resume_task(id).unwrap();
+    sleep(Duration::from_millis(100));
+    assert!(counter.load(Ordering::Relaxed) >= 1);
+
+    scheduler.pause_task(id).unwrap();
+    sleep(Duration::from_millis(50));
+    let paused_at = counter.load(Ordering::Relaxed);
+    sleep(Duration::from_millis(100));
+    assert_eq!(counter.load(Ordering::Relaxed), paused_at);
+}
+
+#[test]
+fn slow_runs_time_out() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let id = scheduler
+        .async_task(Stuck)
+        .schedule(IntervalSchedule::every(Duration::from_millis(10)))
+        .timeout(Duration::from_millis(20))
+        .register()
+        .unwrap();
+
+    let mut outputs = scheduler.subscribe::<()>(id).unwrap();
+    assert_eq!(
+        outputs.blocking_recv().unwrap(),
+        Err(TaskFailure::TimedOut(Duration::from_millis(20)))
+    );
+}
+
+#[test]
+fn jitter_delays_runs_within_bounds() {
+    for mut scheduler in [
+        Scheduler::tokio_scheduler(Runtime::new().unwrap()),
+        Scheduler::tokio_scheduler(Runtime::new().unwrap()).with_timer_driver(),
+    ] {
+        let task = SpyingTask::default();
+        let counter = task.counter();
+        scheduler
+            .task(task)
+            .schedule(IntervalSchedule::every(Duration::from_millis(10)))
+            .jitter(Duration::from_millis(40))
+            .register()
+            .unwrap();
+
+        // every run is due 10 to 50ms after the previous one, rather than after 10ms
+        sleep(Duration::from_millis(400));
+        let runs = counter.load(Ordering::Relaxed);
+        assert!((4..=25).contains(&runs), "{runs} runs");
+    }
+}
+
+/// Sleeps through its runs, and records how many of them ran at once.
+#[derive(Clone, Default)]
+struct Sleepy {
+    running: Arc<AtomicUsize>,
+    most_running: Arc<AtomicUsize>,
+}
+
+impl Task for Sleepy {
+    fn run(&self) {
+        let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+        self.most_running.fetch_max(running, Ordering::SeqCst);
+        sleep(Duration::from_millis(50));
+        self.running.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn timed_out_runs_overlap_unless_told_to_wait() {
+    for (overlap, overlapped) in [(Overlap::Allow, true), (Overlap::Wait, false)] {
+        let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+        let task = Sleepy::default();
+        let most_running = task.most_running.clone();
+        scheduler
+            .task(task)
+            .schedule(IntervalSchedule::every(Duration::from_millis(5)))
+            .timeout(Duration::from_millis(10))
+            .overlap(overlap)
+            .register()
+            .unwrap();
+
+        sleep(Duration::from_millis(300));
+        assert_eq!(most_running.load(Ordering::SeqCst) > 1, overlapped);
+    }
+}
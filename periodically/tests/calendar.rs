@@ -0,0 +1,146 @@
+#![cfg(feature = "calendar")]
+
+use chrono::{
+    FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday,
+};
+use periodically::{CalendarSchedule, HolidayCalendar, HolidayCalendarError};
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+/// A timezone which moves its clocks forward from 01:00 to 02:00 on 2024-03-31, like Europe/London.
+#[derive(Debug, Clone, Copy)]
+struct SpringForward;
+
+impl SpringForward {
+    const WINTER: i32 = 0;
+    const SUMMER: i32 = 60 * 60;
+
+    fn transition() -> NaiveDateTime {
+        date(2024, 3, 31).and_hms_opt(1, 0, 0).unwrap()
+    }
+}
+
+impl TimeZone for SpringForward {
+    type Offset = FixedOffset;
+
+    fn from_offset(_: &FixedOffset) -> Self {
+        SpringForward
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+        self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+        let skipped = Self::transition()..Self::transition() + chrono::TimeDelta::hours(1);
+        match *local {
+            local if local < skipped.start => {
+                LocalResult::Single(self.offset_from_utc_datetime(&local))
+            }
+            local if skipped.contains(&local) => LocalResult::None,
+            _ => LocalResult::Single(FixedOffset::east_opt(Self::SUMMER).unwrap()),
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+        self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+        let offset = match *utc < Self::transition() {
+            true => Self::WINTER,
+            false => Self::SUMMER,
+        };
+        FixedOffset::east_opt(offset).unwrap()
+    }
+}
+
+#[test]
+fn last_business_day_skips_weekends_and_holidays() {
+    // 2024-06-30 is a Sunday, and the Friday before is a holiday
+    let holidays = HolidayCalendar::from_dates([date(2024, 6, 28)]);
+    let schedule = CalendarSchedule::builder(Utc)
+        .on_last_business_day_of_month()
+        .excluding_calendar(&holidays)
+        .build();
+
+    let after = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+    let next = schedule.next_after(&after).unwrap();
+    assert_eq!(next.date_naive(), date(2024, 6, 27));
+    let next = schedule.next_after(&next).unwrap();
+    assert_eq!(next.date_naive(), date(2024, 7, 31));
+}
+
+#[test]
+fn days_of_month_skip_short_months() {
+    let schedule = CalendarSchedule::builder(Utc)
+        .on_days_of_month([31])
+        .build();
+
+    let after = Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+    let next = schedule.next_after(&after).unwrap();
+    assert_eq!(next.date_naive(), date(2024, 3, 31));
+}
+
+#[test]
+fn last_weekday_of_month() {
+    let schedule = CalendarSchedule::builder(Utc)
+        .on_last_weekday(Weekday::Fri)
+        .build();
+
+    let after = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+    let next = schedule.next_after(&after).unwrap();
+    assert_eq!(next.date_naive(), date(2024, 5, 31));
+}
+
+#[test]
+fn times_are_observed_in_the_schedules_timezone() {
+    let sydney = FixedOffset::east_opt(10 * 60 * 60).unwrap();
+    let schedule = CalendarSchedule::builder(sydney)
+        .at(NaiveTime::from_hms_opt(9, 0, 0).unwrap())
+        .on_weekdays([Weekday::Mon])
+        .build();
+
+    // 2024-06-02 20:00 UTC is already Monday 06:00 in UTC+10
+    let after = sydney.from_utc_datetime(&date(2024, 6, 2).and_hms_opt(20, 0, 0).unwrap());
+    let next = schedule.next_after(&after).unwrap();
+    assert_eq!(
+        next.with_timezone(&Utc),
+        Utc.with_ymd_and_hms(2024, 6, 2, 23, 0, 0).unwrap()
+    );
+}
+
+#[test]
+fn invalid_list_reports_line() {
+    let err = HolidayCalendar::from_list("2024-01-01\n2024-13-01").unwrap_err();
+    assert!(matches!(err, HolidayCalendarError::Parse { line: 2, .. }));
+}
+
+#[test]
+fn times_skipped_by_daylight_saving_run_after_the_gap() {
+    let schedule = CalendarSchedule::builder(SpringForward)
+        .at(NaiveTime::from_hms_opt(1, 30, 0).unwrap())
+        .build();
+    let after = SpringForward
+        .with_ymd_and_hms(2024, 3, 31, 0, 0, 0)
+        .unwrap();
+
+    let next = schedule.next_after(&after).unwrap();
+    assert_eq!(
+        next.naive_local(),
+        date(2024, 3, 31).and_hms_opt(2, 0, 0).unwrap()
+    );
+}
+
+#[test]
+fn events_without_a_start_are_reported() {
+    let ical = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:Unknown\nEND:VEVENT\nEND:VCALENDAR";
+    let err = HolidayCalendar::from_ical(ical).unwrap_err();
+    assert!(matches!(
+        err,
+        HolidayCalendarError::MissingStart { line: 4 }
+    ));
+    assert_eq!(err.to_string(), "event ending on line 4 has no DTSTART");
+}
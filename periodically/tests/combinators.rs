@@ -0,0 +1,70 @@
+pub mod tasks;
+
+use periodically::{AlignedInterval, IntervalSchedule, OneShot, Schedule, Scheduler};
+use std::{
+    sync::atomic::Ordering,
+    thread::sleep,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tasks::SpyingTask;
+use tokio::runtime::Runtime;
+
+#[test]
+fn then_hands_over_to_next_schedule() {
+    let task = SpyingTask::default();
+    let counter = task.counter();
+    let schedule =
+        OneShot::after(Duration::ZERO).then(IntervalSchedule::every(Duration::from_millis(40)));
+
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    scheduler.add_sync_task(task, schedule).unwrap();
+
+    sleep(Duration::from_millis(20));
+    assert_eq!(counter.load(Ordering::Acquire), 1);
+    sleep(Duration::from_millis(100));
+    assert!(counter.load(Ordering::Acquire) >= 2);
+}
+
+#[test]
+fn or_keeps_each_schedules_timeline() {
+    let schedule = IntervalSchedule::every(Duration::from_millis(100))
+        .or(IntervalSchedule::every(Duration::from_millis(130)));
+
+    let runs: Vec<_> = schedule
+        .preview(UNIX_EPOCH, || ())
+        .take(3)
+        .map(|at| at.duration_since(UNIX_EPOCH).unwrap())
+        .collect();
+    // the slower schedule is still due 130ms after starting, and the faster one was reset at 100ms
+    assert_eq!(runs, [100, 130, 200].map(Duration::from_millis));
+}
+
+#[test]
+fn one_shots_at_a_time_follow_the_wall_clock() {
+    let at = OneShot::at(SystemTime::now() + Duration::from_secs(60));
+    assert!(Schedule::<()>::follows_wall_clock(&at));
+    let after = OneShot::after(Duration::from_secs(60));
+    assert!(!Schedule::<()>::follows_wall_clock(&after));
+}
+
+#[test]
+fn combinators_follow_the_wall_clock_of_their_schedules() {
+    let interval = || IntervalSchedule::every(Duration::from_secs(60));
+    let aligned = || AlignedInterval::every(Duration::from_secs(60));
+
+    assert!(!Schedule::<()>::follows_wall_clock(&interval()));
+    assert!(Schedule::<()>::follows_wall_clock(&aligned()));
+    assert!(Schedule::<()>::follows_wall_clock(
+        &interval().or(aligned())
+    ));
+    assert!(!Schedule::<()>::follows_wall_clock(
+        &interval().for_duration(Duration::from_secs(60))
+    ));
+
+    // only the schedule in use counts
+    let schedule = OneShot::after(Duration::ZERO).then(aligned());
+    assert!(!schedule.follows_wall_clock());
+    schedule.initial();
+    schedule.next(());
+    assert!(schedule.follows_wall_clock());
+}
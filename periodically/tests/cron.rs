@@ -0,0 +1,136 @@
+#![cfg(feature = "cron")]
+
+use chrono::{DateTime, TimeZone, Utc};
+use periodically::{CronError, CronExpression, CronField, CronSchedule, Misfire, Schedule};
+use std::{thread::sleep, time::Duration};
+
+const EVERY_SECOND: &str = "* * * * * *";
+
+fn misfiring(misfire: Misfire) -> Box<dyn Schedule<()>> {
+    let schedule = CronSchedule::from_cron_str(EVERY_SECOND)
+        .unwrap()
+        .with_misfire_policy(misfire);
+    let schedule = Box::new(schedule) as Box<dyn Schedule<()>>;
+    sleep(schedule.initial().unwrap());
+    // the task "runs" for long enough that at least two more slots pass
+    sleep(Duration::from_millis(2500));
+    schedule
+}
+
+#[test]
+fn skip_waits_for_upcoming_slot() {
+    let schedule = misfiring(Misfire::Skip);
+    assert!(schedule.next(()).unwrap() > Duration::ZERO);
+}
+
+#[test]
+fn fire_once_collapses_misfired_slots() {
+    let schedule = misfiring(Misfire::FireOnce);
+    assert_eq!(schedule.next(()), Some(Duration::ZERO));
+    assert!(schedule.next(()).unwrap() > Duration::ZERO);
+}
+
+#[test]
+fn fire_all_runs_every_misfired_slot() {
+    let schedule = misfiring(Misfire::FireAll);
+    assert_eq!(schedule.next(()), Some(Duration::ZERO));
+    assert_eq!(schedule.next(()), Some(Duration::ZERO));
+}
+
+fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+        .unwrap()
+}
+
+/// The next `count` times `expression` fires, starting from the 1st of January 2024.
+fn upcoming(expression: &str, count: usize) -> Vec<DateTime<Utc>> {
+    let expression: CronExpression = expression.parse().unwrap();
+    std::iter::successors(Some(at(2024, 1, 1, 0, 0)), |after| {
+        expression.next_after(after)
+    })
+    .skip(1)
+    .take(count)
+    .collect()
+}
+
+#[test]
+fn parses_crontab_lines() {
+    assert_eq!(
+        upcoming("*/20 9 * * MON-FRI", 4),
+        vec![
+            at(2024, 1, 1, 9, 0),
+            at(2024, 1, 1, 9, 20),
+            at(2024, 1, 1, 9, 40),
+            at(2024, 1, 2, 9, 0),
+        ]
+    );
+    // both day fields are restricted, so either one matches
+    assert_eq!(
+        upcoming("0 12 13 * 5", 3),
+        vec![
+            at(2024, 1, 5, 12, 0),
+            at(2024, 1, 12, 12, 0),
+            at(2024, 1, 13, 12, 0)
+        ]
+    );
+    // sunday is both 0 and 7
+    assert_eq!(upcoming("0 0 * * 0", 1), upcoming("0 0 * * 7", 1));
+}
+
+#[test]
+fn parses_macros() {
+    assert_eq!(upcoming("@hourly", 1), vec![at(2024, 1, 1, 1, 0)]);
+    assert_eq!(upcoming("@weekly", 1), vec![at(2024, 1, 7, 0, 0)]);
+    assert_eq!(upcoming("@MONTHLY", 1), vec![at(2024, 2, 1, 0, 0)]);
+
+    let reboot = CronSchedule::from_cron_str("@reboot").unwrap();
+    let reboot = Box::new(reboot) as Box<dyn Schedule<()>>;
+    assert_eq!(reboot.initial(), Some(Duration::ZERO));
+    assert_eq!(reboot.next(()), None);
+}
+
+#[test]
+fn parses_day_extensions() {
+    assert_eq!(
+        upcoming("0 0 L * *", 2),
+        vec![at(2024, 1, 31, 0, 0), at(2024, 2, 29, 0, 0)]
+    );
+    assert_eq!(upcoming("0 0 L-2 2 *", 1), vec![at(2024, 2, 27, 0, 0)]);
+    // the 1st of June 2024 is a saturday, and the 30th a sunday
+    assert_eq!(upcoming("0 0 1W 6 *", 1), vec![at(2024, 6, 3, 0, 0)]);
+    assert_eq!(upcoming("0 0 LW 6 *", 1), vec![at(2024, 6, 28, 0, 0)]);
+    assert_eq!(
+        upcoming("0 0 * * MON#2", 2),
+        vec![at(2024, 1, 8, 0, 0), at(2024, 2, 12, 0, 0)]
+    );
+    assert_eq!(upcoming("0 0 * * 5L", 1), vec![at(2024, 1, 26, 0, 0)]);
+    // with a seconds field, sunday is 1
+    assert_eq!(upcoming("0 0 0 * * 6L", 1), vec![at(2024, 1, 26, 0, 0)]);
+}
+
+#[test]
+fn explains_which_field_is_invalid() {
+    let error = |expression: &str| expression.parse::<CronExpression>().unwrap_err();
+
+    assert_eq!(error("* * *"), CronError::FieldCount(3));
+    assert!(matches!(error("@fortnightly"), CronError::UnknownMacro(_)));
+    assert!(matches!(
+        error("0 25 * * *"),
+        CronError::InvalidField {
+            field: CronField::Hours,
+            ..
+        }
+    ));
+    assert_eq!(
+        error("0 0 * FOO *").to_string(),
+        "invalid month field [FOO]: [FOO] is not a number or name"
+    );
+    assert_eq!(
+        error("0 0 * * 5-1").to_string(),
+        "invalid day of week field [5-1]: the range starts after it ends"
+    );
+    assert_eq!(
+        error("*/0 * * * *").to_string(),
+        "invalid minutes field [*/0]: the step must be a positive number"
+    );
+}
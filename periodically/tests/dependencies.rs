@@ -0,0 +1,257 @@
+pub mod tasks;
+
+use periodically::{
+    Dependency, DependencyError, Error, IntervalSchedule, OneShot, Piped, PipedOutput, Scheduler,
+    Task, TaskStatus,
+};
+use std::{
+    sync::{atomic::Ordering, mpsc, Arc, Mutex},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+use tasks::{PanickingTask, SpyingTask};
+use tokio::runtime::Runtime;
+
+#[test]
+fn dependent_task_runs_after_upstream() {
+    let upstream = SpyingTask::default();
+    let upstream_counter = upstream.counter();
+    let dependent = SpyingTask::default();
+    let dependent_counter = dependent.counter();
+
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let upstream_id = scheduler
+        .add_sync_task(upstream, IntervalSchedule::every(Duration::from_millis(10)))
+        .unwrap();
+    scheduler
+        .add_sync_task_after(
+            dependent,
+            IntervalSchedule::every(Duration::ZERO),
+            [Dependency::on_success(upstream_id)],
+        )
+        .unwrap();
+
+    sleep(Duration::from_millis(100));
+    scheduler.cancel_task(upstream_id).unwrap();
+    sleep(Duration::from_millis(20));
+
+    let upstream_runs = upstream_counter.load(Ordering::Acquire);
+    let dependent_runs = dependent_counter.load(Ordering::Acquire);
+    assert!(dependent_runs > 0);
+    assert!(dependent_runs <= upstream_runs);
+}
+
+#[test]
+fn dependent_task_waits_for_every_dependency() {
+    let a = SpyingTask::default();
+    let b = SpyingTask::default();
+    let c = SpyingTask::default();
+    let c_counter = c.counter();
+
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let a = scheduler
+        .add_async_task(a, IntervalSchedule::every(Duration::from_millis(5)))
+        .unwrap();
+    let b = scheduler
+        .add_async_task(b, OneShot::after(Duration::from_millis(50)))
+        .unwrap();
+    scheduler
+        .add_async_task_after(
+            c,
+            IntervalSchedule::every(Duration::ZERO),
+            [Dependency::on_success(a), Dependency::on_success(b)],
+        )
+        .unwrap();
+
+    sleep(Duration::from_millis(30));
+    assert_eq!(c_counter.load(Ordering::Acquire), 0);
+    // once b finished, c only waits on a
+    sleep(Duration::from_millis(70));
+    assert!(c_counter.load(Ordering::Acquire) > 1);
+}
+
+#[test]
+fn failure_triggers_only_failure_dependents() {
+    let upstream = PanickingTask::with_modulo(1);
+    let on_success = SpyingTask::default();
+    let on_success_counter = on_success.counter();
+    let on_failure = SpyingTask::default();
+    let on_failure_counter = on_failure.counter();
+
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let upstream = scheduler
+        .add_sync_task(upstream, OneShot::after(Duration::from_millis(10)))
+        .unwrap();
+    scheduler
+        .add_sync_task_after(
+            on_success,
+            IntervalSchedule::every(Duration::ZERO),
+            [Dependency::on_success(upstream)],
+        )
+        .unwrap();
+    scheduler
+        .add_sync_task_after(
+            on_failure,
+            IntervalSchedule::every(Duration::ZERO),
+            [Dependency::on_failure(upstream)],
+        )
+        .unwrap();
+
+    // panics are slow to report with backtraces enabled, so wait on the run rather than the clock
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while on_failure_counter.load(Ordering::Acquire) == 0 && Instant::now() < deadline {
+        sleep(Duration::from_millis(10));
+    }
+    sleep(Duration::from_millis(50));
+    assert_eq!(on_success_counter.load(Ordering::Acquire), 0);
+    assert_eq!(on_failure_counter.load(Ordering::Acquire), 1);
+}
+
+struct Producer;
+
+impl Task<usize> for Producer {
+    fn run(&self) -> usize {
+        42
+    }
+}
+
+struct Consumer {
+    input: PipedOutput<usize>,
+    received: Arc<Mutex<Vec<usize>>>,
+}
+
+impl Task for Consumer {
+    fn run(&self) {
+        if let Some(input) = self.input.take() {
+            self.received.lock().unwrap().push(input);
+        }
+    }
+}
+
+#[test]
+fn piped_output_is_passed_to_dependent() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let (schedule, input) = Piped::new(OneShot::after(Duration::from_millis(10)));
+    let consumer = Consumer {
+        input,
+        received: received.clone(),
+    };
+
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let producer = scheduler.add_sync_task(Producer, schedule).unwrap();
+    scheduler
+        .add_sync_task_after(
+            consumer,
+            IntervalSchedule::every(Duration::ZERO),
+            [Dependency::on_success(producer)],
+        )
+        .unwrap();
+
+    sleep(Duration::from_millis(100));
+    assert_eq!(*received.lock().unwrap(), vec![42]);
+}
+
+#[test]
+fn invalid_dependencies_are_rejected() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let timer = scheduler
+        .add_sync_task(SpyingTask::default(), OneShot::after(Duration::MAX))
+        .unwrap();
+    let other = scheduler
+        .add_sync_task(SpyingTask::default(), OneShot::after(Duration::MAX))
+        .unwrap();
+    let b = scheduler
+        .add_sync_task_after(
+            SpyingTask::default(),
+            IntervalSchedule::every(Duration::ZERO),
+            [Dependency::on_success(timer)],
+        )
+        .unwrap();
+    let c = scheduler
+        .add_sync_task_after(
+            SpyingTask::default(),
+            IntervalSchedule::every(Duration::ZERO),
+            [Dependency::on_success(b)],
+        )
+        .unwrap();
+
+    assert!(matches!(
+        scheduler.add_dependency(b, Dependency::on_success(c)),
+        Err(Error::Dependency(DependencyError::Cycle(cycle))) if cycle == vec![b, c, b]
+    ));
+    assert!(matches!(
+        scheduler.add_dependency(timer, Dependency::on_success(other)),
+        Err(Error::Dependency(DependencyError::NotDependent(id))) if id == timer
+    ));
+
+    scheduler.cancel_task(c).unwrap();
+    assert!(matches!(
+        scheduler.add_dependency(b, Dependency::on_success(c)),
+        Err(Error::Dependency(DependencyError::UnknownTask(id))) if id == c
+    ));
+    assert!(matches!(
+        scheduler.add_sync_task_after(
+            SpyingTask::default(),
+            IntervalSchedule::every(Duration::ZERO),
+            Vec::new(),
+        ),
+        Err(Error::Dependency(DependencyError::NoDependencies(_)))
+    ));
+}
+
+#[test]
+fn dependents_finish_once_their_upstreams_are_gone() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let first = scheduler
+        .add_sync_task(SpyingTask::default(), OneShot::after(Duration::MAX))
+        .unwrap();
+    let second = scheduler
+        .add_sync_task(SpyingTask::default(), OneShot::after(Duration::MAX))
+        .unwrap();
+    let (sender, finished) = mpsc::channel();
+    let dependent = scheduler
+        .task(SpyingTask::default())
+        .schedule(IntervalSchedule::every(Duration::ZERO))
+        .after([
+            Dependency::on_success(first),
+            Dependency::on_success(second),
+        ])
+        .on_finish(move |id, status| sender.send((id, status)).unwrap())
+        .register()
+        .unwrap();
+
+    scheduler.cancel_task(first).unwrap();
+    assert!(finished.recv_timeout(Duration::from_millis(50)).is_err());
+    assert_eq!(
+        scheduler.task_status(dependent).unwrap(),
+        TaskStatus::Scheduled
+    );
+
+    scheduler.cancel_task(second).unwrap();
+    let (finished_id, status) = finished.recv_timeout(Duration::from_secs(1)).unwrap();
+    assert_eq!(finished_id, dependent);
+    assert_eq!(status, TaskStatus::Finished);
+}
+
+#[test]
+fn dependents_of_finished_tasks_finish_after_their_last_trigger() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let upstream = scheduler
+        .add_sync_task(SpyingTask::default(), OneShot::after(Duration::ZERO))
+        .unwrap();
+    let dependent = SpyingTask::default();
+    let dependent_counter = dependent.counter();
+    let (sender, finished) = mpsc::channel();
+    let dependent = scheduler
+        .task(dependent)
+        .schedule(IntervalSchedule::every(Duration::ZERO))
+        .after([Dependency::on_success(upstream)])
+        .on_finish(move |id, status| sender.send((id, status)).unwrap())
+        .register()
+        .unwrap();
+
+    let (finished_id, status) = finished.recv_timeout(Duration::from_secs(1)).unwrap();
+    assert_eq!(finished_id, dependent);
+    assert_eq!(status, TaskStatus::Finished);
+    assert_eq!(dependent_counter.load(Ordering::Acquire), 1);
+}
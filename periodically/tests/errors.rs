@@ -0,0 +1,63 @@
+pub mod tasks;
+
+use periodically::{Error, IntervalSchedule, OneShot, Scheduler};
+use std::{sync::atomic::Ordering, time::Duration};
+use tasks::SpyingTask;
+use tokio::runtime::Runtime;
+
+#[test]
+fn cancelling_unknown_tasks_fails() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let id = scheduler
+        .add_sync_task(
+            SpyingTask::default(),
+            IntervalSchedule::every(Duration::MAX),
+        )
+        .unwrap();
+
+    scheduler.cancel_task(id).unwrap();
+    assert!(matches!(scheduler.cancel_task(id), Err(Error::UnknownTask(unknown)) if unknown == id));
+}
+
+#[test]
+fn cancelling_finished_tasks_fails() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let task = SpyingTask::default();
+    let counter = task.counter();
+    let id = scheduler
+        .add_sync_task(task, OneShot::after(Duration::ZERO))
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(100));
+    assert_eq!(counter.load(Ordering::Relaxed), 1);
+    assert!(
+        matches!(scheduler.cancel_task(id), Err(Error::AlreadyFinished(finished)) if finished == id)
+    );
+}
+
+#[test]
+fn names_are_unique() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let schedule = || IntervalSchedule::every(Duration::MAX);
+    let id = scheduler
+        .add_named_sync_task("report", SpyingTask::default(), schedule())
+        .unwrap();
+
+    let duplicate = scheduler.add_named_sync_task("report", SpyingTask::default(), schedule());
+    assert!(matches!(duplicate, Err(Error::DuplicateName(name)) if name == "report"));
+
+    scheduler.cancel_task(id).unwrap();
+    scheduler
+        .add_named_sync_task("report", SpyingTask::default(), schedule())
+        .unwrap();
+}
+
+#[test]
+fn registering_on_a_shut_down_runtime_fails() {
+    let runtime = Runtime::new().unwrap();
+    let mut scheduler = Scheduler::tokio_scheduler_with_handle(runtime.handle().clone());
+    drop(runtime);
+
+    let result = scheduler.add_sync_task(SpyingTask::default(), OneShot::after(Duration::ZERO));
+    assert!(matches!(result, Err(Error::RuntimeShutDown)));
+}
@@ -0,0 +1,203 @@
+use periodically::{IntervalSchedule, Scheduler, SyncExecutor, Task, TaskFailure, ThreadPool};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+use tokio::runtime::Runtime;
+
+/// Records the name of the thread of every run.
+#[derive(Clone, Default)]
+struct ThreadNames {
+    names: Arc<Mutex<Vec<Option<String>>>>,
+}
+
+impl Task for ThreadNames {
+    fn run(&self) {
+        let name = std::thread::current().name().map(str::to_string);
+        self.names.lock().unwrap().push(name);
+    }
+}
+
+/// Panics on every other run, and outputs the number of runs otherwise.
+#[derive(Default)]
+struct PanicsEveryOtherRun {
+    runs: AtomicUsize,
+}
+
+impl Task<usize> for PanicsEveryOtherRun {
+    fn run(&self) -> usize {
+        let runs = self.runs.fetch_add(1, Ordering::SeqCst) + 1;
+        if runs % 2 == 1 {
+            panic!("Task panicked!");
+        }
+        runs
+    }
+}
+
+/// Runs every job on a new thread, and counts them.
+#[derive(Default)]
+struct ThreadPerJob {
+    jobs: AtomicUsize,
+}
+
+impl ThreadPool for ThreadPerJob {
+    fn spawn(&self, job: Box<dyn FnOnce() + Send>) {
+        self.jobs.fetch_add(1, Ordering::SeqCst);
+        std::thread::Builder::new()
+            .name("thread-per-job".to_string())
+            .spawn(job)
+            .unwrap();
+    }
+}
+
+fn every_10ms() -> IntervalSchedule {
+    IntervalSchedule::every(Duration::from_millis(10))
+}
+
+#[test]
+fn dedicated_threads_are_named_after_their_task() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let named = ThreadNames::default();
+    scheduler
+        .task(named.clone())
+        .schedule(every_10ms())
+        .name("reindex")
+        .executor(SyncExecutor::DedicatedThread)
+        .register()
+        .unwrap();
+    let unnamed = ThreadNames::default();
+    let id = scheduler
+        .task(unnamed.clone())
+        .schedule(every_10ms())
+        .executor(SyncExecutor::DedicatedThread)
+        .register()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(100));
+    let named = named.names.lock().unwrap();
+    assert!(named.len() >= 5);
+    assert!(named
+        .iter()
+        .all(|name| name.as_deref() == Some("periodically-reindex")));
+    let unnamed = unnamed.names.lock().unwrap();
+    assert!(unnamed.len() >= 5);
+    assert!(unnamed
+        .iter()
+        .all(|name| *name == Some(format!("periodically-task-{id}"))));
+}
+
+#[test]
+fn panicking_tasks_keep_their_dedicated_thread() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let id = scheduler
+        .task(PanicsEveryOtherRun::default())
+        .schedule(every_10ms())
+        .executor(SyncExecutor::DedicatedThread)
+        .register()
+        .unwrap();
+    let mut outputs = scheduler.subscribe::<usize>(id).unwrap();
+
+    let outputs: Vec<_> = (0..4).map(|_| outputs.blocking_recv().unwrap()).collect();
+    let first = match outputs[0] {
+        Ok(runs) => runs - 1,
+        Err(_) => 0,
+    };
+    for (i, output) in outputs.into_iter().enumerate() {
+        let runs = first + i + 1;
+        match runs % 2 {
+            0 => assert_eq!(output, Ok(runs)),
+            _ => assert_eq!(
+                output,
+                Err(TaskFailure::Panicked(Some("Task panicked!".to_string())))
+            ),
+        }
+    }
+}
+
+#[test]
+fn pools_run_tasks_and_report_panics() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let pool = Arc::new(ThreadPerJob::default());
+    let executor = SyncExecutor::pool(pool.clone());
+    let names = ThreadNames::default();
+    scheduler
+        .task(names.clone())
+        .schedule(every_10ms())
+        .executor(executor.clone())
+        .register()
+        .unwrap();
+    let id = scheduler
+        .task(PanicsEveryOtherRun::default())
+        .schedule(every_10ms())
+        .executor(executor)
+        .register()
+        .unwrap();
+    let mut outputs = scheduler.subscribe::<usize>(id).unwrap();
+
+    let outputs: Vec<_> = (0..4).map(|_| outputs.blocking_recv().unwrap()).collect();
+    assert_eq!(outputs.iter().filter(|output| output.is_ok()).count(), 2);
+    let names = names.names.lock().unwrap();
+    assert!(!names.is_empty());
+    assert!(names
+        .iter()
+        .all(|name| name.as_deref() == Some("thread-per-job")));
+    assert!(pool.jobs.load(Ordering::SeqCst) >= names.len() + 4);
+}
+
+#[test]
+fn pool_tasks_can_enter_the_runtime() {
+    struct UsesRuntime;
+
+    impl Task<bool> for UsesRuntime {
+        fn run(&self) -> bool {
+            tokio::runtime::Handle::try_current().is_ok()
+        }
+    }
+
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let pooled = scheduler
+        .task(UsesRuntime)
+        .schedule(every_10ms())
+        .executor(SyncExecutor::pool(ThreadPerJob::default()))
+        .register()
+        .unwrap();
+    let dedicated = scheduler
+        .task(UsesRuntime)
+        .schedule(every_10ms())
+        .executor(SyncExecutor::DedicatedThread)
+        .register()
+        .unwrap();
+
+    for id in [pooled, dedicated] {
+        let mut outputs = scheduler.subscribe::<bool>(id).unwrap();
+        assert_eq!(outputs.blocking_recv().unwrap(), Ok(true));
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn tasks_run_on_rayon_pools() {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .thread_name(|i| format!("rayon-{i}"))
+        .build()
+        .unwrap();
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let names = ThreadNames::default();
+    scheduler
+        .task(names.clone())
+        .schedule(every_10ms())
+        .executor(SyncExecutor::pool(pool))
+        .register()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(100));
+    let names = names.names.lock().unwrap();
+    assert!(names.len() >= 5);
+    assert!(names.iter().all(|name| name
+        .as_deref()
+        .is_some_and(|name| name.starts_with("rayon-"))));
+}
@@ -0,0 +1,177 @@
+pub mod tasks;
+
+use periodically::{AsyncTask, Error, IntervalSchedule, Scheduler};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread::sleep,
+    time::Duration,
+};
+use tasks::SpyingTask;
+use tokio::runtime::Runtime;
+
+/// Keeps track of how many runs of the tasks sharing it overlap.
+#[derive(Clone, Default)]
+struct Overlapping {
+    running: Arc<AtomicUsize>,
+    most_running: Arc<AtomicUsize>,
+}
+
+impl AsyncTask for Overlapping {
+    async fn run(&self) {
+        let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+        self.most_running.fetch_max(running, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        self.running.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn every_10ms() -> IntervalSchedule {
+    IntervalSchedule::every(Duration::from_millis(10))
+}
+
+#[test]
+fn groups_are_cancelled_as_a_unit() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let group = scheduler.task_group();
+    let mut counters = Vec::new();
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        let task = SpyingTask::default();
+        counters.push(task.counter());
+        let id = scheduler
+            .task(task)
+            .schedule(every_10ms())
+            .group(&group)
+            .register()
+            .unwrap();
+        ids.push(id);
+    }
+    let outsider = SpyingTask::default();
+    let outsider_counter = outsider.counter();
+    scheduler.add_sync_task(outsider, every_10ms()).unwrap();
+    assert_eq!(group.tasks().unwrap(), ids);
+
+    scheduler.cancel_task(ids[0]).unwrap();
+    assert_eq!(group.tasks().unwrap(), ids[1..]);
+
+    group.cancel().unwrap();
+    assert!(group.tasks().unwrap().is_empty());
+    assert!(matches!(
+        scheduler.cancel_task(ids[1]),
+        Err(Error::UnknownTask(_))
+    ));
+    sleep(Duration::from_millis(50));
+    let counts: Vec<_> = counters.iter().map(|c| c.load(Ordering::Relaxed)).collect();
+    let outsider_count = outsider_counter.load(Ordering::Relaxed);
+    sleep(Duration::from_millis(50));
+    let later: Vec<_> = counters.iter().map(|c| c.load(Ordering::Relaxed)).collect();
+    assert_eq!(counts, later);
+    assert!(outsider_counter.load(Ordering::Relaxed) > outsider_count);
+}
+
+#[test]
+fn dropping_a_group_cancels_its_tasks() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let group = scheduler.task_group();
+    let task = SpyingTask::default();
+    let counter = task.counter();
+    let id = scheduler
+        .async_task(task)
+        .schedule(every_10ms())
+        .group(&group)
+        .register()
+        .unwrap();
+
+    sleep(Duration::from_millis(50));
+    drop(group);
+    assert!(matches!(
+        scheduler.cancel_task(id),
+        Err(Error::UnknownTask(_))
+    ));
+    sleep(Duration::from_millis(20));
+    let count = counter.load(Ordering::Relaxed);
+    assert!(count > 0);
+    sleep(Duration::from_millis(50));
+    assert_eq!(counter.load(Ordering::Relaxed), count);
+}
+
+#[test]
+fn groups_are_paused_and_resumed_as_a_unit() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let group = scheduler.task_group();
+    let early = SpyingTask::default();
+    let early_counter = early.counter();
+    scheduler
+        .task(early)
+        .schedule(every_10ms())
+        .group(&group)
+        .register()
+        .unwrap();
+
+    group.pause().unwrap();
+    assert!(group.is_paused().unwrap());
+    // joining a paused group starts paused
+    let late = SpyingTask::default();
+    let late_counter = late.counter();
+    scheduler
+        .task(late)
+        .schedule(every_10ms())
+        .group(&group)
+        .register()
+        .unwrap();
+    sleep(Duration::from_millis(20));
+    let early_count = early_counter.load(Ordering::Relaxed);
+    sleep(Duration::from_millis(50));
+    assert_eq!(early_counter.load(Ordering::Relaxed), early_count);
+    assert_eq!(late_counter.load(Ordering::Relaxed), 0);
+
+    group.resume().unwrap();
+    assert!(!group.is_paused().unwrap());
+    sleep(Duration::from_millis(50));
+    assert!(early_counter.load(Ordering::Relaxed) > early_count);
+    assert!(late_counter.load(Ordering::Relaxed) > 0);
+}
+
+#[test]
+fn groups_limit_how_many_of_their_tasks_run_at_once() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let group = scheduler.task_group_with_concurrency_limit(2);
+    let grouped = Overlapping::default();
+    let ungrouped = Overlapping::default();
+    for _ in 0..4 {
+        scheduler
+            .async_task(grouped.clone())
+            .schedule(every_10ms())
+            .group(&group)
+            .register()
+            .unwrap();
+        scheduler
+            .add_async_task(ungrouped.clone(), every_10ms())
+            .unwrap();
+    }
+
+    sleep(Duration::from_millis(200));
+    assert_eq!(grouped.most_running.load(Ordering::SeqCst), 2);
+    assert!(ungrouped.most_running.load(Ordering::SeqCst) > 2);
+}
+
+#[test]
+fn groups_belong_to_their_scheduler() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let mut other = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let group = other.task_group();
+
+    let result = scheduler
+        .task(SpyingTask::default())
+        .schedule(every_10ms())
+        .group(&group)
+        .register();
+    assert!(matches!(result, Err(Error::InvalidOptions(_))));
+
+    drop(other);
+    assert!(matches!(group.tasks(), Err(Error::SchedulerDropped)));
+    assert!(matches!(group.pause(), Err(Error::SchedulerDropped)));
+}
@@ -0,0 +1,138 @@
+pub mod tasks;
+
+use periodically::{AsyncTask, Error, IntervalSchedule, OneShot, Scheduler, SchedulerHandle, Task};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread::sleep,
+    time::Duration,
+};
+use tasks::SpyingTask;
+use tokio::runtime::Runtime;
+
+struct Increment(Arc<AtomicUsize>);
+
+impl Task for Increment {
+    fn run(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Schedules an [Increment] from within a run, through the current handle.
+struct FollowUp(Arc<AtomicUsize>);
+
+impl AsyncTask for FollowUp {
+    async fn run(&self) {
+        let scheduler = SchedulerHandle::current().unwrap();
+        let follow_up = Increment(self.0.clone());
+        scheduler
+            .add_sync_task(follow_up, OneShot::after(Duration::ZERO))
+            .await
+            .unwrap();
+    }
+}
+
+/// Cancels itself on its first run, through the current handle.
+struct CancelsItself(Arc<AtomicUsize>);
+
+impl Task for CancelsItself {
+    fn run(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        let scheduler = SchedulerHandle::current().unwrap();
+        let id = tokio::runtime::Handle::current()
+            .block_on(scheduler.add_sync_task(SpyingTask::default(), OneShot::after(Duration::MAX)))
+            .unwrap();
+        tokio::runtime::Handle::current()
+            .block_on(scheduler.cancel(id))
+            .unwrap();
+    }
+}
+
+#[test]
+fn handles_are_shareable() {
+    fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+    assert_shareable::<SchedulerHandle>();
+}
+
+#[test]
+fn handles_manage_tasks_from_other_threads() {
+    let runtime = Runtime::new().unwrap();
+    let mut scheduler = Scheduler::tokio_scheduler_with_handle(runtime.handle().clone());
+    let task = SpyingTask::default();
+    let counter = task.counter();
+    let id = scheduler
+        .add_sync_task(task, IntervalSchedule::every(Duration::from_millis(10)))
+        .unwrap();
+
+    let handle = scheduler.handle();
+    let other_thread = std::thread::spawn(move || {
+        let runtime = Runtime::new().unwrap();
+        runtime.block_on(handle.pause(id)).unwrap();
+        runtime.block_on(handle.cancel(id)).unwrap();
+        runtime.block_on(handle.cancel(id))
+    });
+    let cancelled_twice = other_thread.join().unwrap();
+    assert!(matches!(cancelled_twice, Err(Error::UnknownTask(unknown)) if unknown == id));
+
+    let cancelled_at = counter.load(Ordering::Relaxed);
+    sleep(Duration::from_millis(50));
+    assert_eq!(counter.load(Ordering::Relaxed), cancelled_at);
+}
+
+#[test]
+fn tasks_schedule_follow_up_work() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let follow_ups = Arc::new(AtomicUsize::new(0));
+    scheduler
+        .add_async_task(FollowUp(follow_ups.clone()), OneShot::after(Duration::ZERO))
+        .unwrap();
+
+    sleep(Duration::from_millis(200));
+    assert_eq!(follow_ups.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn sync_tasks_receive_the_handle() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let runs = Arc::new(AtomicUsize::new(0));
+    scheduler
+        .add_sync_task(CancelsItself(runs.clone()), OneShot::after(Duration::ZERO))
+        .unwrap();
+
+    sleep(Duration::from_millis(100));
+    assert_eq!(runs.load(Ordering::Relaxed), 1);
+    assert!(SchedulerHandle::current().is_none());
+}
+
+#[test]
+fn triggered_tasks_run_ahead_of_schedule() {
+    let runtime = Runtime::new().unwrap();
+    let mut scheduler = Scheduler::tokio_scheduler_with_handle(runtime.handle().clone());
+    let task = SpyingTask::default();
+    let counter = task.counter();
+    let id = scheduler
+        .add_sync_task(task, IntervalSchedule::every(Duration::MAX))
+        .unwrap();
+
+    scheduler.trigger_task(id).unwrap();
+    sleep(Duration::from_millis(50));
+    assert_eq!(counter.load(Ordering::Relaxed), 1);
+
+    runtime.block_on(scheduler.handle().trigger(id)).unwrap();
+    sleep(Duration::from_millis(50));
+    assert_eq!(counter.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn handles_fail_once_the_scheduler_is_dropped() {
+    let runtime = Runtime::new().unwrap();
+    let scheduler = Scheduler::tokio_scheduler_with_handle(runtime.handle().clone());
+    let handle = scheduler.handle();
+    drop(scheduler);
+
+    let result = runtime
+        .block_on(handle.add_sync_task(SpyingTask::default(), OneShot::after(Duration::ZERO)));
+    assert!(matches!(result, Err(Error::SchedulerDropped)));
+}
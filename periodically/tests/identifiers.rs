@@ -0,0 +1,118 @@
+pub mod tasks;
+
+use periodically::{Dependency, Error, IntervalSchedule, Scheduler, TaskIdentifier, TaskStatus};
+use std::time::Duration;
+use tasks::SpyingTask;
+use tokio::runtime::Runtime;
+
+fn every_minute() -> IntervalSchedule {
+    IntervalSchedule::every(Duration::from_secs(60))
+}
+
+#[test]
+fn identifiers_only_work_with_their_scheduler() {
+    let mut first = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let mut second = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let id = first
+        .add_sync_task(SpyingTask::default(), every_minute())
+        .unwrap();
+    let other = second
+        .add_sync_task(SpyingTask::default(), every_minute())
+        .unwrap();
+    assert_ne!(id, other);
+
+    assert!(matches!(second.cancel_task(id), Err(Error::ForeignTask(foreign)) if foreign == id));
+    assert!(matches!(second.pause_task(id), Err(Error::ForeignTask(_))));
+    assert!(matches!(
+        second.add_sync_task_after(
+            SpyingTask::default(),
+            every_minute(),
+            [Dependency::on_success(id)]
+        ),
+        Err(Error::ForeignTask(_))
+    ));
+    // neither task was touched
+    assert_eq!(first.task_status(id).unwrap(), TaskStatus::Scheduled);
+    assert_eq!(second.task_status(other).unwrap(), TaskStatus::Scheduled);
+}
+
+#[test]
+fn identifiers_from_a_previous_process_are_foreign() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let id = scheduler
+        .add_sync_task(SpyingTask::default(), every_minute())
+        .unwrap();
+
+    // the same scheduler number and slot, as handed out by the scheduler before a restart
+    let id_text = id.to_string();
+    let [number, nonce, slot, generation] = id_text.split('-').collect::<Vec<_>>()[..] else {
+        panic!("identifiers have four parts");
+    };
+    let other_nonce = u64::from_str_radix(nonce, 16).unwrap().wrapping_add(1);
+    let previous: TaskIdentifier = format!("{number}-{other_nonce:016x}-{slot}-{generation}")
+        .parse()
+        .unwrap();
+
+    assert!(
+        matches!(scheduler.cancel_task(previous), Err(Error::ForeignTask(foreign)) if foreign == previous)
+    );
+    assert_eq!(scheduler.task_status(id).unwrap(), TaskStatus::Scheduled);
+}
+
+#[test]
+fn stale_identifiers_do_not_reach_newer_tasks() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let old = scheduler
+        .add_sync_task(SpyingTask::default(), every_minute())
+        .unwrap();
+    scheduler.cancel_task(old).unwrap();
+    assert!(matches!(
+        scheduler.cancel_task(old),
+        Err(Error::UnknownTask(_))
+    ));
+
+    let new = scheduler
+        .add_sync_task(SpyingTask::default(), every_minute())
+        .unwrap();
+    assert_ne!(old, new);
+    assert!(matches!(scheduler.cancel_task(old), Err(Error::StaleTask(stale)) if stale == old));
+    assert!(matches!(
+        scheduler.task_status(old),
+        Err(Error::StaleTask(_))
+    ));
+    assert_eq!(scheduler.task_status(new).unwrap(), TaskStatus::Scheduled);
+}
+
+#[test]
+fn identifiers_round_trip_through_strings() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let id = scheduler
+        .add_sync_task(SpyingTask::default(), every_minute())
+        .unwrap();
+
+    let parsed: TaskIdentifier = id.to_string().parse().unwrap();
+    assert_eq!(parsed, id);
+    scheduler.pause_task(parsed).unwrap();
+
+    for invalid in ["", "1-42-0", "1-ff-2-3-4", "1-xyz-2-3", "1-ff--3"] {
+        let error = invalid.parse::<TaskIdentifier>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("invalid task identifier [{invalid}], expected a scheduler, its nonce, a slot and a generation, eg; 1-8c6e0a2ff4b1d093-42-0")
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn identifiers_serialize_as_strings() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let id = scheduler
+        .add_sync_task(SpyingTask::default(), every_minute())
+        .unwrap();
+
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(json, format!("\"{id}\""));
+    assert_eq!(serde_json::from_str::<TaskIdentifier>(&json).unwrap(), id);
+    assert!(serde_json::from_str::<TaskIdentifier>("\"nonsense\"").is_err());
+}
@@ -1,7 +1,12 @@
 pub mod tasks;
 
-use periodically::{IntervalSchedule, Scheduler};
-use std::{sync::atomic::Ordering, thread::sleep, time::Duration};
+use periodically::{AlignedInterval, IntervalSchedule, Schedule, Scheduler};
+use std::{
+    collections::HashSet,
+    sync::atomic::Ordering,
+    thread::sleep,
+    time::{Duration, UNIX_EPOCH},
+};
 use tasks::SpyingTask;
 use tokio::runtime::Runtime;
 
@@ -15,10 +20,57 @@ fn interval_scheduling_is_not_too_fast() {
     let counter = task.counter();
 
     let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
-    let id = scheduler.add_async_task(task, IntervalSchedule::every(INTERVAL));
+    let id = scheduler
+        .add_async_task(task, IntervalSchedule::every(INTERVAL))
+        .unwrap();
     sleep(TEST_DURATION);
     scheduler.cancel_task(id).unwrap();
 
     let real_count = counter.load(Ordering::Relaxed);
     assert!(real_count <= EXPECTED_COUNT);
 }
+
+#[test]
+fn aligned_offsets_spread_instances() {
+    let period = Duration::from_secs(300);
+    let offsets: HashSet<_> = ["host-a", "host-b", "host-c"]
+        .into_iter()
+        .map(|host| {
+            let schedule = AlignedInterval::every(period).with_offset_from(host);
+            let first = schedule.preview(UNIX_EPOCH, || ()).next().unwrap();
+            first.duration_since(UNIX_EPOCH).unwrap()
+        })
+        .collect();
+    assert_eq!(offsets.len(), 3);
+    assert!(offsets.iter().all(|offset| *offset < period));
+
+    let again = AlignedInterval::every(period).with_offset_from("host-a");
+    let first = again.preview(UNIX_EPOCH, || ()).next().unwrap();
+    assert!(offsets.contains(&first.duration_since(UNIX_EPOCH).unwrap()));
+}
+
+#[test]
+fn aligned_intervals_run_on_boundaries() {
+    let period = Duration::from_millis(50);
+    let start = UNIX_EPOCH + Duration::from_millis(1_234);
+    let runs: Vec<_> = AlignedInterval::every(period)
+        .preview(start, || ())
+        .take(3)
+        .map(|at| at.duration_since(UNIX_EPOCH).unwrap())
+        .collect();
+    assert_eq!(
+        runs,
+        [1_250, 1_300, 1_350].map(Duration::from_millis).to_vec()
+    );
+
+    let task = SpyingTask::default();
+    let counter = task.counter();
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    scheduler
+        .add_sync_task(task, AlignedInterval::every(period))
+        .unwrap();
+
+    sleep(Duration::from_millis(275));
+    let count = counter.load(Ordering::Relaxed);
+    assert!((4..=6).contains(&count), "ran {count} times");
+}
@@ -0,0 +1,137 @@
+use periodically::{Error, IntervalSchedule, LocalAsyncTask, Scheduler};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::{Duration, Instant},
+};
+use tokio::{runtime::Runtime, task::LocalSet, time::sleep};
+
+/// Appends to a log which can only be shared within a single thread.
+struct LocalLog {
+    entries: Rc<RefCell<Vec<usize>>>,
+}
+
+impl LocalAsyncTask for LocalLog {
+    async fn run(&self) {
+        let len = self.entries.borrow().len();
+        // holding on to an `Rc` across an await point makes the future `!Send`
+        let entries = self.entries.clone();
+        tokio::task::yield_now().await;
+        entries.borrow_mut().push(len);
+    }
+}
+
+/// Panics on every other run.
+struct LocalPanicking {
+    counter: Rc<Cell<usize>>,
+}
+
+impl LocalAsyncTask for LocalPanicking {
+    async fn run(&self) {
+        let count = self.counter.get();
+        self.counter.set(count + 1);
+        if count.is_multiple_of(2) {
+            panic!("Task panicked!");
+        }
+    }
+}
+
+/// Outputs how often it ran, in an output which is not `Send`.
+struct LocalCount {
+    counter: Rc<Cell<usize>>,
+}
+
+impl LocalAsyncTask<Rc<usize>> for LocalCount {
+    async fn run(&self) -> Rc<usize> {
+        self.counter.set(self.counter.get() + 1);
+        Rc::new(self.counter.get())
+    }
+}
+
+#[test]
+fn local_tasks_run_on_their_schedule() {
+    let runtime = Runtime::new().unwrap();
+    let mut scheduler = Scheduler::tokio_scheduler_with_handle(runtime.handle().clone());
+    let local_set = LocalSet::new();
+    let entries = Rc::new(RefCell::new(Vec::new()));
+    let task = LocalLog {
+        entries: entries.clone(),
+    };
+    let schedule = IntervalSchedule::every(Duration::from_millis(10));
+    scheduler
+        .add_local_task(&local_set, task, schedule)
+        .unwrap();
+
+    local_set.block_on(&runtime, async { sleep(Duration::from_millis(100)).await });
+    let entries = entries.borrow();
+    assert!(entries.len() >= 5);
+    assert!(entries.iter().enumerate().all(|(i, len)| i == *len));
+}
+
+#[test]
+fn panicking_local_task_keeps_running() {
+    let runtime = Runtime::new().unwrap();
+    let mut scheduler = Scheduler::tokio_scheduler_with_handle(runtime.handle().clone());
+    let local_set = LocalSet::new();
+    let counter = Rc::new(Cell::new(0));
+    let task = LocalPanicking {
+        counter: counter.clone(),
+    };
+    let schedule = IntervalSchedule::every(Duration::from_millis(10));
+    scheduler
+        .add_local_task(&local_set, task, schedule)
+        .unwrap();
+
+    // panics are slow to report with backtraces enabled, so wait on the runs rather than the clock
+    local_set.block_on(&runtime, async {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while counter.get() < 5 && Instant::now() < deadline {
+            sleep(Duration::from_millis(10)).await;
+        }
+    });
+    assert!(counter.get() >= 5);
+}
+
+#[test]
+fn cancelled_local_task_stops_running() {
+    let runtime = Runtime::new().unwrap();
+    let mut scheduler = Scheduler::tokio_scheduler_with_handle(runtime.handle().clone());
+    let local_set = LocalSet::new();
+    let entries = Rc::new(RefCell::new(Vec::new()));
+    let task = LocalLog {
+        entries: entries.clone(),
+    };
+    let schedule = IntervalSchedule::every(Duration::from_millis(10));
+    let id = scheduler
+        .add_local_task(&local_set, task, schedule)
+        .unwrap();
+
+    local_set.block_on(&runtime, async { sleep(Duration::from_millis(50)).await });
+    scheduler.cancel_task(id).unwrap();
+    local_set.block_on(&runtime, async { sleep(Duration::from_millis(20)).await });
+    let runs = entries.borrow().len();
+    local_set.block_on(&runtime, async { sleep(Duration::from_millis(50)).await });
+    assert_eq!(entries.borrow().len(), runs);
+}
+
+#[test]
+fn local_outputs_need_not_be_send() {
+    let runtime = Runtime::new().unwrap();
+    let mut scheduler = Scheduler::tokio_scheduler_with_handle(runtime.handle().clone());
+    let local_set = LocalSet::new();
+    let counter = Rc::new(Cell::new(0));
+    let task = LocalCount {
+        counter: counter.clone(),
+    };
+    let schedule = IntervalSchedule::every(Duration::from_millis(10));
+    let id = scheduler
+        .add_local_task(&local_set, task, schedule)
+        .unwrap();
+
+    local_set.block_on(&runtime, async { sleep(Duration::from_millis(50)).await });
+    assert!(counter.get() > 0);
+    assert!(matches!(
+        scheduler.subscribe::<usize>(id),
+        Err(Error::LocalOutputs(_))
+    ));
+}
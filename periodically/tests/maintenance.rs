@@ -0,0 +1,242 @@
+pub mod tasks;
+
+use periodically::{CatchUp, IntervalSchedule, Scheduler, Task};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread::sleep,
+    time::{Duration, Instant},
+};
+use tasks::{schedulers, SpyingTask};
+use tokio::runtime::Runtime;
+
+/// Takes a while to run.
+#[derive(Default)]
+struct Slow {
+    finished: Arc<AtomicUsize>,
+}
+
+impl Task for Slow {
+    fn run(&self) {
+        sleep(Duration::from_millis(200));
+        self.finished.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn every(millis: u64) -> IntervalSchedule {
+    IntervalSchedule::every(Duration::from_millis(millis))
+}
+
+#[test]
+fn every_task_is_paused_and_resumed() {
+    for mut scheduler in schedulers() {
+        let sync_task = SpyingTask::default();
+        let sync_counter = sync_task.counter();
+        let async_task = SpyingTask::default();
+        let async_counter = async_task.counter();
+        scheduler.add_sync_task(sync_task, every(10)).unwrap();
+        scheduler.add_async_task(async_task, every(10)).unwrap();
+
+        sleep(Duration::from_millis(50));
+        scheduler.pause_all().wait();
+        let counts = (
+            sync_counter.load(Ordering::Relaxed),
+            async_counter.load(Ordering::Relaxed),
+        );
+        assert!(counts.0 > 0 && counts.1 > 0);
+        sleep(Duration::from_millis(50));
+        assert_eq!(
+            (
+                sync_counter.load(Ordering::Relaxed),
+                async_counter.load(Ordering::Relaxed)
+            ),
+            counts
+        );
+
+        scheduler.resume_all();
+        sleep(Duration::from_millis(50));
+        assert!(sync_counter.load(Ordering::Relaxed) > counts.0);
+        assert!(async_counter.load(Ordering::Relaxed) > counts.1);
+    }
+}
+
+#[test]
+fn only_matching_tasks_are_paused_and_resumed() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let database = SpyingTask::default();
+    let database_counter = database.counter();
+    let cache = SpyingTask::default();
+    let cache_counter = cache.counter();
+    scheduler
+        .add_named_sync_task("db-vacuum", database, every(10))
+        .unwrap();
+    scheduler
+        .add_named_sync_task("cache-warmup", cache, every(10))
+        .unwrap();
+
+    let in_flight =
+        scheduler.pause_matching(|_, name| name.is_some_and(|name| name.starts_with("db-")));
+    in_flight.wait();
+    let database_count = database_counter.load(Ordering::Relaxed);
+    let cache_count = cache_counter.load(Ordering::Relaxed);
+    sleep(Duration::from_millis(50));
+    assert_eq!(database_counter.load(Ordering::Relaxed), database_count);
+    assert!(cache_counter.load(Ordering::Relaxed) > cache_count);
+
+    scheduler.resume_matching(|_, name| name == Some("cache-warmup"));
+    sleep(Duration::from_millis(50));
+    assert_eq!(database_counter.load(Ordering::Relaxed), database_count);
+    scheduler.resume_matching(|_, name| name == Some("db-vacuum"));
+    sleep(Duration::from_millis(50));
+    assert!(database_counter.load(Ordering::Relaxed) > database_count);
+}
+
+#[test]
+fn paused_groups_stay_paused_when_every_task_is_resumed() {
+    for mut scheduler in schedulers() {
+        let tenant = scheduler.task_group();
+        let member = SpyingTask::default();
+        let member_counter = member.counter();
+        let loner = SpyingTask::default();
+        let loner_counter = loner.counter();
+        scheduler
+            .task(member)
+            .schedule(every(10))
+            .group(&tenant)
+            .register()
+            .unwrap();
+        scheduler.add_sync_task(loner, every(10)).unwrap();
+
+        tenant.pause().unwrap();
+        scheduler.pause_all().wait();
+        scheduler.resume_all();
+        let member_count = member_counter.load(Ordering::Relaxed);
+        let loner_count = loner_counter.load(Ordering::Relaxed);
+        sleep(Duration::from_millis(50));
+        assert_eq!(member_counter.load(Ordering::Relaxed), member_count);
+        assert!(loner_counter.load(Ordering::Relaxed) > loner_count);
+        assert!(tenant.is_paused().unwrap());
+
+        tenant.resume().unwrap();
+        sleep(Duration::from_millis(50));
+        assert!(member_counter.load(Ordering::Relaxed) > member_count);
+    }
+}
+
+#[test]
+fn pausing_waits_for_runs_in_flight() {
+    for mut scheduler in schedulers() {
+        let task = Slow::default();
+        let finished = task.finished.clone();
+        scheduler
+            .task(task)
+            .schedule(every(10))
+            .timeout(Duration::from_secs(1))
+            .register()
+            .unwrap();
+
+        // the first run starts after 10ms, and takes 200ms
+        sleep(Duration::from_millis(50));
+        let in_flight = scheduler.pause_all();
+        assert!(!in_flight.is_finished());
+        in_flight.wait();
+        assert_eq!(finished.load(Ordering::SeqCst), 1);
+        sleep(Duration::from_millis(250));
+        assert_eq!(finished.load(Ordering::SeqCst), 1);
+        assert!(scheduler.pause_all().is_finished());
+    }
+}
+
+#[test]
+fn pausing_does_not_wait_for_runs_waiting_on_their_permits() {
+    for mut scheduler in schedulers() {
+        let group = scheduler.task_group_with_concurrency_limit(1);
+        scheduler
+            .task(Slow::default())
+            .schedule(every(10))
+            .group(&group)
+            .register()
+            .unwrap();
+        let task = SpyingTask::default();
+        let counter = task.counter();
+        scheduler
+            .task(task)
+            .name("waiting")
+            .schedule(every(10))
+            .group(&group)
+            .register()
+            .unwrap();
+
+        // one of the tasks is running, and the other one waits on the group's permit
+        sleep(Duration::from_millis(50));
+        let in_flight = scheduler.pause_matching(|_, name| name == Some("waiting"));
+        let count = counter.load(Ordering::SeqCst);
+        assert!(in_flight.is_finished());
+        sleep(Duration::from_millis(450));
+        assert_eq!(counter.load(Ordering::SeqCst), count);
+    }
+}
+
+#[test]
+fn missed_runs_follow_the_catch_up_policy() {
+    for driven in [false, true] {
+        for (catch_up, caught_up) in [
+            (CatchUp::Skip, 0..=0),
+            (CatchUp::RunOnce, 1..=1),
+            (CatchUp::RunAll, 4..=7),
+        ] {
+            let scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+            let scheduler = match driven {
+                true => scheduler.with_timer_driver(),
+                false => scheduler,
+            };
+            let mut scheduler = scheduler.with_pause_catch_up(catch_up);
+            let task = SpyingTask::default();
+            let counter = task.counter();
+            scheduler.add_sync_task(task, every(50)).unwrap();
+
+            sleep(Duration::from_millis(75));
+            scheduler.pause_all().wait();
+            let count = counter.load(Ordering::Relaxed);
+            sleep(Duration::from_millis(300));
+            scheduler.resume_all();
+            sleep(Duration::from_millis(20));
+            // the missed runs may take longer to catch up on a busy host
+            let deadline = Instant::now() + Duration::from_secs(1);
+            while counter.load(Ordering::Relaxed) - count < *caught_up.start()
+                && Instant::now() < deadline
+            {
+                sleep(Duration::from_millis(5));
+            }
+            let caught = counter.load(Ordering::Relaxed) - count;
+            assert!(
+                caught_up.contains(&caught),
+                "{catch_up:?} caught up on {caught} runs, with the timer driver: {driven}"
+            );
+        }
+    }
+}
+
+#[test]
+fn tasks_override_the_catch_up_policy() {
+    let mut scheduler =
+        Scheduler::tokio_scheduler(Runtime::new().unwrap()).with_pause_catch_up(CatchUp::RunAll);
+    let task = SpyingTask::default();
+    let counter = task.counter();
+    scheduler
+        .task(task)
+        .schedule(every(50))
+        .catch_up(CatchUp::Skip)
+        .register()
+        .unwrap();
+
+    sleep(Duration::from_millis(75));
+    scheduler.pause_all().wait();
+    let count = counter.load(Ordering::Relaxed);
+    sleep(Duration::from_millis(300));
+    scheduler.resume_all();
+    sleep(Duration::from_millis(20));
+    assert_eq!(counter.load(Ordering::Relaxed), count);
+}
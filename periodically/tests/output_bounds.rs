@@ -0,0 +1,8 @@
+//! Pins which task outputs each registration path accepts. Outputs must be `Send + 'static`, and nothing more.
+
+#[test]
+fn output_bounds() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/pass/*.rs");
+    cases.compile_fail("tests/ui/fail/*.rs");
+}
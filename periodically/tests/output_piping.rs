@@ -57,7 +57,7 @@ fn task_output_is_piped_to_schedule() {
     };
 
     let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
-    scheduler.add_sync_task(task, schedule);
+    scheduler.add_sync_task(task, schedule).unwrap();
 
     std::thread::sleep(Duration::from_millis(100));
 
@@ -0,0 +1,111 @@
+pub mod tasks;
+
+use periodically::{AsyncTask, Error, IntervalSchedule, OneShot, Scheduler, Task, TaskFailure};
+use std::time::Duration;
+use tasks::PanickingTask;
+use tokio::{runtime::Runtime, sync::broadcast::error::RecvError};
+
+struct Answer;
+
+impl Task<usize> for Answer {
+    fn run(&self) -> usize {
+        42
+    }
+}
+
+impl AsyncTask<usize> for Answer {
+    async fn run(&self) -> usize {
+        42
+    }
+}
+
+#[test]
+fn subscribers_receive_outputs() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let id = scheduler
+        .add_sync_task(Answer, IntervalSchedule::every(Duration::from_millis(10)))
+        .unwrap();
+
+    let mut first = scheduler.subscribe::<usize>(id).unwrap();
+    let mut second = scheduler.subscribe::<usize>(id).unwrap();
+    for _ in 0..3 {
+        assert_eq!(first.blocking_recv().unwrap(), Ok(42));
+        assert_eq!(second.blocking_recv().unwrap(), Ok(42));
+    }
+}
+
+#[test]
+fn panics_are_published_as_failures() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let id = scheduler
+        .add_sync_task(
+            PanickingTask::with_modulo(2),
+            IntervalSchedule::every(Duration::from_millis(10)),
+        )
+        .unwrap();
+
+    let mut outputs = scheduler.subscribe::<()>(id).unwrap();
+    let mut received = vec![
+        outputs.blocking_recv().unwrap(),
+        outputs.blocking_recv().unwrap(),
+    ];
+    received.sort_by_key(Result::is_ok);
+    assert_eq!(
+        received,
+        vec![
+            Err(TaskFailure::Panicked(Some("Task panicked!".to_string()))),
+            Ok(())
+        ]
+    );
+}
+
+#[test]
+fn subscriptions_end_with_the_schedule() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let id = scheduler
+        .add_sync_task(Answer, OneShot::after(Duration::from_millis(50)))
+        .unwrap();
+
+    let mut outputs = scheduler.subscribe::<usize>(id).unwrap();
+    assert_eq!(outputs.blocking_recv().unwrap(), Ok(42));
+    assert_eq!(outputs.blocking_recv(), Err(RecvError::Closed));
+    assert!(matches!(
+        scheduler.subscribe::<usize>(id),
+        Err(Error::AlreadyFinished(finished)) if finished == id
+    ));
+}
+
+#[test]
+fn subscribing_with_the_wrong_type_fails() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let id = scheduler
+        .add_sync_task(Answer, IntervalSchedule::every(Duration::MAX))
+        .unwrap();
+
+    assert!(matches!(
+        scheduler.subscribe::<String>(id),
+        Err(Error::OutputType(wrong)) if wrong == id
+    ));
+
+    scheduler.cancel_task(id).unwrap();
+    assert!(matches!(
+        scheduler.subscribe::<usize>(id),
+        Err(Error::UnknownTask(unknown)) if unknown == id
+    ));
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn outputs_can_be_streamed() {
+    use tokio_stream::StreamExt;
+
+    let runtime = Runtime::new().unwrap();
+    let mut scheduler = Scheduler::tokio_scheduler_with_handle(runtime.handle().clone());
+    let id = scheduler
+        .add_async_task(Answer, OneShot::after(Duration::from_millis(50)))
+        .unwrap();
+
+    let stream = scheduler.output_stream::<usize>(id).unwrap();
+    let outputs: Vec<_> = runtime.block_on(stream.collect());
+    assert_eq!(outputs, vec![Ok(42)]);
+}
@@ -1,20 +1,33 @@
 pub mod tasks;
 
 use periodically::{IntervalSchedule, Scheduler};
-use std::{sync::atomic::Ordering, thread::sleep, time::Duration};
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread::sleep,
+    time::{Duration, Instant},
+};
 use tasks::PanickingTask;
 use tokio::runtime::Runtime;
 
+/// Waits for `runs` runs, or gives up after a few seconds. Panics are slow to report with
+///   backtraces enabled, so this waits on the runs rather than the clock.
+fn wait_for_runs(counter: &AtomicUsize, runs: usize) -> usize {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while counter.load(Ordering::Acquire) < runs && Instant::now() < deadline {
+        sleep(Duration::from_millis(10));
+    }
+    counter.load(Ordering::Acquire)
+}
+
 #[test]
 fn panicking_sync_task_keeps_running() {
     let task = PanickingTask::with_modulo(2);
     let schedule = IntervalSchedule::every(Duration::from_millis(10));
     let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
     let counter = task.counter();
-    scheduler.add_sync_task(task, schedule);
+    scheduler.add_sync_task(task, schedule).unwrap();
 
-    sleep(Duration::from_millis(100));
-    assert!(counter.load(Ordering::Acquire) >= 5);
+    assert!(wait_for_runs(&counter, 5) >= 5);
 }
 
 #[test]
@@ -23,8 +36,7 @@ fn panicking_async_task_keeps_running() {
     let schedule = IntervalSchedule::every(Duration::from_millis(10));
     let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
     let counter = task.counter();
-    scheduler.add_async_task(task, schedule);
+    scheduler.add_async_task(task, schedule).unwrap();
 
-    sleep(Duration::from_millis(100));
-    assert!(counter.load(Ordering::Acquire) >= 5);
+    assert!(wait_for_runs(&counter, 5) >= 5);
 }
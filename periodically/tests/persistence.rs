@@ -0,0 +1,152 @@
+pub mod tasks;
+
+use periodically::{
+    CatchUp, IntervalSchedule, MemoryStateStore, OneShot, Persistent, Schedule, Scheduler,
+    StateStore, TaskState,
+};
+use std::{
+    sync::{atomic::Ordering, Arc},
+    thread::sleep,
+    time::{Duration, SystemTime},
+};
+use tasks::SpyingTask;
+use tokio::runtime::Runtime;
+
+const INTERVAL: Duration = Duration::from_secs(1);
+
+/// A store where the task "missed" was due 3.5 intervals ago.
+fn store_with_missed_runs() -> Arc<dyn StateStore> {
+    let store = MemoryStateStore::default();
+    let state = TaskState {
+        last_run: None,
+        next_due: Some(SystemTime::now() - INTERVAL.mul_f64(3.5)),
+    };
+    store.save("missed", state).unwrap();
+    Arc::new(store)
+}
+
+fn restored(catch_up: CatchUp) -> Box<dyn Schedule<()>> {
+    let schedule = IntervalSchedule::every(INTERVAL);
+    Box::new(Persistent::new(
+        "missed",
+        schedule,
+        store_with_missed_runs(),
+        catch_up,
+    ))
+}
+
+#[test]
+fn skip_resumes_normal_schedule() {
+    let schedule = restored(CatchUp::Skip);
+    assert_eq!(schedule.initial(), Some(INTERVAL));
+}
+
+#[test]
+fn run_once_collapses_missed_runs() {
+    let schedule = restored(CatchUp::RunOnce);
+    assert_eq!(schedule.initial(), Some(Duration::ZERO));
+    assert_eq!(schedule.next(()), Some(INTERVAL));
+}
+
+#[test]
+fn run_all_replays_every_missed_run() {
+    let schedule = restored(CatchUp::RunAll);
+    assert_eq!(schedule.initial(), Some(Duration::ZERO));
+    assert_eq!(schedule.next(()), Some(Duration::ZERO));
+    assert_eq!(schedule.next(()), Some(Duration::ZERO));
+    assert_eq!(schedule.next(()), Some(Duration::ZERO));
+    let caught_up = schedule.next(()).unwrap();
+    assert!(caught_up > Duration::ZERO && caught_up <= INTERVAL / 2);
+}
+
+#[test]
+fn restored_schedules_are_started() {
+    let store: Arc<dyn StateStore> = Arc::new(MemoryStateStore::default());
+    // Or only keeps track of its schedules once started
+    let either = || {
+        let every = |secs| IntervalSchedule::every(Duration::from_secs(secs));
+        let schedule = Persistent::new(
+            "either",
+            every(10).or(every(60)),
+            store.clone(),
+            CatchUp::RunOnce,
+        );
+        Box::new(schedule) as Box<dyn Schedule<()>>
+    };
+    let schedule = either();
+    schedule.initial().unwrap();
+    schedule.next(()).unwrap();
+
+    let restored = either();
+    assert!(restored.initial().unwrap() <= Duration::from_secs(10));
+    assert!(restored.next(()).unwrap() <= Duration::from_secs(10));
+}
+
+#[test]
+fn finished_one_shot_does_not_run_after_restart() {
+    let store = Arc::new(MemoryStateStore::default());
+    let run_once = || {
+        let task = SpyingTask::default();
+        let counter = task.counter();
+        let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap())
+            .with_state_store(store.clone(), CatchUp::RunOnce);
+        scheduler
+            .add_named_sync_task("warm-up", task, OneShot::after(Duration::ZERO))
+            .unwrap();
+        sleep(Duration::from_millis(50));
+        counter.load(Ordering::Acquire)
+    };
+
+    assert_eq!(run_once(), 1);
+    assert_eq!(run_once(), 0);
+    let state = store.load("warm-up").unwrap().unwrap();
+    assert!(state.last_run.is_some());
+    assert_eq!(state.next_due, None);
+}
+
+#[cfg(feature = "persistence")]
+#[test]
+fn file_store_writes_in_the_background() {
+    use periodically::FileStateStore;
+
+    let path = std::env::temp_dir().join(format!(
+        "periodically-test-background-{}.json",
+        std::process::id()
+    ));
+    let store = FileStateStore::open(&path).unwrap();
+    let states: Vec<_> = (0..100)
+        .map(|secs| TaskState {
+            last_run: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs)),
+            next_due: None,
+        })
+        .collect();
+    for state in &states {
+        store.save("busy", *state).unwrap();
+    }
+    store.flush().unwrap();
+    assert_eq!(
+        FileStateStore::open(&path).unwrap().load("busy").unwrap(),
+        states.last().copied()
+    );
+
+    // dropping the store writes whatever is still pending
+    store.save("dropped", states[0]).unwrap();
+    drop(store);
+    let reopened = FileStateStore::open(&path).unwrap();
+    assert_eq!(reopened.load("dropped").unwrap(), Some(states[0]));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "persistence")]
+#[test]
+fn file_store_reports_failed_writes_when_flushed() {
+    use periodically::FileStateStore;
+
+    let path = std::env::temp_dir()
+        .join(format!("periodically-test-missing-{}", std::process::id()))
+        .join("state.json");
+    let store = FileStateStore::open(&path).unwrap();
+    store.save("lost", TaskState::default()).unwrap();
+    assert!(store.flush().is_err());
+    assert!(store.flush().is_ok());
+}
@@ -0,0 +1,97 @@
+use periodically::{
+    AlignedInterval, CatchUp, IntervalSchedule, MemoryStateStore, OneShot, Persistent, Schedule,
+    StateStore,
+};
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+fn secs(secs: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+#[test]
+fn previews_intervals() {
+    let schedule = IntervalSchedule::with_initial_delay(Duration::from_secs(10), Duration::ZERO);
+    let times: Vec<_> = schedule.preview(secs(100), || ()).take(3).collect();
+    assert_eq!(times, vec![secs(100), secs(110), secs(120)]);
+}
+
+#[test]
+fn previews_wall_clock_schedules_from_the_simulated_time() {
+    let schedule = OneShot::at(secs(500));
+    let times: Vec<_> = schedule.preview(secs(100), || ()).collect();
+    assert_eq!(times, vec![secs(500)]);
+}
+
+#[test]
+fn previews_combinators() {
+    let schedule = IntervalSchedule::every(Duration::from_secs(10))
+        .or(IntervalSchedule::every(Duration::from_secs(25)))
+        .for_duration(Duration::from_secs(60));
+    let times: Vec<_> = schedule.preview(secs(0), || ()).collect();
+    assert_eq!(
+        times,
+        vec![
+            secs(10),
+            secs(20),
+            secs(25),
+            secs(30),
+            secs(40),
+            secs(50),
+            secs(60),
+        ]
+    );
+}
+
+#[test]
+fn feeds_outputs_into_the_schedule() {
+    let mut warm = false;
+    let schedule = IntervalSchedule::every(Duration::from_secs(1))
+        .until(|warm: &bool| *warm)
+        .then(IntervalSchedule::every(Duration::from_secs(60)));
+    let times: Vec<_> = schedule
+        .preview(secs(0), || std::mem::replace(&mut warm, true))
+        .take(3)
+        .collect();
+    assert_eq!(times, vec![secs(1), secs(2), secs(62)]);
+}
+
+#[test]
+fn previewing_does_not_save_state() {
+    let store = Arc::new(MemoryStateStore::default());
+    let schedule = Persistent::new(
+        "preview",
+        IntervalSchedule::every(Duration::from_secs(1)),
+        store.clone(),
+        CatchUp::RunOnce,
+    );
+    assert_eq!(schedule.preview(secs(0), || ()).take(3).count(), 3);
+    assert_eq!(store.load("preview").unwrap(), None);
+}
+
+#[cfg(feature = "cron")]
+#[test]
+fn previews_cron_schedules() {
+    use periodically::CronSchedule;
+
+    let schedule = CronSchedule::from_cron_str("0 9 * * MON-FRI").unwrap();
+    // friday the 5th of january 2024, at noon
+    let times: Vec<_> = schedule
+        .preview(secs(1_704_456_000), || ())
+        .take(2)
+        .collect();
+    // monday and tuesday at 9am
+    assert_eq!(times, vec![secs(1_704_704_400), secs(1_704_790_800)]);
+}
+
+#[test]
+fn previews_aligned_intervals() {
+    let schedule = AlignedInterval::every(Duration::from_secs(300));
+    let times: Vec<_> = schedule.preview(secs(299), || ()).take(3).collect();
+    assert_eq!(times, vec![secs(300), secs(600), secs(900)]);
+
+    let on_boundary: Vec<_> = schedule.preview(secs(600), || ()).take(2).collect();
+    assert_eq!(on_boundary, vec![secs(600), secs(900)]);
+}
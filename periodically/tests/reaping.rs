@@ -0,0 +1,151 @@
+pub mod tasks;
+
+use periodically::{Dependency, Error, OneShot, Scheduler, TaskStatus};
+use std::{sync::mpsc, thread::sleep, time::Duration};
+use tasks::{schedulers, SpyingTask};
+use tokio::runtime::Runtime;
+
+fn soon() -> OneShot {
+    OneShot::after(Duration::from_millis(10))
+}
+
+#[test]
+fn finished_tasks_are_reaped() {
+    for mut scheduler in schedulers() {
+        let group = scheduler.task_group();
+        let id = scheduler
+            .task(SpyingTask::default())
+            .schedule(soon())
+            .name("warm-cache")
+            .group(&group)
+            .register()
+            .unwrap();
+        assert_eq!(group.tasks().unwrap(), vec![id]);
+
+        sleep(Duration::from_millis(50));
+        assert!(group.tasks().unwrap().is_empty());
+        assert_eq!(scheduler.task_status(id).unwrap(), TaskStatus::Finished);
+        assert!(matches!(
+            scheduler.pause_task(id),
+            Err(Error::AlreadyFinished(_))
+        ));
+        assert!(matches!(
+            scheduler.cancel_task(id),
+            Err(Error::AlreadyFinished(_))
+        ));
+        // the name is free again
+        scheduler
+            .add_named_sync_task("warm-cache", SpyingTask::default(), soon())
+            .unwrap();
+    }
+}
+
+#[test]
+fn on_finish_is_called_once_the_schedule_finishes() {
+    for mut scheduler in schedulers() {
+        let (sender, finished) = mpsc::channel();
+        let id = scheduler
+            .async_task(SpyingTask::default())
+            .schedule(soon())
+            .on_finish(move |id, status| sender.send((id, status)).unwrap())
+            .register()
+            .unwrap();
+
+        let (finished_id, status) = finished.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert_eq!(finished_id, id);
+        assert_eq!(status, TaskStatus::Finished);
+    }
+}
+
+#[test]
+fn on_finish_is_not_called_for_cancelled_tasks() {
+    for mut scheduler in schedulers() {
+        let (sender, finished) = mpsc::channel();
+        let id = scheduler
+            .task(SpyingTask::default())
+            .schedule(OneShot::after(Duration::from_millis(50)))
+            .on_finish(move |id, status| sender.send((id, status)).unwrap())
+            .register()
+            .unwrap();
+
+        scheduler.cancel_task(id).unwrap();
+        assert!(finished.recv_timeout(Duration::from_millis(100)).is_err());
+    }
+}
+
+#[test]
+fn finished_tasks_cancelled_before_being_reaped_are_remembered() {
+    for mut scheduler in schedulers() {
+        let (finishing, finished) = mpsc::channel();
+        let (release, released) = mpsc::channel::<()>();
+        let id = scheduler
+            .task(SpyingTask::default())
+            .schedule(soon())
+            .on_finish(move |_, _| {
+                // holds off the reaping until the task was cancelled
+                finishing.send(()).unwrap();
+                let _ = released.recv_timeout(Duration::from_secs(5));
+            })
+            .register()
+            .unwrap();
+
+        finished.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(matches!(
+            scheduler.cancel_task(id),
+            Err(Error::AlreadyFinished(_))
+        ));
+        release.send(()).unwrap();
+        sleep(Duration::from_millis(50));
+        assert_eq!(scheduler.task_status(id).unwrap(), TaskStatus::Finished);
+    }
+}
+
+#[test]
+fn only_the_latest_endings_are_remembered() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap()).with_timer_driver();
+    let ids: Vec<_> = (0..1100)
+        .map(|_| {
+            scheduler
+                .add_sync_task(SpyingTask::default(), OneShot::after(Duration::ZERO))
+                .unwrap()
+        })
+        .collect();
+
+    sleep(Duration::from_millis(500));
+    // tasks finish in any order, so only count them
+    let remembered = ids
+        .iter()
+        .filter(|id| matches!(scheduler.task_status(**id), Ok(TaskStatus::Finished)))
+        .count();
+    let forgotten = ids
+        .iter()
+        .filter(|id| {
+            matches!(
+                scheduler.task_status(**id),
+                Err(Error::UnknownTask(_) | Error::StaleTask(_))
+            )
+        })
+        .count();
+    assert_eq!(remembered, 1024);
+    assert_eq!(forgotten, 1100 - 1024);
+}
+
+#[test]
+fn dependents_still_run_after_their_upstream_is_reaped() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let upstream = scheduler
+        .add_sync_task(SpyingTask::default(), soon())
+        .unwrap();
+    let dependent = SpyingTask::default();
+    let counter = dependent.counter();
+    scheduler
+        .add_sync_task_after(
+            dependent,
+            OneShot::after(Duration::ZERO),
+            [Dependency::on_completion(upstream)],
+        )
+        .unwrap();
+
+    sleep(Duration::from_millis(100));
+    assert_eq!(counter.load(std::sync::atomic::Ordering::Relaxed), 1);
+}
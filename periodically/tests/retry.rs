@@ -0,0 +1,121 @@
+use periodically::{IntervalSchedule, RetryOnError, Schedule, Scheduler, Task};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread::sleep,
+    time::{Duration, Instant},
+};
+use tokio::runtime::Runtime;
+
+type Output = Result<(), ()>;
+
+const HOUR: Duration = Duration::from_secs(60 * 60);
+
+fn millis(millis: u64) -> Option<Duration> {
+    Some(Duration::from_millis(millis))
+}
+
+/// Fails its first `failures` runs, and succeeds afterwards.
+struct FlakyTask {
+    failures: usize,
+    runs: Arc<AtomicUsize>,
+}
+
+impl Task<Output> for FlakyTask {
+    fn run(&self) -> Output {
+        match self.runs.fetch_add(1, Ordering::Relaxed) < self.failures {
+            true => Err(()),
+            false => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn retries_are_counted_until_exhausted() {
+    let schedule = RetryOnError::new(IntervalSchedule::every(HOUR), 3, Duration::from_millis(10));
+
+    let delays: Vec<_> = (0..5).map(|_| schedule.next(Output::Err(()))).collect();
+    assert_eq!(
+        delays,
+        [millis(10), millis(10), millis(10), Some(HOUR), millis(10)]
+    );
+}
+
+#[test]
+fn backoff_grows_up_to_the_max_delay() {
+    let schedule = RetryOnError::new(IntervalSchedule::every(HOUR), 6, Duration::from_millis(10))
+        .with_multiplier(3.0)
+        .with_max_delay(Duration::from_millis(500));
+
+    let delays: Vec<_> = (0..7).map(|_| schedule.next(Output::Err(()))).collect();
+    assert_eq!(
+        delays,
+        [
+            millis(10),
+            millis(30),
+            millis(90),
+            millis(270),
+            millis(500),
+            millis(500),
+            Some(HOUR)
+        ]
+    );
+}
+
+#[test]
+fn retries_start_over_after_a_success() {
+    let schedule: &dyn Schedule<Output> =
+        &RetryOnError::new(IntervalSchedule::every(HOUR), 2, Duration::from_millis(10))
+            .with_multiplier(2.0);
+
+    assert_eq!(schedule.next(Output::Err(())), millis(10));
+    assert_eq!(schedule.next_on_task_panic(), millis(20));
+    assert_eq!(schedule.next(Output::Ok(())), Some(HOUR));
+    assert_eq!(schedule.next(Output::Err(())), millis(10));
+    assert_eq!(schedule.next(Output::Err(())), millis(20));
+    assert_eq!(schedule.next(Output::Err(())), Some(HOUR));
+}
+
+#[test]
+#[should_panic(expected = "multiplier must be finite and at least one")]
+fn shrinking_multipliers_are_rejected() {
+    let _ = RetryOnError::new(IntervalSchedule::every(HOUR), 2, Duration::from_secs(1))
+        .with_multiplier(0.5);
+}
+
+#[test]
+#[should_panic(expected = "multiplier must be finite and at least one")]
+fn nan_multipliers_are_rejected() {
+    let _ = RetryOnError::new(IntervalSchedule::every(HOUR), 2, Duration::from_secs(1))
+        .with_multiplier(f64::NAN);
+}
+
+#[test]
+fn failed_runs_are_retried_by_the_scheduler() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let task = FlakyTask {
+        failures: 3,
+        runs: runs.clone(),
+    };
+    let schedule = RetryOnError::new(
+        IntervalSchedule::with_initial_delay(HOUR, Duration::ZERO),
+        5,
+        Duration::from_millis(10),
+    )
+    .with_multiplier(2.0);
+
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    scheduler.add_sync_task(task, schedule).unwrap();
+
+    // three failures, each retried sooner than the hourly schedule, then a success
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while runs.load(Ordering::Relaxed) < 4 && Instant::now() < deadline {
+        sleep(Duration::from_millis(10));
+    }
+    assert_eq!(runs.load(Ordering::Relaxed), 4);
+    // once it succeeds, the next run is an hour away
+    sleep(Duration::from_millis(200));
+    assert_eq!(runs.load(Ordering::Relaxed), 4);
+}
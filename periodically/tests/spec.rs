@@ -0,0 +1,87 @@
+#![cfg(feature = "serde")]
+
+use periodically::{Schedule, ScheduleSpec};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn every(spec: &str) -> Duration {
+    match spec.parse::<ScheduleSpec>().unwrap() {
+        ScheduleSpec::Every(interval) => interval,
+        other => panic!("expected an interval from [{spec}], got {other:?}"),
+    }
+}
+
+fn reason(spec: &str) -> String {
+    spec.parse::<ScheduleSpec>()
+        .unwrap_err()
+        .reason()
+        .to_owned()
+}
+
+#[test]
+fn parses_durations() {
+    assert_eq!(every("5m"), Duration::from_secs(5 * 60));
+    assert_eq!(every("1h30m"), Duration::from_secs(90 * 60));
+    assert_eq!(every("1h 30m 15s"), Duration::from_secs(90 * 60 + 15));
+    assert_eq!(every("250ms"), Duration::from_millis(250));
+    assert_eq!(every("2 days"), Duration::from_secs(2 * 24 * 60 * 60));
+    assert_eq!(every("every 10 seconds"), Duration::from_secs(10));
+    assert_eq!(every("Every Hour"), Duration::from_secs(60 * 60));
+    assert_eq!(every("1 h 2 m 3 s"), Duration::from_secs(3723));
+}
+
+#[test]
+fn parses_timestamps() {
+    let spec: ScheduleSpec = "at 2030-01-01T00:00:00+01:00".parse().unwrap();
+    let expected = UNIX_EPOCH + Duration::from_secs(1_893_452_400);
+    assert_eq!(spec, ScheduleSpec::At(expected));
+
+    let schedule = spec.into_schedule::<()>();
+    assert!(schedule.follows_wall_clock());
+    let delay = expected.duration_since(SystemTime::now()).unwrap();
+    assert!(schedule.initial().unwrap() <= delay);
+    assert_eq!(schedule.next(()), None);
+}
+
+#[cfg(feature = "cron")]
+#[test]
+fn parses_cron() {
+    let hourly: ScheduleSpec = "@hourly".parse().unwrap();
+    assert!(matches!(hourly, ScheduleSpec::Cron(_)));
+    let schedule = hourly.into_schedule::<()>();
+    assert!(schedule.initial().unwrap() <= Duration::from_secs(60 * 60));
+
+    let expression: ScheduleSpec = "0 */5 * * * *".parse().unwrap();
+    assert!(matches!(expression, ScheduleSpec::Cron(_)));
+}
+
+#[test]
+fn explains_invalid_specs() {
+    assert_eq!(reason(""), "the schedule is empty");
+    assert_eq!(
+        reason("10"),
+        "missing unit after [10], expected one of ms, s, m, h, d, w"
+    );
+    assert_eq!(
+        reason("3 parsecs"),
+        "unknown unit [parsecs], expected one of ms, s, m, h, d, w"
+    );
+    assert_eq!(reason("0s"), "the interval must be longer than zero");
+    assert!(reason("at tomorrow").starts_with("invalid timestamp [tomorrow]"));
+}
+
+#[test]
+fn deserializes_from_strings() {
+    let specs: Vec<ScheduleSpec> = serde_json::from_str(r#"["5m", "every 2 hours"]"#).unwrap();
+    assert_eq!(
+        specs,
+        vec![
+            ScheduleSpec::Every(Duration::from_secs(5 * 60)),
+            ScheduleSpec::Every(Duration::from_secs(2 * 60 * 60)),
+        ]
+    );
+
+    let error = serde_json::from_str::<ScheduleSpec>(r#""5 fortnights""#).unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("invalid schedule [5 fortnights]: unknown unit [fortnights]"));
+}
@@ -0,0 +1,212 @@
+pub mod tasks;
+
+use periodically::{
+    Error, IntervalSchedule, OneShot, Schedule, ScheduleFailure, Scheduler, Supervision,
+    TaskIdentifier, TaskStatus,
+};
+use std::{
+    cell::Cell,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread::sleep,
+    time::Duration,
+};
+use tasks::{schedulers, SpyingTask};
+use tokio::runtime::Runtime;
+
+/// Panics once it has been consulted after `runs` runs.
+struct Fragile {
+    runs: usize,
+    seen: Cell<usize>,
+}
+
+impl Fragile {
+    fn breaking_after(runs: usize) -> Self {
+        Self {
+            runs,
+            seen: Cell::new(0),
+        }
+    }
+}
+
+impl Schedule<()> for Fragile {
+    fn initial(&self) -> Option<Duration> {
+        Some(Duration::from_millis(10))
+    }
+
+    fn next(&self, _: ()) -> Option<Duration> {
+        let seen = self.seen.get() + 1;
+        self.seen.set(seen);
+        if seen >= self.runs {
+            panic!("fragile schedule broke");
+        }
+        Some(Duration::from_millis(10))
+    }
+}
+
+#[test]
+fn panicking_schedules_mark_their_task_failed() {
+    for mut scheduler in schedulers() {
+        let task = SpyingTask::default();
+        let counter = task.counter();
+        let id = scheduler
+            .add_sync_task(task, Fragile::breaking_after(3))
+            .unwrap();
+
+        sleep(Duration::from_millis(100));
+        assert_eq!(counter.load(Ordering::Relaxed), 3);
+        let Ok(TaskStatus::Failed(failure)) = scheduler.task_status(id) else {
+            panic!("the task should have failed");
+        };
+        assert_eq!(failure.message(), Some("fragile schedule broke"));
+        assert!(matches!(
+            scheduler.cancel_task(id),
+            Err(Error::AlreadyFinished(_))
+        ));
+        // finished tasks are reaped, but remembered
+        assert!(matches!(
+            scheduler.task_status(id),
+            Ok(TaskStatus::Failed(_))
+        ));
+    }
+}
+
+#[test]
+fn restarted_tasks_get_a_fresh_schedule() {
+    for mut scheduler in schedulers() {
+        let task = SpyingTask::default();
+        let counter = task.counter();
+        let made = Arc::new(AtomicUsize::new(0));
+        let id = scheduler
+            .task(task)
+            .schedule_factory({
+                let made = made.clone();
+                move || {
+                    made.fetch_add(1, Ordering::SeqCst);
+                    Fragile::breaking_after(2)
+                }
+            })
+            .supervision(Supervision::Restart { max_restarts: 1 })
+            .register()
+            .unwrap();
+
+        sleep(Duration::from_millis(150));
+        assert!(made.load(Ordering::SeqCst) > 2);
+        assert!(counter.load(Ordering::Relaxed) > 4);
+        assert!(matches!(
+            scheduler.task_status(id),
+            Ok(TaskStatus::Scheduled | TaskStatus::Running)
+        ));
+    }
+}
+
+#[test]
+fn restarts_are_limited_to_a_streak() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let task = SpyingTask::default();
+    let counter = task.counter();
+    let made = Arc::new(AtomicUsize::new(0));
+    let id = scheduler
+        .task(task)
+        .schedule_factory({
+            let made = made.clone();
+            // only the first schedule manages to start
+            move || match made.fetch_add(1, Ordering::SeqCst) {
+                0 => Fragile::breaking_after(1),
+                _ => panic!("no more schedules"),
+            }
+        })
+        .supervision(Supervision::Restart { max_restarts: 2 })
+        .register()
+        .unwrap();
+
+    sleep(Duration::from_millis(100));
+    assert_eq!(counter.load(Ordering::Relaxed), 1);
+    assert_eq!(made.load(Ordering::SeqCst), 3);
+    let Ok(TaskStatus::Failed(failure)) = scheduler.task_status(id) else {
+        panic!("the task should have failed");
+    };
+    assert_eq!(failure.message(), Some("no more schedules"));
+}
+
+#[test]
+fn failures_are_escalated() {
+    for mut scheduler in schedulers() {
+        let (sender, escalated) = mpsc::channel::<(TaskIdentifier, ScheduleFailure)>();
+        let id = scheduler
+            .task(SpyingTask::default())
+            .schedule(Fragile::breaking_after(1))
+            .supervision(Supervision::escalate(move |id, failure| {
+                sender.send((id, failure.clone())).unwrap()
+            }))
+            .register()
+            .unwrap();
+
+        let (escalated_id, failure) = escalated.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(escalated_id, id);
+        assert_eq!(
+            failure.to_string(),
+            "schedule panicked: fragile schedule broke"
+        );
+        assert!(escalated.recv_timeout(Duration::from_millis(50)).is_err());
+        assert_eq!(
+            scheduler.task_status(id).unwrap(),
+            TaskStatus::Failed(failure)
+        );
+    }
+}
+
+#[test]
+fn restarting_requires_a_schedule_factory() {
+    let mut scheduler = Scheduler::tokio_scheduler(Runtime::new().unwrap());
+    let result = scheduler
+        .task(SpyingTask::default())
+        .schedule(IntervalSchedule::every(Duration::from_millis(10)))
+        .supervision(Supervision::Restart { max_restarts: 1 })
+        .register();
+    assert!(matches!(result, Err(Error::InvalidOptions(_))));
+}
+
+#[test]
+fn status_follows_the_task() {
+    for mut scheduler in schedulers() {
+        let id = scheduler
+            .add_sync_task(
+                SpyingTask::default(),
+                IntervalSchedule::every(Duration::from_secs(60)),
+            )
+            .unwrap();
+        assert_eq!(scheduler.task_status(id).unwrap(), TaskStatus::Scheduled);
+        scheduler.pause_task(id).unwrap();
+        assert_eq!(scheduler.task_status(id).unwrap(), TaskStatus::Paused);
+
+        let once = scheduler
+            .add_sync_task(
+                SpyingTask::default(),
+                OneShot::after(Duration::from_millis(10)),
+            )
+            .unwrap();
+        sleep(Duration::from_millis(50));
+        assert_eq!(scheduler.task_status(once).unwrap(), TaskStatus::Finished);
+    }
+}
+
+#[test]
+fn on_finish_is_told_about_failures() {
+    for mut scheduler in schedulers() {
+        let (sender, finished) = std::sync::mpsc::channel();
+        scheduler
+            .task(SpyingTask::default())
+            .schedule(Fragile::breaking_after(1))
+            .on_finish(move |_, status| sender.send(status).unwrap())
+            .register()
+            .unwrap();
+
+        let status = finished.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(
+            matches!(status, TaskStatus::Failed(failure) if failure.message() == Some("fragile schedule broke"))
+        );
+    }
+}
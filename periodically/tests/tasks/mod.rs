@@ -1,5 +1,7 @@
 mod panicking_task;
+mod schedulers;
 mod spying_task;
 
 pub use panicking_task::PanickingTask;
+pub use schedulers::schedulers;
 pub use spying_task::SpyingTask;
@@ -26,7 +26,11 @@ impl PanickingTask {
 
 impl Task for PanickingTask {
     fn run(&self) {
-        if self.counter.fetch_add(1, Ordering::Relaxed) % self.modulo == 0 {
+        if self
+            .counter
+            .fetch_add(1, Ordering::Relaxed)
+            .is_multiple_of(self.modulo)
+        {
             panic!("Task panicked!");
         }
     }
@@ -34,7 +38,11 @@ impl Task for PanickingTask {
 
 impl AsyncTask for PanickingTask {
     async fn run(&self) {
-        if self.counter.fetch_add(1, Ordering::Relaxed) % self.modulo == 0 {
+        if self
+            .counter
+            .fetch_add(1, Ordering::Relaxed)
+            .is_multiple_of(self.modulo)
+        {
             panic!("Task panicked!");
         }
     }
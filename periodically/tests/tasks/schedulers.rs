@@ -0,0 +1,10 @@
+use periodically::Scheduler;
+use tokio::runtime::Runtime;
+
+/// A scheduler for each way of driving tasks, for tests which should pass with either.
+pub fn schedulers() -> [Scheduler; 2] {
+    [
+        Scheduler::tokio_scheduler(Runtime::new().unwrap()),
+        Scheduler::tokio_scheduler(Runtime::new().unwrap()).with_timer_driver(),
+    ]
+}
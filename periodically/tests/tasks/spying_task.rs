@@ -16,7 +16,7 @@ impl SpyingTask {
 }
 
 impl Task for SpyingTask {
-    fn run(&self) -> () {
+    fn run(&self) {
         self.count.fetch_add(1, Ordering::Relaxed);
     }
 }
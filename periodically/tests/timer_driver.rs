@@ -0,0 +1,189 @@
+pub mod tasks;
+
+use periodically::{
+    AsyncTask, Dependency, Error, IntervalSchedule, OneShot, Scheduler, TaskFailure,
+};
+use std::{
+    sync::atomic::Ordering,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+use tasks::{PanickingTask, SpyingTask};
+use tokio::runtime::Runtime;
+
+struct Stuck;
+
+impl AsyncTask for Stuck {
+    async fn run(&self) {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+    }
+}
+
+fn driven_scheduler() -> Scheduler {
+    Scheduler::tokio_scheduler(Runtime::new().unwrap()).with_timer_driver()
+}
+
+#[test]
+fn driven_tasks_run_on_their_schedule() {
+    let mut scheduler = driven_scheduler();
+    let sync_task = SpyingTask::default();
+    let sync_counter = sync_task.counter();
+    let async_task = SpyingTask::default();
+    let async_counter = async_task.counter();
+    let every = || IntervalSchedule::every(Duration::from_millis(10));
+
+    scheduler.add_sync_task(sync_task, every()).unwrap();
+    scheduler.add_async_task(async_task, every()).unwrap();
+
+    sleep(Duration::from_millis(100));
+    assert!(sync_counter.load(Ordering::Relaxed) >= 5);
+    assert!(async_counter.load(Ordering::Relaxed) >= 5);
+}
+
+#[test]
+fn many_driven_tasks_all_run() {
+    let mut scheduler = driven_scheduler();
+    let counters: Vec<_> = (0..10_000)
+        .map(|_| {
+            let task = SpyingTask::default();
+            let counter = task.counter();
+            let schedule = OneShot::after(Duration::from_millis(10));
+            scheduler.add_async_task(task, schedule).unwrap();
+            counter
+        })
+        .collect();
+
+    sleep(Duration::from_millis(500));
+    assert!(counters
+        .iter()
+        .all(|counter| counter.load(Ordering::Relaxed) == 1));
+}
+
+#[test]
+fn panicking_driven_task_keeps_running() {
+    let mut scheduler = driven_scheduler();
+    let task = PanickingTask::with_modulo(2);
+    let counter = task.counter();
+    let schedule = IntervalSchedule::every(Duration::from_millis(10));
+    scheduler.add_async_task(task, schedule).unwrap();
+
+    // panics are slow to report with backtraces enabled, so wait on the runs rather than the clock
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while counter.load(Ordering::Acquire) < 5 && Instant::now() < deadline {
+        sleep(Duration::from_millis(10));
+    }
+    assert!(counter.load(Ordering::Acquire) >= 5);
+}
+
+#[test]
+fn dropping_the_scheduler_stops_tasks_on_a_borrowed_runtime() {
+    let runtime = Runtime::new().unwrap();
+    for driven in [false, true] {
+        let mut scheduler = Scheduler::tokio_scheduler_with_handle(runtime.handle().clone());
+        if driven {
+            scheduler = scheduler.with_timer_driver();
+        }
+        let task = SpyingTask::default();
+        let counter = task.counter();
+        scheduler
+            .add_sync_task(task, IntervalSchedule::every(Duration::from_millis(10)))
+            .unwrap();
+
+        sleep(Duration::from_millis(50));
+        drop(scheduler);
+        // a run which already started may still finish
+        sleep(Duration::from_millis(20));
+        let count = counter.load(Ordering::Relaxed);
+        assert!(count > 0);
+        sleep(Duration::from_millis(50));
+        assert_eq!(counter.load(Ordering::Relaxed), count);
+    }
+}
+
+#[test]
+fn driven_tasks_pause_resume_and_trigger() {
+    let mut scheduler = driven_scheduler();
+    let task = SpyingTask::default();
+    let counter = task.counter();
+    let id = scheduler
+        .task(task)
+        .schedule(IntervalSchedule::every(Duration::from_secs(60)))
+        .start_paused()
+        .register()
+        .unwrap();
+
+    scheduler.trigger_task(id).unwrap();
+    sleep(Duration::from_millis(50));
+    assert_eq!(counter.load(Ordering::Relaxed), 0);
+
+    scheduler.resume_task(id).unwrap();
+    sleep(Duration::from_millis(50));
+    assert_eq!(counter.load(Ordering::Relaxed), 1);
+
+    scheduler.trigger_task(id).unwrap();
+    sleep(Duration::from_millis(50));
+    assert_eq!(counter.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn driven_tasks_finish_and_cancel() {
+    let mut scheduler = driven_scheduler();
+    let once = scheduler
+        .add_async_task(SpyingTask::default(), OneShot::after(Duration::ZERO))
+        .unwrap();
+    let task = SpyingTask::default();
+    let counter = task.counter();
+    let repeating = scheduler
+        .add_async_task(task, IntervalSchedule::every(Duration::from_millis(10)))
+        .unwrap();
+
+    sleep(Duration::from_millis(50));
+    assert!(matches!(
+        scheduler.pause_task(once),
+        Err(Error::AlreadyFinished(id)) if id == once
+    ));
+    scheduler.cancel_task(repeating).unwrap();
+    let runs = counter.load(Ordering::Relaxed);
+    sleep(Duration::from_millis(50));
+    assert_eq!(counter.load(Ordering::Relaxed), runs);
+}
+
+#[test]
+fn driven_tasks_time_out_and_publish_outputs() {
+    let mut scheduler = driven_scheduler();
+    let id = scheduler
+        .async_task(Stuck)
+        .schedule(IntervalSchedule::every(Duration::from_millis(10)))
+        .timeout(Duration::from_millis(10))
+        .register()
+        .unwrap();
+    let mut outputs = scheduler.subscribe::<()>(id).unwrap();
+
+    assert_eq!(
+        outputs.blocking_recv().unwrap(),
+        Err(TaskFailure::TimedOut(Duration::from_millis(10)))
+    );
+}
+
+#[test]
+fn driven_tasks_trigger_their_dependents() {
+    let mut scheduler = driven_scheduler();
+    let upstream = scheduler
+        .add_sync_task(
+            SpyingTask::default(),
+            IntervalSchedule::every(Duration::from_millis(10)),
+        )
+        .unwrap();
+    let dependent = SpyingTask::default();
+    let counter = dependent.counter();
+    scheduler
+        .add_sync_task_after(
+            dependent,
+            IntervalSchedule::every(Duration::ZERO),
+            [Dependency::on_success(upstream)],
+        )
+        .unwrap();
+
+    sleep(Duration::from_millis(100));
+    assert!(counter.load(Ordering::Relaxed) >= 5);
+}
@@ -0,0 +1,18 @@
+use periodically::{AsyncTask, IntervalSchedule, Scheduler};
+use std::{rc::Rc, time::Duration};
+
+struct Shared;
+
+impl AsyncTask<Rc<u32>> for Shared {
+    async fn run(&self) -> Rc<u32> {
+        Rc::new(1)
+    }
+}
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut scheduler = Scheduler::tokio_scheduler(runtime);
+    let schedule = IntervalSchedule::every(Duration::from_secs(60));
+
+    scheduler.add_async_task(Shared, schedule).unwrap();
+}
@@ -0,0 +1,26 @@
+use periodically::{AsyncTask, IntervalSchedule, Scheduler, Task};
+use std::{error::Error, time::Duration};
+
+struct Fallible;
+
+impl Task<Result<(), Box<dyn Error + Send>>> for Fallible {
+    fn run(&self) -> Result<(), Box<dyn Error + Send>> {
+        Ok(())
+    }
+}
+
+impl AsyncTask<Result<(), Box<dyn Error + Send>>> for Fallible {
+    async fn run(&self) -> Result<(), Box<dyn Error + Send>> {
+        Ok(())
+    }
+}
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut scheduler = Scheduler::tokio_scheduler(runtime);
+    let every = || IntervalSchedule::every(Duration::from_secs(60));
+
+    scheduler.add_sync_task(Fallible, every()).unwrap();
+    scheduler.add_async_task(Fallible, every()).unwrap();
+    scheduler.task(Fallible).schedule(every()).register().unwrap();
+}
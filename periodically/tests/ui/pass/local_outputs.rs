@@ -0,0 +1,22 @@
+use periodically::{IntervalSchedule, LocalAsyncTask, Scheduler};
+use std::{cell::Cell, rc::Rc, time::Duration};
+use tokio::task::LocalSet;
+
+struct Counter(Rc<Cell<u32>>);
+
+impl LocalAsyncTask<Cell<u32>> for Counter {
+    async fn run(&self) -> Cell<u32> {
+        Cell::new(self.0.get())
+    }
+}
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut scheduler = Scheduler::tokio_scheduler(runtime);
+    let local_set = LocalSet::new();
+    let schedule = IntervalSchedule::every(Duration::from_secs(60));
+
+    scheduler
+        .add_local_task(&local_set, Counter(Rc::default()), schedule)
+        .unwrap();
+}
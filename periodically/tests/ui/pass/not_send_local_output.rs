@@ -0,0 +1,20 @@
+use periodically::{IntervalSchedule, LocalAsyncTask, Scheduler};
+use std::{rc::Rc, time::Duration};
+use tokio::task::LocalSet;
+
+struct Shared;
+
+impl LocalAsyncTask<Rc<u32>> for Shared {
+    async fn run(&self) -> Rc<u32> {
+        Rc::new(1)
+    }
+}
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut scheduler = Scheduler::tokio_scheduler(runtime);
+    let local_set = LocalSet::new();
+    let schedule = IntervalSchedule::every(Duration::from_secs(60));
+
+    scheduler.add_local_task(&local_set, Shared, schedule).unwrap();
+}
@@ -0,0 +1,30 @@
+use periodically::{AsyncTask, IntervalSchedule, Scheduler, Task};
+use std::{cell::Cell, time::Duration};
+
+struct Counter;
+
+impl Task<Cell<u32>> for Counter {
+    fn run(&self) -> Cell<u32> {
+        Cell::new(1)
+    }
+}
+
+impl AsyncTask<Cell<u32>> for Counter {
+    async fn run(&self) -> Cell<u32> {
+        Cell::new(1)
+    }
+}
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut scheduler = Scheduler::tokio_scheduler(runtime);
+    let every = || IntervalSchedule::every(Duration::from_secs(60));
+
+    scheduler.add_sync_task(Counter, every()).unwrap();
+    scheduler.add_async_task(Counter, every()).unwrap();
+    scheduler.add_named_async_task("counter", Counter, every()).unwrap();
+    scheduler.async_task(Counter).schedule(every()).register().unwrap();
+
+    let handle = scheduler.handle();
+    let _ = handle.add_async_task(Counter, every());
+}
@@ -0,0 +1,149 @@
+#![cfg(feature = "calendar")]
+
+use chrono::{DateTime, FixedOffset, NaiveTime, TimeZone, Utc};
+use periodically::{IntervalSchedule, Schedule, TimeWindows, Windowed};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn time(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+}
+
+/// The local times of day at which `schedule` would run, starting at the epoch.
+fn preview_times<T>(
+    schedule: &impl Schedule<T>,
+    timezone: &FixedOffset,
+    outputs: impl FnMut() -> T,
+    runs: usize,
+) -> Vec<NaiveTime> {
+    schedule
+        .preview(UNIX_EPOCH, outputs)
+        .take(runs)
+        .map(|at: SystemTime| DateTime::<Utc>::from(at).with_timezone(timezone).time())
+        .collect()
+}
+
+#[test]
+fn allowed_windows_span_midnight() {
+    let windows = TimeWindows::new(Utc).allow(time(22, 0), time(6, 0));
+
+    let evening = Utc.with_ymd_and_hms(2024, 3, 1, 23, 0, 0).unwrap();
+    assert_eq!(windows.next_allowed(&evening), Some(evening));
+    let morning = Utc.with_ymd_and_hms(2024, 3, 2, 5, 59, 0).unwrap();
+    assert_eq!(windows.next_allowed(&morning), Some(morning));
+    let day = Utc.with_ymd_and_hms(2024, 3, 2, 6, 0, 0).unwrap();
+    assert_eq!(
+        windows.next_allowed(&day),
+        Some(Utc.with_ymd_and_hms(2024, 3, 2, 22, 0, 0).unwrap())
+    );
+}
+
+#[test]
+fn blackouts_take_precedence_over_allowed_windows() {
+    let windows = TimeWindows::new(Utc)
+        .allow(time(9, 0), time(17, 0))
+        .block(time(12, 0), time(13, 0))
+        .block(time(12, 30), time(14, 0));
+
+    let lunch = Utc.with_ymd_and_hms(2024, 3, 1, 12, 15, 0).unwrap();
+    assert_eq!(
+        windows.next_allowed(&lunch),
+        Some(Utc.with_ymd_and_hms(2024, 3, 1, 14, 0, 0).unwrap())
+    );
+    let evening = Utc.with_ymd_and_hms(2024, 3, 1, 18, 0, 0).unwrap();
+    assert_eq!(
+        windows.next_allowed(&evening),
+        Some(Utc.with_ymd_and_hms(2024, 3, 2, 9, 0, 0).unwrap())
+    );
+}
+
+#[test]
+fn fully_blocked_windows_never_allow_anything() {
+    let windows = TimeWindows::new(Utc)
+        .allow(time(9, 0), time(10, 0))
+        .block(time(8, 0), time(11, 0));
+    let at = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+    assert_eq!(windows.next_allowed(&at), None);
+
+    let schedule = Windowed::new(IntervalSchedule::every(Duration::from_secs(60)), windows);
+    let mut times = schedule.preview(UNIX_EPOCH, || ());
+    assert_eq!(times.next(), None);
+}
+
+#[test]
+fn windows_are_observed_in_their_timezone() {
+    let tokyo = FixedOffset::east_opt(9 * 60 * 60).unwrap();
+    let windows = TimeWindows::new(tokyo).allow(time(9, 0), time(17, 0));
+
+    // 02:00 UTC is 11:00 in Tokyo
+    let at = Utc.with_ymd_and_hms(2024, 3, 1, 2, 0, 0).unwrap();
+    assert_eq!(
+        windows.next_allowed(&at.with_timezone(&tokyo)),
+        Some(at.with_timezone(&tokyo))
+    );
+    // 09:00 UTC is 18:00 in Tokyo
+    let at = Utc.with_ymd_and_hms(2024, 3, 1, 9, 0, 0).unwrap();
+    let next = windows.next_allowed(&at.with_timezone(&tokyo)).unwrap();
+    assert_eq!(next, Utc.with_ymd_and_hms(2024, 3, 2, 0, 0, 0).unwrap());
+}
+
+#[test]
+fn intervals_only_run_during_office_hours() {
+    let paris = FixedOffset::east_opt(60 * 60).unwrap();
+    let schedule = Windowed::new(
+        IntervalSchedule::every(Duration::from_secs(3 * 60 * 60)),
+        TimeWindows::new(paris).allow(time(9, 0), time(17, 0)),
+    );
+
+    // the epoch is 01:00 in Paris
+    let times = preview_times(&schedule, &paris, || (), 5);
+    assert_eq!(
+        times,
+        vec![
+            time(9, 0),
+            time(12, 0),
+            time(15, 0),
+            time(9, 0),
+            time(12, 0)
+        ]
+    );
+}
+
+#[cfg(feature = "cron")]
+#[test]
+fn cron_slots_in_a_blackout_are_pushed_back() {
+    use periodically::CronSchedule;
+
+    let utc = FixedOffset::east_opt(0).unwrap();
+    // every 20 minutes, except during the nightly backup
+    let schedule = Windowed::new(
+        CronSchedule::from_cron_str("0 */20 * * * * *").unwrap(),
+        TimeWindows::new(Utc).block(time(0, 30), time(1, 10)),
+    );
+
+    let times = preview_times(&schedule, &utc, || (), 4);
+    assert_eq!(
+        times,
+        vec![time(0, 20), time(1, 10), time(1, 20), time(1, 40)]
+    );
+}
+
+#[cfg(feature = "backoff")]
+#[test]
+fn backoff_retries_wait_for_the_window() {
+    use backoff::backoff::Constant;
+    use chrono::Timelike;
+    use periodically::BackoffSchedule;
+
+    let utc = FixedOffset::east_opt(0).unwrap();
+    let schedule = Windowed::new(
+        BackoffSchedule::from_backoff(Constant::new(Duration::from_secs(45 * 60))),
+        TimeWindows::new(Utc).allow(time(1, 0), time(2, 0)),
+    );
+
+    let times = preview_times(&schedule, &utc, || Err::<(), ()>(()), 4);
+    assert_eq!(
+        times,
+        vec![time(1, 0), time(1, 45), time(1, 0), time(1, 45)]
+    );
+    assert!(times.iter().all(|at| at.hour() == 1));
+}